@@ -1,231 +1,895 @@
-use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+// 未启用相应特性时注册表为空，只为其服务的用例构造与容量参数不会被使用
+#![cfg_attr(
+    not(all(
+        any(feature = "dashmap", feature = "hashlink", feature = "lru", feature = "moka"),
+        any(feature = "hashlink", feature = "lru", feature = "mini-moka", feature = "quick_cache", feature = "schnellru")
+    )),
+    allow(dead_code, unused_variables)
+)]
+
+use bytes::Bytes;
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{BatchSize, Criterion, Throughput};
+#[cfg(feature = "hashlink")]
 use hashlink::LruCache as HashlinkLruCache;
-use lru::LruCache;
-use mini_moka::unsync::Cache as MokaCache;
-use std::time::Duration;
+#[cfg(feature = "moka")]
+use moka::future::Cache as MokaFutureCache;
+#[cfg(feature = "moka")]
+use moka::sync::Cache as MokaSyncCache;
+#[cfg(feature = "quick_cache")]
+use quick_cache::unsync::Cache as QuickCache;
+#[cfg(feature = "schnellru")]
+use schnellru::{ByLength, LruMap as SchnellLruMap};
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // 导入项目模块
-use cache_bench::cache::{CacheRunner, WorkloadGenerator, WarmupManager, Op};
-use cache_bench::config::{CACHE_CAPACITY, WORKLOAD_SIZE, bench, messages};
-use cache_bench::error::{AppError, ErrorContext};
+use cache_bench::analysis::opt;
+use cache_bench::config::{CACHE_CAPACITY, bench, messages};
+use cache_bench::{
+    AsyncAdapter, AsyncCacheOps, BenchRuntime, CacheKind, CacheOps, CacheResult, CacheRunner, Clock, ConcurrentAdapter,
+    ConcurrentCacheOps, Config, ErrorContext, Expiration, FixedLru, Gdsf, Key, LoadCounter, MemProbe, MockClock, NoCache,
+    Op, Report, RunStats, S3Fifo, Scenario, ScenarioMode, ScenarioRegistry, Sieve, SweepAxis, TimeSource, TtlCache, Value,
+    WarmupManager, WorkloadGenerator, belady, dogpile_workloads, render_table, replay_sync, sweep_zipf, unit_cost,
+};
+#[cfg(feature = "hashlink")]
+use cache_bench::{ArcCache, RwLockHashlink};
+#[cfg(feature = "dashmap")]
+use cache_bench::{CappedDashMap, MapEviction};
+#[cfg(feature = "lru")]
+use cache_bench::{MutexLru, ShardedLru, lru_cache};
+#[cfg(any(feature = "mini-moka", feature = "moka"))]
+use cache_bench::OptimizedMokaCacheBuilder;
+#[cfg(feature = "mini-moka")]
+use cache_bench::{ENTRY_HEADER, SizeSampler};
+
+#[cfg(feature = "mem")]
+#[global_allocator]
+static ALLOC: cache_bench::CountingAlloc = cache_bench::CountingAlloc;
 
-pub struct CompioExecutor;
+/// Criterion 异步执行器，复用基准函数创建的运行时
+pub struct CompioExecutor<'a>(pub &'a BenchRuntime);
 
-impl criterion::async_executor::AsyncExecutor for CompioExecutor {
+impl criterion::async_executor::AsyncExecutor for CompioExecutor<'_> {
     fn block_on<T>(&self, future: impl std::future::Future<Output = T>) -> T {
-        // 为每个 bench iteration 创建一个新的轻量级 runtime
-        let runtime = compio::runtime::Runtime::new()
-            .expect("Failed to create Compio runtime in executor");
-        runtime.block_on(future)
+        self.0.block_on(future)
     }
 }
 
-// 操作类型已在 cache.rs 中定义，直接使用
-
-// 使用导入的函数和类型
-
 // ----------------------------------------------------------------
-// 各缓存实现
+// 基准用例：每个基准组由一组用例生成，先逐个运行一次输出检查结果，再每个用例注册一个基准
 // ----------------------------------------------------------------
-async fn run_hashlink(ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    let cache = HashlinkLruCache::new(CACHE_CAPACITY as usize);
-    CacheRunner::run_cache(cache, ops).await
+
+/// 计时的一次运行，运行时由基准组创建并在各用例间共享
+type Run<'a> = Box<dyn FnMut(&BenchRuntime) -> RunStats + 'a>;
+
+/// 基准用例：`prepare` 新建缓存并完成不计时的准备，返回计时的一次运行；运行结束后缓存的释放不计时
+struct Case<'a> {
+    name: String,
+    prepare: Box<dyn Fn() -> Run<'a> + 'a>,
+}
+
+impl<'a> Case<'a> {
+    fn new<R: FnMut(&BenchRuntime) -> RunStats + 'a>(name: impl Into<String>, prepare: impl Fn() -> R + 'a) -> Self {
+        Self {
+            name: name.into(),
+            prepare: Box::new(move || Box::new(prepare())),
+        }
+    }
+}
+
+/// 场景的运行器与操作序列，由各用例共享
+struct Env<V = usize, K = usize> {
+    runner: CacheRunner,
+    warmup_ops: Vec<Op<V, K>>,
+    ops: Vec<Op<V, K>>,
+}
+
+impl<V: Value, K: Key> Env<V, K> {
+    /// 以 `ops` 为工作负载，按配置生成预热序列与运行器
+    fn new(cfg: &Config, ops: cache_bench::Result<Vec<Op<V, K>>>) -> Self {
+        let ops = ops.with_context(messages::WORKLOAD_GEN_FAILED).expect("Failed to generate workload");
+        let warmup_ops = WarmupManager::new(cfg.warmup())
+            .generate_warmup_ops_for(&ops)
+            .with_context(messages::WARMUP_FAILED)
+            .expect("Failed to generate warmup ops");
+        Self { runner: CacheRunner::from_config(cfg), warmup_ops, ops }
+    }
+
+    /// 按配置生成 `V`、`K` 类型的工作负载
+    fn generate(cfg: &Config) -> Self {
+        Self::new(cfg, WorkloadGenerator::new(cfg.workload.clone()).generate())
+    }
+
+    /// 预热 + 运行单线程缓存，预热计入测量；`warm` 为 false 时冷启动
+    fn case<'a, C: CacheOps<V, K> + 'a>(&'a self, name: impl Into<String>, warm: bool, build: impl Fn() -> C + 'a) -> Case<'a> {
+        let warmup_ops: &[Op<V, K>] = if warm { &self.warmup_ops } else { &[] };
+        Case::new(name, move || {
+            let mut cache = build();
+            move |runtime: &BenchRuntime| {
+                WarmupManager::warmup_cache(&mut cache, warmup_ops);
+                expect_run(runtime.block_on(self.runner.run_cache(&mut cache, &self.ops)))
+            }
+        })
+    }
+
+    /// 同步回放、不模拟回源，预热不计入测量
+    fn micro<'a, C: CacheOps<V, K> + 'a>(&'a self, name: impl Into<String>, build: impl Fn() -> C + 'a) -> Case<'a> {
+        Case::new(name, move || {
+            let mut cache = build();
+            WarmupManager::warmup_cache(&mut cache, &self.warmup_ops);
+            move |_: &BenchRuntime| replay_sync(&mut cache, &self.ops)
+        })
+    }
+}
+
+impl Env {
+    /// 按名称构建、预热并运行，代价模型取自运行器
+    fn kind(&self, name: impl Into<String>, kind: CacheKind, capacity: u64) -> Case<'_> {
+        Case::new(name, move || {
+            move |runtime: &BenchRuntime| {
+                expect_run(runtime.block_on(kind.run(&self.runner, capacity, &self.warmup_ops, &self.ops)))
+            }
+        })
+    }
+
+    /// 预热 + 运行异步缓存，在运行时上 await
+    #[cfg(feature = "moka")]
+    fn async_case<'a, C: AsyncCacheOps<usize> + 'a>(&'a self, name: impl Into<String>, build: impl Fn() -> C + 'a) -> Case<'a> {
+        Case::new(name, move || {
+            let cache = build();
+            move |runtime: &BenchRuntime| {
+                expect_run(runtime.block_on(async {
+                    WarmupManager::warmup_async_cache(&cache, &self.warmup_ops).await;
+                    self.runner.run_async_cache(&cache, &self.ops).await
+                }))
+            }
+        })
+    }
+
+    /// 每个任务在同一线程上并发 get_or_load，不预热
+    fn loader<'a, C: AsyncCacheOps<usize> + 'a>(
+        &'a self,
+        name: impl Into<String>,
+        tasks: &'a [Vec<Op>],
+        build: impl Fn() -> C + 'a,
+    ) -> Case<'a> {
+        Case::new(name, move || {
+            let cache = build();
+            move |runtime: &BenchRuntime| expect_run(runtime.block_on(self.runner.run_async_loader(&cache, tasks)))
+        })
+    }
 }
 
-async fn run_lru(ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    let cache = LruCache::new(std::num::NonZeroUsize::new(CACHE_CAPACITY as usize).unwrap());
-    CacheRunner::run_cache(cache, ops).await
+fn expect_run(stats: cache_bench::Result<RunStats>) -> RunStats {
+    stats.with_context(messages::CACHE_OPERATION_FAILED).expect("Benchmark failed")
+}
+
+/// 读命中统计
+fn reads(hits: impl IntoIterator<Item = bool>) -> RunStats {
+    let mut stats = RunStats::default();
+    for hit in hits {
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+    }
+    stats.ops = stats.hits + stats.misses;
+    stats
 }
 
-async fn run_mini_moka(ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    let cache = cache_bench::cache::OptimizedMokaCacheBuilder::build_high_performance_cache();
-    CacheRunner::run_cache(cache, ops).await
+/// 输出一次运行的检查结果
+fn check(name: &str, stats: &RunStats) {
+    println!(
+        "{name:<40} hit rate {:>6.2}%  backend calls {:>8}  cache {:>8.1} ns/op",
+        stats.hit_rate(),
+        stats.backend_calls,
+        stats.cache_ns_per_op()
+    );
+}
+
+/// 注册一组用例：先逐个运行一次交给 `check` 输出，再每个用例一个基准，`elements` 为每次运行处理的元素数
+fn bench_group(c: &mut Criterion, name: &str, elements: usize, cases: &[Case], check: impl Fn(&str, &RunStats)) {
+    if cases.is_empty() {
+        return;
+    }
+    let runtime = BenchRuntime::new().expect(messages::RUNTIME_CREATE_FAILED);
+    println!("=== {name} ===");
+    for case in cases {
+        check(&case.name, &(case.prepare)()(&runtime));
+    }
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(bench::SAMPLE_SIZE);
+    group.measurement_time(Duration::from_secs(bench::MEASUREMENT_TIME_SECS));
+    group.throughput(Throughput::Elements(elements as u64));
+    for case in cases {
+        // 预热后的缓存占用较大，逐个构建
+        group.bench_function(&case.name, |b| {
+            b.iter_batched_ref(&case.prepare, |run| run(&runtime), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
 }
 
 // ----------------------------------------------------------------
-// 带预热的缓存函数（用于 benchmark）
+// 缓存注册表：基准组遍历注册的工厂，新增缓存只需一行注册
 // ----------------------------------------------------------------
-async fn run_hashlink_with_cache(cache: HashlinkLruCache<usize, usize>, ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    CacheRunner::run_cache(cache, ops).await
+
+/// 缓存工厂，按名称生成用例
+trait CacheFactory<V: Value = usize, K: Key = usize> {
+    /// 基准名称
+    fn name(&self) -> &str;
+
+    /// 预热 + 运行，`warm` 为 false 时冷启动
+    fn case<'a>(&'a self, env: &'a Env<V, K>, name: String, warm: bool) -> Case<'a>;
+
+    /// 同步回放的微基准
+    fn micro<'a>(&'a self, env: &'a Env<V, K>) -> Case<'a>;
+
+    /// 经 [`TtlCache`] 包装后预热 + 运行
+    fn ttl<'a>(&'a self, env: &'a Env<V, K>, expiration: Expiration) -> Case<'a>;
 }
 
-async fn run_lru_with_cache(cache: LruCache<usize, usize>, ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    CacheRunner::run_cache(cache, ops).await
+/// 多线程共享的缓存工厂，单线程用例经 [`ConcurrentAdapter`] 包装
+trait SharedFactory: CacheFactory {
+    /// 每个任务一个线程共享缓存，预热不计入测量
+    fn threads<'a>(&'a self, env: &'a Env, tasks: &'a [Vec<Op>], name: String) -> Case<'a>;
+
+    /// 经 [`AsyncAdapter`] 手工模拟 get_or_load
+    fn loader<'a>(&'a self, env: &'a Env, tasks: &'a [Vec<Op>]) -> Case<'a>;
+
+    /// 按批大小 `batch` 逐个调用与批量读写工作负载中的 key，不模拟回源，预热不计入测量
+    fn batch<'a>(&'a self, env: &'a Env, batch: usize) -> Vec<Case<'a>>;
 }
 
-async fn run_mini_moka_with_cache(cache: MokaCache<usize, usize>, ops: &[Op]) -> std::result::Result<(u64, u64), AppError> {
-    CacheRunner::run_cache(cache, ops).await
+/// 名称加构建闭包
+struct Factory<F> {
+    name: &'static str,
+    build: F,
+}
+
+impl<V: Value, K: Key, C: CacheOps<V, K> + 'static, F: Fn() -> C> CacheFactory<V, K> for Factory<F> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn case<'a>(&'a self, env: &'a Env<V, K>, name: String, warm: bool) -> Case<'a> {
+        env.case(name, warm, &self.build)
+    }
+
+    fn micro<'a>(&'a self, env: &'a Env<V, K>) -> Case<'a> {
+        env.micro(self.name, &self.build)
+    }
+
+    fn ttl<'a>(&'a self, env: &'a Env<V, K>, e: Expiration) -> Case<'a> {
+        env.case(format!("{}_ttl", self.name), true, move || TtlCache::new((self.build)(), e.ttl, e.tti))
+    }
+}
+
+/// 名称加构建线程安全缓存的闭包
+struct Shared<F> {
+    name: &'static str,
+    build: F,
+}
+
+impl<C: ConcurrentCacheOps<usize>, F: Fn() -> C> Shared<F> {
+    fn warmed(&self, env: &Env) -> C {
+        let cache = (self.build)();
+        WarmupManager::warmup_cache(&mut ConcurrentAdapter::new(&cache), &env.warmup_ops);
+        cache
+    }
+}
+
+impl<C: ConcurrentCacheOps<usize> + 'static, F: Fn() -> C> CacheFactory for Shared<F> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn case<'a>(&'a self, env: &'a Env, name: String, warm: bool) -> Case<'a> {
+        env.case(name, warm, || ConcurrentAdapter::new((self.build)()))
+    }
+
+    fn micro<'a>(&'a self, env: &'a Env) -> Case<'a> {
+        env.micro(self.name, || ConcurrentAdapter::new((self.build)()))
+    }
+
+    fn ttl<'a>(&'a self, env: &'a Env, e: Expiration) -> Case<'a> {
+        env.case(format!("{}_ttl", self.name), true, move || {
+            TtlCache::new(ConcurrentAdapter::new((self.build)()), e.ttl, e.tti)
+        })
+    }
+}
+
+impl<C: ConcurrentCacheOps<usize> + 'static, F: Fn() -> C> SharedFactory for Shared<F> {
+    fn threads<'a>(&'a self, env: &'a Env, tasks: &'a [Vec<Op>], name: String) -> Case<'a> {
+        Case::new(name, move || {
+            let cache = self.warmed(env);
+            move |_: &BenchRuntime| expect_run(env.runner.run_cache_threads(&cache, tasks))
+        })
+    }
+
+    fn loader<'a>(&'a self, env: &'a Env, tasks: &'a [Vec<Op>]) -> Case<'a> {
+        env.loader(format!("{}_manual", self.name), tasks, || AsyncAdapter((self.build)()))
+    }
+
+    fn batch<'a>(&'a self, env: &'a Env, batch: usize) -> Vec<Case<'a>> {
+        let name = self.name;
+        let entries = move || -> Vec<(usize, usize)> { env.ops.iter().map(|op| (*op.key(), usize::load(*op.key()))).collect() };
+        let keys = move || -> Vec<usize> { env.ops.iter().map(|op| *op.key()).collect() };
+        vec![
+            Case::new(format!("{name}_get_b{batch}"), move || {
+                let (cache, keys) = (self.warmed(env), keys());
+                move |_: &BenchRuntime| reads(keys.chunks(batch).flat_map(|c| c.iter().map(|k| cache.get(k).is_some())))
+            }),
+            Case::new(format!("{name}_get_many_b{batch}"), move || {
+                let (cache, keys) = (self.warmed(env), keys());
+                move |_: &BenchRuntime| reads(keys.chunks(batch).flat_map(|c| cache.get_many(c)))
+            }),
+            Case::new(format!("{name}_insert_b{batch}"), move || {
+                let (cache, entries) = (self.warmed(env), entries());
+                move |_: &BenchRuntime| {
+                    entries.chunks(batch).flatten().for_each(|&(key, value)| cache.insert(key, value));
+                    RunStats::default()
+                }
+            }),
+            Case::new(format!("{name}_insert_many_b{batch}"), move || {
+                let (cache, entries) = (self.warmed(env), entries());
+                move |_: &BenchRuntime| {
+                    entries.chunks(batch).for_each(|chunk| cache.insert_many(chunk));
+                    RunStats::default()
+                }
+            }),
+        ]
+    }
+}
+
+/// 注册一个缓存
+fn factory<V: Value, K: Key, C: CacheOps<V, K> + 'static>(
+    name: &'static str,
+    build: impl Fn() -> C + 'static,
+) -> Box<dyn CacheFactory<V, K>> {
+    Box::new(Factory { name, build })
+}
+
+/// 注册一个线程安全缓存
+fn shared<C: ConcurrentCacheOps<usize> + 'static>(name: &'static str, build: impl Fn() -> C + 'static) -> Box<dyn SharedFactory> {
+    Box::new(Shared { name, build })
+}
+
+/// 对任意键值类型实现 [`CacheOps`] 的缓存
+fn typed_registry<V: Value + 'static, K: Key + 'static>(capacity: u64) -> Vec<Box<dyn CacheFactory<V, K>>> {
+    vec![
+        #[cfg(feature = "hashlink")]
+        factory("hashlink_lru", move || HashlinkLruCache::new(capacity as usize)),
+        #[cfg(feature = "lru")]
+        factory("lru", move || lru_cache(capacity as usize).expect(messages::CACHE_BUILD_FAILED)),
+        #[cfg(feature = "mini-moka")]
+        factory("mini_moka_unsync_optimized", move || OptimizedMokaCacheBuilder::build_high_performance_cache(capacity)),
+        #[cfg(feature = "quick_cache")]
+        factory("quick_cache_unsync", move || QuickCache::new(capacity as usize)),
+        #[cfg(feature = "schnellru")]
+        factory("schnellru", move || SchnellLruMap::new(ByLength::new(capacity as u32))),
+    ]
+}
+
+/// 多线程共享的缓存
+fn shared_registry(capacity: u64) -> Vec<Box<dyn SharedFactory>> {
+    vec![
+        #[cfg(feature = "moka")]
+        shared("moka_sync", move || MokaSyncCache::new(capacity)),
+        // 关闭 TinyLFU 准入，与上一项对比准入策略的贡献
+        #[cfg(feature = "moka")]
+        shared("moka_sync_no_admission", move || OptimizedMokaCacheBuilder::sync_cache_builder(capacity, false).build()),
+        #[cfg(feature = "lru")]
+        shared("mutex_lru", move || MutexLru::new(capacity as usize).expect(messages::CACHE_BUILD_FAILED)),
+        #[cfg(feature = "hashlink")]
+        shared("rwlock_hashlink", move || RwLockHashlink::new(capacity as usize).expect(messages::CACHE_BUILD_FAILED)),
+        #[cfg(feature = "lru")]
+        shared("sharded_lru", move || ShardedLru::new(capacity as usize).expect(messages::CACHE_BUILD_FAILED)),
+        // 并发 map 加手工清理的伪缓存基线
+        #[cfg(feature = "dashmap")]
+        shared("dashmap_fifo", move || {
+            CappedDashMap::new(capacity as usize, MapEviction::Fifo).expect(messages::CACHE_BUILD_FAILED)
+        }),
+        #[cfg(feature = "dashmap")]
+        shared("dashmap_random", move || {
+            CappedDashMap::new(capacity as usize, MapEviction::Random).expect(messages::CACHE_BUILD_FAILED)
+        }),
+    ]
+}
+
+/// 单线程主基准组的缓存，只含已编译的实现，按名称排序
+fn registry(capacity: u64) -> Vec<Box<dyn CacheFactory>> {
+    let cap = capacity as usize;
+    let mut caches = typed_registry(capacity);
+    caches.extend(shared_registry(capacity).into_iter().map(|f| f as Box<dyn CacheFactory>));
+    caches.extend([
+        factory("s3_fifo", move || S3Fifo::new(cap).expect(messages::CACHE_BUILD_FAILED)),
+        factory("sieve", move || Sieve::new(cap).expect(messages::CACHE_BUILD_FAILED)),
+        factory("clock", move || Clock::new(cap).expect(messages::CACHE_BUILD_FAILED)),
+        #[cfg(feature = "hashlink")]
+        factory("arc", move || ArcCache::new(cap).expect(messages::CACHE_BUILD_FAILED)),
+        factory("gdsf", move || Gdsf::new(capacity, unit_cost).expect(messages::CACHE_BUILD_FAILED)),
+        // 锚点：不淘汰的 HashMap 与不缓存
+        factory("unbounded_hashmap", HashMap::new),
+        factory("no_cache", || NoCache),
+        #[cfg(feature = "mini-moka")]
+        factory("mini_moka_unsync_weigher", move || OptimizedMokaCacheBuilder::build_optimized_cache(capacity)),
+    ]);
+    // 编译期容量 LRU（容量固定为 CACHE_CAPACITY，配置不同时跳过以保证公平）
+    if capacity == CACHE_CAPACITY {
+        caches.push(factory("fixed_lru", FixedLru::<usize, { CACHE_CAPACITY as usize }>::new));
+    }
+    // 按名称排序，报告顺序与注册的特性组合、条件注册无关
+    caches.sort_by(|a, b| a.name().cmp(b.name()));
+    caches
 }
 
 // ----------------------------------------------------------------
 // Criterion Benchmark 设置
 // ----------------------------------------------------------------
-fn bench_caches(c: &mut Criterion) {
-    // 生成工作负载和预热操作
-    let mut workload_gen = WorkloadGenerator::new(bench::WORKLOAD_SEED);
-    let ops = workload_gen.generate()
-        .with_context(messages::WORKLOAD_GEN_FAILED)
-        .expect("Failed to generate workload");
-    
-    let mut warmup_mgr = WarmupManager::new();
-    let warmup_ops = warmup_mgr.generate_warmup_ops()
-        .with_context(messages::WARMUP_FAILED)
-        .expect("Failed to generate warmup ops");
+
+/// 校准检查的缓存：实现、报告名称、输出标签
+const CALIBRATION: [(CacheKind, &str, &str); 3] = [
+    (CacheKind::Hashlink, "hashlink_lru", "Hashlink"),
+    (CacheKind::Lru, "lru", "LRU"),
+    (CacheKind::MiniMoka, "mini_moka_unsync", "Mini-Moka"),
+];
+
+/// 校准检查发现基线回归时返回失败状态，基准照常运行完毕
+fn bench_caches(c: &mut Criterion) -> ExitCode {
+    let cfg = Config::load().and_then(|cfg| cfg.sized("caches")).expect(messages::CONFIG_LOAD_FAILED);
+    let capacity = cfg.capacity;
+    let env = Env::new(&cfg, cfg.workload_ops());
+    let (runner, ops) = (&env.runner, &env.ops);
 
     // 1. 验证并打印命中率 (只跑一次作为检查)
-    let runtime = compio::runtime::Runtime::new()
-        .map_err(|e| AppError::RuntimeCreate(e.to_string()))
-        .expect(messages::RUNTIME_CREATE_FAILED);
-    
-    runtime.block_on(async {
+    let runtime = BenchRuntime::new().expect(messages::RUNTIME_CREATE_FAILED);
+
+    let status = runtime.block_on(async {
         println!("=== Warmup & Calibration Check ===");
-        let (hashlink_hits, hashlink_misses) = run_hashlink(&ops).await
-            .with_context(messages::CACHE_OPERATION_FAILED)
-            .expect("Failed to run hashlink");
-        let hashlink_rate = CacheRunner::calculate_hit_rate(hashlink_hits, hashlink_misses);
-        println!(
-            "Hashlink Hit Rate: {:.2}% (Hits: {}, Misses: {})",
-            hashlink_rate, hashlink_hits, hashlink_misses
-        );
+        let mut report = Report::new(cfg.clone());
+        let mut record = |name: &str, stats: &RunStats, start: Instant| {
+            let elapsed = start.elapsed();
+            report.push(CacheResult::new(name, capacity, cfg.workload.zipf_s, stats, ops.len(), elapsed));
+        };
 
-        let (lru_hits, lru_misses) = run_lru(&ops).await
-            .with_context(messages::CACHE_OPERATION_FAILED)
-            .expect("Failed to run lru");
-        let lru_rate = CacheRunner::calculate_hit_rate(lru_hits, lru_misses);
-        println!(
-            "LRU Hit Rate: {:.2}% (Hits: {}, Misses: {})",
-            lru_rate, lru_hits, lru_misses
-        );
+        // 三个 LRU crate 的命中率，低于目标时告警；未编译的跳过
+        let mut rates = Vec::new();
+        for (kind, name, label) in CALIBRATION {
+            if !kind.available() {
+                continue;
+            }
+            let start = Instant::now();
+            let stats = kind.run(runner, capacity, &[], ops).await
+                .with_context(messages::CACHE_OPERATION_FAILED)
+                .expect("Failed to run cache");
+            record(name, &stats, start);
+            println!(
+                "{label} Hit Rate: {:.2}% (Hits: {}, Misses: {})",
+                stats.hit_rate(), stats.hits, stats.misses
+            );
+            rates.push(stats.hit_rate());
+        }
 
-        let (moka_hits, moka_misses) = run_mini_moka(&ops).await
+        let start = Instant::now();
+        let opt_stats = belady(&[], ops, capacity)
             .with_context(messages::CACHE_OPERATION_FAILED)
-            .expect("Failed to run mini moka");
-        let moka_rate = CacheRunner::calculate_hit_rate(moka_hits, moka_misses);
+            .expect("Failed to run opt");
+        record(opt::NAME, &opt_stats, start);
         println!(
-            "Mini-Moka Hit Rate: {:.2}% (Hits: {}, Misses: {})",
-            moka_rate, moka_hits, moka_misses
+            "OPT (Belady) Hit Rate: {:.2}% (Hits: {}, Misses: {})",
+            opt_stats.hit_rate(), opt_stats.hits, opt_stats.misses
         );
 
-        if hashlink_rate < bench::MIN_HIT_RATE_TARGET 
-            || lru_rate < bench::MIN_HIT_RATE_TARGET 
-            || moka_rate < bench::MIN_HIT_RATE_TARGET {
-            println!("WARNING: Hit rate is below target. Adjust ZIPF_S or CACHE_CAPACITY.");
+        if rates.iter().any(|&rate| rate < bench::MIN_HIT_RATE_TARGET) {
+            println!("WARNING: Hit rate is below target. Adjust LRU_BENCH_ZIPF_S or LRU_BENCH_CAPACITY.");
+        }
+        if cfg!(feature = "mem") {
+            println!("--- Memory Footprint ---");
+            for kind in CacheKind::ALL {
+                let probe = MemProbe::start();
+                let start = Instant::now();
+                let stats = kind.run(runner, capacity, &[], ops).await
+                    .with_context(messages::CACHE_OPERATION_FAILED)
+                    .expect("Failed to run cache");
+                let elapsed = start.elapsed();
+                let Some(memory) = probe.finish(ops.len()) else { break };
+                let entries = kind.resident(capacity, &[], ops);
+                let per_entry = memory.per_entry(entries).unwrap_or_default();
+                println!(
+                    "{:<12} allocated {:>12} B  peak {:>10} B  {per_entry:>8.1} B/entry  {:>8.1} allocs/kop",
+                    kind.key(), memory.allocated, memory.peak, memory.allocations_per_kop
+                );
+                report.push(
+                    CacheResult::new(kind.key(), capacity, cfg.workload.zipf_s, &stats, ops.len(), elapsed)
+                        .with_memory(memory),
+                );
+            }
+        }
+        if let Some(conv) = &cfg.convergence {
+            println!("--- Warmup Convergence ---");
+            for kind in CacheKind::ALL {
+                let c = kind.converge(capacity, cfg.warmup(), conv)
+                    .with_context(messages::WARMUP_FAILED)
+                    .expect("Failed to detect warmup convergence");
+                let state = if c.converged { "" } else { " (not converged)" };
+                println!("{:<12} {:>8} ops  window hit rate {:.2}%{state}", kind.key(), c.ops, c.hit_rate);
+            }
+        }
+        // 先对比再写出，回归随报告一起保存
+        let status = match report.check_baseline() {
+            Ok([]) => ExitCode::SUCCESS,
+            Ok(regressions) => {
+                for r in regressions {
+                    log::error!("{}: {r}", messages::BASELINE_REGRESSION);
+                }
+                ExitCode::FAILURE
+            }
+            Err(e) => {
+                log::error!("{}: {e}", messages::BASELINE_REGRESSION);
+                ExitCode::FAILURE
+            }
+        };
+        for path in report.save().expect(messages::REPORT_WRITE_FAILED) {
+            println!("Report written to {}", path.display());
         }
         println!("==================================");
+        status
     });
 
-    let mut group = c.benchmark_group("Single-Thread Cache + Compio Async IO");
-    // 设置采样参数
+    // 冷启动：同一工作负载、不预热，与预热版本在同一报告中直接对比
+    let caches = registry(capacity);
+    let cases: Vec<_> = [true, false]
+        .into_iter()
+        .flat_map(|warm| {
+            let env = &env;
+            caches.iter().map(move |f| {
+                let name = if warm { f.name().to_owned() } else { format!("{}_cold", f.name()) };
+                f.case(env, name, warm)
+            })
+        })
+        .collect();
+    bench_group(c, "Single-Thread Cache + Compio Async IO", ops.len(), &cases, check);
+    status
+}
+
+// ----------------------------------------------------------------
+// 场景：每个登记的场景（内置与 scenario_files 中的）一个基准组，按场景的驱动方式生成用例
+// ----------------------------------------------------------------
+
+/// 多线程争用的工作线程数
+const THREADS: [usize; 4] = [2, 4, 8, 16];
+
+fn bench_scenarios(c: &mut Criterion) {
+    let base = Config::load().expect(messages::CONFIG_LOAD_FAILED);
+    let registry = ScenarioRegistry::for_config(&base).expect(messages::CONFIG_LOAD_FAILED);
+    for scenario in registry.iter() {
+        let mut cfg = base.clone();
+        cfg.scenario = Some(scenario.name.clone());
+        cfg.apply_scenario()
+            .and_then(|()| cfg.validate())
+            .expect(messages::CONFIG_LOAD_FAILED);
+        bench_scenario(c, scenario, cfg);
+    }
+}
+
+fn bench_scenario(c: &mut Criterion, scenario: &Scenario, mut cfg: Config) {
+    let group = &format!("Scenario: {}", scenario.name);
+    let capacity = cfg.capacity;
+    let size = cfg.workload.size;
+    // moka::future 只实现异步接口，单独注册
+    #[cfg(feature = "moka")]
+    let moka_future = move || MokaFutureCache::new(capacity);
+    match scenario.mode {
+        ScenarioMode::Bytes => {
+            let env = Env::<Bytes>::generate(&cfg);
+            let caches = typed_registry(capacity);
+            #[allow(unused_mut)]
+            let mut cases: Vec<_> = caches.iter().map(|f| f.case(&env, f.name().into(), true)).collect();
+            // 按真实值大小加权，字节预算为容量乘以平均条目大小
+            #[cfg(feature = "mini-moka")]
+            {
+                let sizes = SizeSampler::new(&cfg.workload.value_size).expect(messages::WORKLOAD_GEN_FAILED);
+                let budget = (capacity as f64 * (sizes.mean() + ENTRY_HEADER as f64)) as u64;
+                cases.push(env.case("mini_moka_unsync_weigher", true, move || {
+                    OptimizedMokaCacheBuilder::build_weighted_cache(budget)
+                }));
+            }
+            bench_group(c, group, size, &cases, check);
+        }
+        ScenarioMode::StringKeys => {
+            let env = Env::<Vec<u8>, String>::generate(&cfg);
+            let caches = typed_registry(capacity);
+            let cases: Vec<_> = caches.iter().map(|f| f.case(&env, f.name().into(), true)).collect();
+            bench_group(c, group, size, &cases, check);
+        }
+        ScenarioMode::NegativeCache => {
+            if cfg.workload.missing_ratio == 0.0 {
+                cfg.workload.missing_ratio = bench::MISSING_RATIO;
+            }
+            let envs = [("refetch", false), ("negative_cache", true)].map(|(label, enabled)| {
+                cfg.negative_cache = enabled;
+                (label, Env::new(&cfg, cfg.workload_ops()))
+            });
+            let caches = typed_registry(capacity);
+            let cases: Vec<_> = envs
+                .iter()
+                .flat_map(|(label, env)| caches.iter().map(move |f| f.case(env, format!("{}_{label}", f.name()), true)))
+                .collect();
+            bench_group(c, group, size, &cases, |name, stats| {
+                check(name, stats);
+                println!("{:<40} negative hits {}, negative misses {}", "", stats.negative_hits, stats.negative_misses);
+            });
+        }
+        ScenarioMode::Expiration => {
+            let env = Env::new(&cfg, cfg.workload_ops());
+            let caches = typed_registry(capacity);
+            #[allow(unused_mut)]
+            let mut cases: Vec<_> = caches.iter().map(|f| f.ttl(&env, cfg.expiration)).collect();
+            #[cfg(feature = "mini-moka")]
+            cases.push(env.case("mini_moka_expiration", true, || {
+                OptimizedMokaCacheBuilder::build_cache_with_expiration(capacity, cfg.expiration.ttl, cfg.expiration.tti)
+            }));
+            bench_group(c, group, size, &cases, check);
+        }
+        ScenarioMode::Async => {
+            let env = Env::new(&cfg, cfg.workload_ops());
+            let caches = shared_registry(capacity);
+            #[allow(unused_mut)]
+            let mut cases: Vec<_> = caches.iter().map(|f| f.case(&env, f.name().into(), true)).collect();
+            #[cfg(feature = "moka")]
+            cases.push(env.async_case("moka_future", moka_future));
+            bench_group(c, group, size, &cases, check);
+        }
+        ScenarioMode::Loader => {
+            let env = Env::new(&cfg, cfg.workload_ops());
+            // 交错切分，各任务访问同一批热点
+            let tasks: Vec<Vec<Op>> = (0..bench::LOADER_TASKS)
+                .map(|t| env.ops.iter().skip(t).step_by(bench::LOADER_TASKS).copied().collect())
+                .collect();
+            let caches = shared_registry(capacity);
+            #[allow(unused_mut)]
+            let mut cases: Vec<_> = caches.iter().map(|f| f.loader(&env, &tasks)).collect();
+            #[cfg(feature = "moka")]
+            cases.push(env.loader("moka_future", &tasks, moka_future));
+            bench_group(c, group, size, &cases, check);
+        }
+        ScenarioMode::Dogpile => {
+            // 所有任务（线程）读取同一冷 key，不预热
+            let key = 0;
+            let tasks = dogpile_workloads(bench::DOGPILE_TASKS, key, bench::DOGPILE_READS);
+            let loads = Arc::new(LoadCounter::new());
+            let runner = CacheRunner::from_config(&cfg).with_load_counter(loads.clone());
+            let env = Env { runner, warmup_ops: Vec::new(), ops: Vec::new() };
+            let caches = shared_registry(capacity);
+            #[allow(unused_mut)]
+            let mut cases: Vec<_> = caches.iter().map(|f| f.loader(&env, &tasks)).collect();
+            #[cfg(feature = "moka")]
+            cases.push(env.loader("moka_future", &tasks, moka_future));
+            bench_group(c, group, bench::DOGPILE_TASKS * bench::DOGPILE_READS, &cases, |name, _| {
+                println!("{name:<40} backend loads {}, redundant {}", loads.get(key), loads.redundant());
+                loads.clear();
+            });
+        }
+        // 未配置 zipf_sweep 时跳过
+        ScenarioMode::ZipfSweep => {
+            if cfg.zipf_sweep.is_empty() {
+                return;
+            }
+            let runtime = BenchRuntime::new().expect(messages::RUNTIME_CREATE_FAILED);
+            let points = runtime
+                .block_on(sweep_zipf(&cfg, &cfg.zipf_sweep, CacheKind::ALL))
+                .with_context(messages::CACHE_OPERATION_FAILED)
+                .expect("Zipf sweep failed");
+            println!("{}", render_table(&points, SweepAxis::ZipfS));
+            let envs: Vec<_> = cfg
+                .zipf_sweep
+                .iter()
+                .map(|&s| {
+                    let ops = WorkloadGenerator::new(cfg.workload.clone()).generate_with_params(s, cfg.workload.total_keys);
+                    (s, Env::new(&cfg, ops))
+                })
+                .collect();
+            let cases: Vec<_> = envs
+                .iter()
+                .flat_map(|(s, env)| CacheKind::ALL.iter().map(move |&k| env.kind(format!("{}_zipf_{s}", k.key()), k, capacity)))
+                .collect();
+            bench_group(c, group, size, &cases, check);
+        }
+        // 每个线程数一个基准组，各线程使用不同种子的同分布工作负载
+        ScenarioMode::Threads => {
+            let env = Env::new(&cfg, cfg.workload_ops());
+            let caches = shared_registry(capacity);
+            for threads in THREADS {
+                let tasks: Vec<Vec<Op>> = (0..threads as u64)
+                    .map(|i| {
+                        let mut w = cfg.workload.clone();
+                        w.seed += i;
+                        WorkloadGenerator::new(w)
+                            .generate()
+                            .with_context(messages::WORKLOAD_GEN_FAILED)
+                            .expect("Failed to generate workload")
+                    })
+                    .collect();
+                let cases: Vec<_> = caches.iter().map(|f| f.threads(&env, &tasks, format!("{}_t{threads}", f.name()))).collect();
+                bench_group(c, &format!("{group} ({threads} threads)"), size * threads, &cases, check);
+            }
+        }
+        ScenarioMode::Batch => {
+            let env = &Env::new(&cfg, cfg.workload_ops());
+            let caches = shared_registry(capacity);
+            let cases: Vec<_> = bench::BATCH_SIZES
+                .into_iter()
+                .flat_map(|batch| caches.iter().flat_map(move |f| f.batch(env, batch)))
+                .collect();
+            bench_group(c, group, env.ops.len(), &cases, check);
+        }
+        // 场景文件可能列出未编译的实现，跳过
+        _ => {
+            let env = Env::new(&cfg, cfg.workload_ops());
+            let cases: Vec<_> = scenario
+                .caches()
+                .into_iter()
+                .filter(|k| k.available())
+                .map(|k| env.kind(k.key(), k, capacity))
+                .collect();
+            bench_group(c, group, env.ops.len(), &cases, check);
+        }
+    }
+}
+
+
+// ----------------------------------------------------------------
+// 模型耗时：模拟时间模式下回源不真实睡眠，以模拟时钟累计的回源延迟为测量值，
+// 关闭真实睡眠时仍可比较各缓存端到端的模型开销
+// ----------------------------------------------------------------
+
+/// 以模拟时钟的推进量为测量值的 Criterion 度量，时钟与被测运行器共享
+struct ModeledTime(MockClock);
+
+impl Measurement for ModeledTime {
+    type Intermediate = Duration;
+    type Value = Duration;
+
+    fn start(&self) -> Duration {
+        self.0.now()
+    }
+
+    fn end(&self, start: Duration) -> Duration {
+        self.0.now().saturating_sub(start)
+    }
+
+    fn add(&self, v1: &Duration, v2: &Duration) -> Duration {
+        *v1 + *v2
+    }
+
+    fn zero(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn to_f64(&self, value: &Duration) -> f64 {
+        value.as_nanos() as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &ModeledFormatter
+    }
+}
+
+/// 模型耗时按量级换算时间单位，吞吐量为每模型秒的处理量
+struct ModeledFormatter;
+
+impl ValueFormatter for ModeledFormatter {
+    fn scale_values(&self, typical: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = match typical {
+            t if t < 1e3 => (1.0, "ns"),
+            t if t < 1e6 => (1e-3, "µs"),
+            t if t < 1e9 => (1e-6, "ms"),
+            _ => (1e-9, "s"),
+        };
+        values.iter_mut().for_each(|v| *v *= factor);
+        unit
+    }
+
+    fn scale_throughputs(&self, _typical: f64, throughput: &Throughput, values: &mut [f64]) -> &'static str {
+        let (n, unit) = match *throughput {
+            Throughput::Bits(n) => (n, "b/s"),
+            Throughput::Bytes(n) | Throughput::BytesDecimal(n) => (n, "B/s"),
+            Throughput::Elements(n) | Throughput::ElementsAndBytes { elements: n, .. } => (n, "elem/s"),
+        };
+        values.iter_mut().for_each(|v| *v = n as f64 * 1e9 / *v);
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "ns"
+    }
+}
+
+/// 度量与其余组不同，使用独立的 Criterion 实例
+fn bench_modeled() {
+    let cfg = Config::load().and_then(|cfg| cfg.sized("modeled")).expect(messages::CONFIG_LOAD_FAILED);
+    let clock = MockClock::new();
+    let runner = CacheRunner::from_config(&cfg).with_clock(clock.clone());
+    let ops = cfg.workload_ops()
+        .with_context(messages::WORKLOAD_GEN_FAILED)
+        .expect("Failed to generate workload");
+    let warmup_ops = WarmupManager::new(cfg.warmup())
+        .generate_warmup_ops_for(&ops)
+        .with_context(messages::WARMUP_FAILED)
+        .expect("Failed to generate warmup ops");
+
+    let runtime = BenchRuntime::new().expect(messages::RUNTIME_CREATE_FAILED);
+    println!("=== Modeled Latency Check ===");
+    let mut kinds = Vec::new();
+    for &kind in CacheKind::ALL {
+        let start = clock.now();
+        let stats = runtime
+            .block_on(kind.run(&runner, cfg.capacity, &warmup_ops, &ops))
+            .with_context(messages::CACHE_OPERATION_FAILED)
+            .expect("Benchmark failed");
+        let modeled = clock.now().saturating_sub(start);
+        println!("{:<14} modeled {modeled:>10.1?}  hit rate {:.2}%", kind.key(), stats.hit_rate());
+        // 模型耗时为 0（零延迟后端或全部命中）时 Criterion 的预热无法结束，跳过
+        if !modeled.is_zero() {
+            kinds.push(kind);
+        }
+    }
+
+    // 回源种子固定时各次迭代的模型耗时相同，方差为 0 无法绘制密度图，只输出统计
+    let mut c = Criterion::default()
+        .with_measurement(ModeledTime(clock))
+        .without_plots()
+        .configure_from_args();
+    let mut group = c.benchmark_group("Modeled Latency (Simulated Time)");
     group.sample_size(bench::SAMPLE_SIZE);
     group.measurement_time(Duration::from_secs(bench::MEASUREMENT_TIME_SECS));
-    group.throughput(Throughput::Elements(WORKLOAD_SIZE as u64));
-
-    // 测试 Hashlink
-    group.bench_function("hashlink_lru", |b| {
-        b.iter_batched(
-            || {
-                let cache = HashlinkLruCache::new(CACHE_CAPACITY as usize);
-                let runtime = compio::runtime::Runtime::new()
-                    .expect(messages::RUNTIME_CREATE_FAILED);
-                (cache, runtime)
-            },
-            |(mut cache, runtime)| {
-                // 预热
-                let warmup_result = runtime.block_on(
-                    warmup_mgr.warmup_cache(&mut cache, &warmup_ops)
-                );
-                if let Err(e) = warmup_result {
-                    eprintln!("Warning: Warmup failed for hashlink: {}", e);
-                }
-                // 运行基准测试
-                runtime.block_on(run_hashlink_with_cache(cache, &ops))
-                    .with_context(messages::CACHE_OPERATION_FAILED)
-                    .expect("Benchmark failed")
-            },
-            criterion::BatchSize::SmallInput,
-        )
-    });
+    group.throughput(Throughput::Elements(ops.len() as u64));
 
-    // 测试 LRU
-    group.bench_function("lru", |b| {
-        b.iter_batched(
-            || {
-                let cache = LruCache::new(std::num::NonZeroUsize::new(CACHE_CAPACITY as usize).unwrap());
-                let runtime = compio::runtime::Runtime::new()
-                    .expect(messages::RUNTIME_CREATE_FAILED);
-                (cache, runtime)
-            },
-            |(mut cache, runtime)| {
-                // 预热
-                let warmup_result = runtime.block_on(
-                    warmup_mgr.warmup_cache(&mut cache, &warmup_ops)
-                );
-                if let Err(e) = warmup_result {
-                    eprintln!("Warning: Warmup failed for lru: {}", e);
-                }
-                // 运行基准测试
-                runtime.block_on(run_lru_with_cache(cache, &ops))
+    for kind in kinds {
+        group.bench_function(kind.key(), |b| {
+            b.iter(|| {
+                runtime.block_on(kind.run(&runner, cfg.capacity, &warmup_ops, &ops))
                     .with_context(messages::CACHE_OPERATION_FAILED)
                     .expect("Benchmark failed")
-            },
-            criterion::BatchSize::SmallInput,
-        )
-    });
+            })
+        });
+    }
 
-    // 测试 Mini-Moka (高性能版本)
-    group.bench_function("mini_moka_unsync_optimized", |b| {
-        b.iter_batched(
-            || {
-                let cache = cache_bench::cache::OptimizedMokaCacheBuilder::build_high_performance_cache();
-                let runtime = compio::runtime::Runtime::new()
-                    .expect(messages::RUNTIME_CREATE_FAILED);
-                (cache, runtime)
-            },
-            |(mut cache, runtime)| {
-                // 预热
-                let warmup_result = runtime.block_on(
-                    warmup_mgr.warmup_cache(&mut cache, &warmup_ops)
-                );
-                if let Err(e) = warmup_result {
-                    eprintln!("Warning: Warmup failed for mini moka optimized: {}", e);
-                }
-                // 运行基准测试
-                runtime.block_on(run_mini_moka_with_cache(cache, &ops))
-                    .with_context(messages::CACHE_OPERATION_FAILED)
-                    .expect("Benchmark failed")
-            },
-            criterion::BatchSize::SmallInput,
-        )
-    });
+    group.finish();
+}
 
-    // 测试 Mini-Moka (权重感知版本)
-    group.bench_function("mini_moka_unsync_weigher", |b| {
-        b.iter_batched(
-            || {
-                let cache = cache_bench::cache::OptimizedMokaCacheBuilder::build_optimized_cache();
-                let runtime = compio::runtime::Runtime::new()
-                    .expect(messages::RUNTIME_CREATE_FAILED);
-                (cache, runtime)
-            },
-            |(mut cache, runtime)| {
-                // 预热
-                let warmup_result = runtime.block_on(
-                    warmup_mgr.warmup_cache(&mut cache, &warmup_ops)
-                );
-                if let Err(e) = warmup_result {
-                    eprintln!("Warning: Warmup failed for mini moka weigher: {}", e);
-                }
-                // 运行基准测试
-                runtime.block_on(run_mini_moka_with_cache(cache, &ops))
-                    .with_context(messages::CACHE_OPERATION_FAILED)
-                    .expect("Benchmark failed")
-            },
-            criterion::BatchSize::SmallInput,
-        )
-    });
+// ----------------------------------------------------------------
+// 微基准：同步回放、零回源延迟，只比较缓存自身的每操作耗时
+// ----------------------------------------------------------------
 
-    group.finish();
+fn bench_micro(c: &mut Criterion) {
+    let cfg = Config::load().and_then(|cfg| cfg.sized("micro")).expect(messages::CONFIG_LOAD_FAILED);
+    let env = Env::new(&cfg, cfg.workload_ops());
+    let caches = registry(cfg.capacity);
+    let cases: Vec<_> = caches.iter().map(|f| f.micro(&env)).collect();
+    bench_group(c, "Micro (Sync, No Backend)", env.ops.len(), &cases, check);
 }
 
-criterion_group!(benches, bench_caches);
-criterion_main!(benches);
+// criterion_group! 不支持按 feature 取舍成员，手写等价的组函数；返回校准检查的状态
+fn benches() -> ExitCode {
+    let mut c = Criterion::default().configure_from_args();
+    let status = bench_caches(&mut c);
+    bench_scenarios(&mut c);
+    bench_modeled();
+    bench_micro(&mut c);
+    status
+}
+
+// 与 criterion_main! 等价，另把基线回归作为非零退出状态返回
+fn main() -> ExitCode {
+    env_logger::init();
+    let status = benches();
+    Criterion::default().configure_from_args().final_summary();
+    status
+}
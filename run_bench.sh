@@ -16,6 +16,8 @@ Running benchmark (Compio Single Thread: Hashlink vs LRU vs Mini-Moka)...
 Workload: Zipf distribution, 95% Read / 5% Write, ~90% Hit Rate Target
 Backend Latency: Random 1-2ms
 Enhanced warmup strategy enabled
+Memory footprint: run `cargo bench --features mem`
+Subset of crates: run `cargo bench --no-default-features --features lru,moka`
 EOF
 
 # 运行 Criterion 测试
@@ -42,12 +44,40 @@ cat >./reports/summary.txt <<EOF
 === Benchmark Summary ===
 Date: $(date)
 Configuration:
-- Cache Capacity: ${CACHE_CAPACITY:-7500}
-- Total Keys: 10000
-- Workload Size: 1000
-- Zipf Parameter: 1.6
-- Read Ratio: 95%
-- Backend Latency: 1-2ms
+- Config File: ${LRU_BENCH_CONFIG:-bench.toml}
+- Cache Capacity: ${LRU_BENCH_CAPACITY:-7500}
+- Scenario: ${LRU_BENCH_SCENARIO:-(none)}, files ${LRU_BENCH_SCENARIO_FILES:-(none)}
+- Size: ${LRU_BENCH_SIZE:-(default)}
+- Total Keys: ${LRU_BENCH_TOTAL_KEYS:-10000}
+- Workload Size: ${LRU_BENCH_WORKLOAD_SIZE:-1000}
+- Zipf Parameter: ${LRU_BENCH_ZIPF_S:-1.6}
+- Workload Preset: ${LRU_BENCH_WORKLOAD:-(none)}
+- Key Distribution: ${LRU_BENCH_DISTRIBUTION:-zipf}, scramble ${LRU_BENCH_SCRAMBLE:-false}
+- Zipf Sweep: ${LRU_BENCH_ZIPF_SWEEP:-(disabled)}
+- Workload Cache: ${LRU_BENCH_WORKLOAD_CACHE:-(none)}
+- Report: ${LRU_BENCH_REPORT:-(none)}
+- CSV: ${LRU_BENCH_CSV:-(none)}
+- HTML: ${LRU_BENCH_HTML:-(none)}
+- Markdown: ${LRU_BENCH_MARKDOWN:-(none)}
+- Baseline: ${LRU_BENCH_BASELINE:-(none)}, max drop throughput ${LRU_BENCH_MAX_THROUGHPUT_DROP:-10}%, hit rate ${LRU_BENCH_MAX_HIT_RATE_DROP:-1} pts
+- Read Ratio: ${LRU_BENCH_READ_RATIO:-0.95}
+- Scan: every ${LRU_BENCH_SCAN_EVERY:-(off)} ops, ${LRU_BENCH_SCAN_LEN:-(off)} keys
+- Hotspot Phases: ${LRU_BENCH_PHASES:-1}
+- Warmup Strategy: ${LRU_BENCH_WARMUP_STRATEGY:-zipf}
+- Warmup Convergence: window ${LRU_BENCH_CONVERGE_WINDOW:-(off)}, epsilon ${LRU_BENCH_CONVERGE_EPSILON:-(off)}, max ${LRU_BENCH_CONVERGE_MAX_OPS:-(off)} ops
+- Value Size: ${LRU_BENCH_VALUE_SIZE:-lognormal:4096:1.0}
+- Backend: ${LRU_BENCH_BACKEND:-uniform}
+- Backend Latency: ${LRU_BENCH_MIN_DELAY:-1ms}-${LRU_BENCH_MAX_DELAY:-2ms}
+- Backend Faults: error ${LRU_BENCH_ERROR_RATE:-0}, timeout ${LRU_BENCH_TIMEOUT_RATE:-0} (${LRU_BENCH_TIMEOUT:-50ms}), retries ${LRU_BENCH_RETRIES:-0}
+- Delete Ratio: ${LRU_BENCH_DELETE_RATIO:-0}
+- Insert Ratio: ${LRU_BENCH_INSERT_RATIO:-0} of writes
+- Range Scans: ${LRU_BENCH_RANGE_RATIO:-0} of reads, ${LRU_BENCH_RANGE_LEN:-16} keys
+- Missing Keys: ${LRU_BENCH_MISSING_RATIO:-0}, negative cache ${LRU_BENCH_NEGATIVE_CACHE:-false}
+- Expiration: ttl ${LRU_BENCH_TTL:-20ms}, tti ${LRU_BENCH_TTI:-5ms}
+- Simulated Time: ${LRU_BENCH_SIMULATED_TIME:-false}
+- Remote Cache: ${LRU_BENCH_REMOTE:-(off)}, ${LRU_BENCH_REMOTE_CONNECTIONS:-1} connection(s) (run `lru-bench remote`)
+- Disk Tier: ${LRU_BENCH_DISK_CAPACITY:-(off)} entries, ${LRU_BENCH_DISK_BLOCK:-4096} B blocks
+- Backend File: ${LRU_BENCH_BACKEND_FILE:-(off)}, ${LRU_BENCH_BACKEND_FILE_SIZE:-268435456} B, ${LRU_BENCH_BACKEND_FILE_BLOCK:-4096} B blocks
 
 EOF
 
@@ -55,7 +85,7 @@ EOF
 if command -v jq &>/dev/null; then
   echo "Detailed Results:" >>./reports/summary.txt
   # 从各个 estimates.json 文件提取结果
-  for impl in hashlink_lru lru mini_moka_unsync; do
+  for impl in hashlink_lru hashlink_lru_cold lru lru_cold mini_moka_unsync; do
     ESTIMATE_FILE="./reports/criterion/Single-Thread Cache + Compio Async IO/$impl/new/estimates.json"
     if [ -f "$ESTIMATE_FILE" ]; then
       MEAN=$(jq -r '.mean.point_estimate' "$ESTIMATE_FILE" 2>/dev/null)
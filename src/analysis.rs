@@ -0,0 +1,33 @@
+//! 离线分析模块
+//! 在操作序列上计算与具体实现无关的理论指标
+//!
+//! 读（含 peek）计为请求，写只更新访问顺序，与 [`crate::CacheRunner`] 的命中率口径一致
+
+use crate::Op;
+
+pub mod mrc;
+pub mod opt;
+pub mod reuse_distance;
+pub mod stats;
+pub mod tiers;
+pub mod working_set;
+
+/// 单个 key 上的访问
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+    Delete,
+}
+
+/// 展开为逐 key 的访问序列，范围扫描的每个 key 计为一次读
+fn accesses<V>(ops: &[Op<V>]) -> impl Iterator<Item = (usize, Access)> + '_ {
+    ops.iter().flat_map(|op| {
+        let access = match op {
+            Op::Write(..) | Op::Insert(..) => Access::Write,
+            Op::Delete(_) => Access::Delete,
+            _ => Access::Read,
+        };
+        op.keys().map(move |k| (k, access))
+    })
+}
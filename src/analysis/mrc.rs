@@ -0,0 +1,87 @@
+//! 缺失率曲线
+//! 用 SHARDS 空间采样计算理论 LRU 的重用距离分布：只跟踪哈希落入采样区间的 key，
+//! 距离按采样率放大，内存与时间都只与采样到的访问数相关。
+//! 采用 SHARDS-adj 修正：实际采样请求数与期望值之差计入距离 0，抵消热点 key 是否被采中带来的偏差
+
+use super::reuse_distance::stack_distances;
+use super::{Access, accesses};
+use crate::{AppError, Op, Result};
+
+/// 采样哈希的模数
+const MODULUS: u64 = 1 << 24;
+
+/// 理论 LRU 的缺失率曲线
+#[derive(Clone, Debug)]
+pub struct Mrc {
+    /// 已放大的有限重用距离，升序
+    distances: Vec<u64>,
+    /// 期望采样读请求数（全部读请求数乘采样率）
+    expected: f64,
+    /// 期望与实际采样读请求数之差
+    adjust: f64,
+    rate: f64,
+}
+
+impl Mrc {
+    /// 以采样率 `rate`（0, 1] 计算，1 为精确计算
+    pub fn compute<V>(ops: &[Op<V>], rate: f64) -> Result<Self> {
+        if !(rate > 0.0 && rate <= 1.0) {
+            return Err(AppError::Config(format!("mrc sampling rate {rate} not in (0, 1]")));
+        }
+        // rate 在 (0, 1] 内，乘积不超过 MODULUS
+        let threshold = (rate * MODULUS as f64) as u64;
+        let total_reads = accesses(ops).filter(|&(_, a)| a == Access::Read).count();
+        let sampled: Vec<(usize, Access)> = accesses(ops)
+            .filter(|&(key, _)| mix(key as u64) % MODULUS < threshold)
+            .collect();
+
+        let (distances, reads) = stack_distances(&sampled);
+        // 距离按采样率放大
+        let mut distances: Vec<u64> = distances.into_iter().map(|d| (d as f64 / rate) as u64).collect();
+        distances.sort_unstable();
+        let expected = total_reads as f64 * rate;
+        Ok(Self {
+            distances,
+            expected,
+            adjust: expected - reads as f64,
+            rate,
+        })
+    }
+
+    /// 采样率
+    #[inline]
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// 容量为 `capacity` 的 LRU 的缺失率 [0, 1]，首次访问计为缺失
+    pub fn miss_ratio(&self, capacity: u64) -> f64 {
+        if self.expected <= 0.0 {
+            return 0.0;
+        }
+        let hits = self.distances.partition_point(|&d| d < capacity) as f64 + self.adjust;
+        (1.0 - hits / self.expected).clamp(0.0, 1.0)
+    }
+
+    /// 有限重用距离的 `q` 分位数（q 取 [0, 1]），没有重用时为 None
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        let last = self.distances.len().checked_sub(1)?;
+        // q 已截断到 [0, 1]，下标不超过 last
+        let i = (q.clamp(0.0, 1.0) * last as f64).round() as usize;
+        Some(self.distances[i])
+    }
+
+    /// 命中率（百分比），与 [`crate::RunStats::hit_rate`] 口径一致
+    #[inline]
+    pub fn hit_rate(&self, capacity: u64) -> f64 {
+        (1.0 - self.miss_ratio(capacity)) * 100.0
+    }
+}
+
+/// splitmix64 终混函数，使采样与 key 的数值分布无关
+#[inline]
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,70 @@
+//! Belady 最优替换（OPT）
+//! 离线预知整个操作序列，淘汰下次访问最远的条目，给出同一容量下任何淘汰策略的命中率上界。
+//! 与 [`crate::CacheRunner`] 一致：未命中必定写入缓存，不做旁路
+
+use super::{Access, accesses};
+use crate::{AppError, Op, Result, RunStats};
+use std::collections::{BTreeSet, HashMap};
+
+/// 报告与表格中使用的名称
+pub const NAME: &str = "opt";
+
+/// 在 `warmup` 之后执行 `ops`，只统计 `ops` 中的读请求
+pub fn belady<V>(warmup: &[Op<V>], ops: &[Op<V>], capacity: u64) -> Result<RunStats> {
+    let capacity = usize::try_from(capacity)
+        .ok()
+        .filter(|&c| c > 0)
+        .ok_or_else(|| AppError::Config(format!("opt capacity {capacity} out of range")))?;
+    let warm = accesses(warmup).count();
+    let seq: Vec<(usize, Access)> = accesses(warmup).chain(accesses(ops)).collect();
+
+    // 每个位置上的 key 下次被访问的位置，不再访问或先被删除时为 usize::MAX
+    let mut next = vec![usize::MAX; seq.len()];
+    let mut seen: HashMap<usize, usize> = HashMap::new();
+    for (i, &(key, access)) in seq.iter().enumerate().rev() {
+        if access == Access::Delete {
+            seen.remove(&key);
+        } else if let Some(j) = seen.insert(key, i) {
+            next[i] = j;
+        }
+    }
+
+    let mut stats = RunStats::default();
+    // key -> 下次访问位置；按 (下次访问位置, key) 排序以取最远者
+    let mut cached: HashMap<usize, usize> = HashMap::with_capacity(capacity);
+    let mut order: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for (i, &(key, access)) in seq.iter().enumerate() {
+        if access == Access::Delete {
+            if let Some(at) = cached.remove(&key) {
+                order.remove(&(at, key));
+            }
+            if i >= warm {
+                stats.deletes += 1;
+            }
+            continue;
+        }
+        let prev = cached.insert(key, next[i]);
+        if i >= warm && access == Access::Read {
+            if prev.is_some() {
+                stats.hits += 1;
+            } else {
+                stats.misses += 1;
+            }
+        }
+        match prev {
+            Some(at) => {
+                order.remove(&(at, key));
+            }
+            None if cached.len() > capacity => {
+                // 新条目尚未进入 order，被淘汰的必为其他条目
+                if let Some((_, victim)) = order.pop_last() {
+                    cached.remove(&victim);
+                }
+            }
+            None => {}
+        }
+        order.insert((next[i], key));
+    }
+    stats.ops = ops.len() as u64;
+    Ok(stats)
+}
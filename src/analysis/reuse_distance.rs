@@ -0,0 +1,151 @@
+//! 重用距离
+//! 以树状数组在 O(n log n) 内精确计算读请求的重用（栈）距离：同一 key 两次访问之间出现过的不同 key 数。
+//! 距离小于容量的读请求恰好是该容量的 LRU 能命中的请求，直方图因此直接解释命中率随容量的变化
+
+use super::{Access, accesses};
+use crate::Op;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// 重用距离直方图，桶按 2 的幂划分：第 0 桶为距离 0，第 i 桶为 [2^(i-1), 2^i)
+#[derive(Clone, Debug, Default)]
+pub struct ReuseHistogram {
+    buckets: Vec<u64>,
+    /// 首次访问的读请求数，距离为无穷
+    cold: u64,
+    reads: u64,
+}
+
+impl ReuseHistogram {
+    /// 精确计算读请求（含 peek 与范围扫描的每个 key）的重用距离；写更新访问顺序，删除后再次访问视为首次访问
+    pub fn compute<V>(ops: &[Op<V>]) -> Self {
+        let accesses: Vec<_> = accesses(ops).collect();
+        let (distances, reads) = stack_distances(&accesses);
+        let mut buckets = Vec::new();
+        for d in &distances {
+            let i = bucket(*d);
+            if buckets.len() <= i {
+                buckets.resize(i + 1, 0);
+            }
+            buckets[i] += 1;
+        }
+        Self {
+            buckets,
+            cold: (reads - distances.len()) as u64,
+            reads: reads as u64,
+        }
+    }
+
+    /// 读请求数
+    #[inline]
+    pub fn reads(&self) -> u64 {
+        self.reads
+    }
+
+    /// 首次访问的读请求数，任何容量都无法命中
+    #[inline]
+    pub fn cold(&self) -> u64 {
+        self.cold
+    }
+
+    /// 非空桶：(距离区间, 读请求数)，按距离升序
+    pub fn buckets(&self) -> impl Iterator<Item = (Range<u64>, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|&(_, &n)| n > 0)
+            .map(|(i, &n)| (bounds(i), n))
+    }
+
+    /// 重用距离小于 `capacity` 的读请求比例 [0, 1]，即该容量的 LRU 命中率
+    pub fn below(&self, capacity: u64) -> f64 {
+        if self.reads == 0 {
+            return 0.0;
+        }
+        let hits: f64 = self
+            .buckets()
+            .map(|(range, n)| match range {
+                r if r.end <= capacity => n as f64,
+                // 跨越容量的桶按距离均匀分布折算
+                r if r.start < capacity => n as f64 * (capacity - r.start) as f64 / (r.end - r.start) as f64,
+                _ => 0.0,
+            })
+            .sum();
+        hits / self.reads as f64
+    }
+}
+
+/// 距离所在的桶
+#[inline]
+fn bucket(d: u64) -> usize {
+    (u64::BITS - d.leading_zeros()) as usize
+}
+
+/// 桶的距离区间
+#[inline]
+fn bounds(i: usize) -> Range<u64> {
+    match i {
+        0 => 0..1,
+        i => 1 << (i - 1)..1 << i,
+    }
+}
+
+/// 逐个访问计算读请求的有限重用距离（按出现顺序），并返回读请求总数
+pub(super) fn stack_distances(accesses: &[(usize, Access)]) -> (Vec<u64>, usize) {
+    let mut tree = Fenwick::new(accesses.len());
+    let mut last: HashMap<usize, usize> = HashMap::new();
+    let mut distances = Vec::new();
+    let mut reads = 0;
+    for (t, &(key, access)) in accesses.iter().enumerate() {
+        if access == Access::Delete {
+            // 删除后再次访问视为首次访问
+            if let Some(p) = last.remove(&key) {
+                tree.add(p, -1);
+            }
+            continue;
+        }
+        let is_read = access == Access::Read;
+        let prev = last.insert(key, t);
+        if is_read {
+            reads += 1;
+        }
+        if let Some(p) = prev {
+            if is_read {
+                // 两次访问之间出现过的不同 key 数
+                distances.push((tree.sum(t) - tree.sum(p + 1)) as u64);
+            }
+            tree.add(p, -1);
+        }
+        tree.add(t, 1);
+    }
+    (distances, reads)
+}
+
+/// 树状数组，前缀和
+struct Fenwick(Vec<i64>);
+
+impl Fenwick {
+    fn new(n: usize) -> Self {
+        Self(vec![0; n + 1])
+    }
+
+    #[inline]
+    fn add(&mut self, i: usize, v: i64) {
+        let mut i = i + 1;
+        while i < self.0.len() {
+            self.0[i] += v;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 下标 [0, i) 之和
+    #[inline]
+    fn sum(&self, i: usize) -> i64 {
+        let (mut i, mut s) = (i, 0);
+        while i > 0 {
+            s += self.0[i];
+            i -= i & i.wrapping_neg();
+        }
+        s
+    }
+}
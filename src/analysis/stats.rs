@@ -0,0 +1,145 @@
+//! 工作负载统计
+//! 运行前检查生成的工作负载是否符合预期：访问过的 key 数、工作集大小、热点 key 占比、实际读比例与重用距离。
+//! 用于尽早发现配置错误，例如 Zipf 参数过大使任何缓存都几乎全部命中
+
+use super::{Access, accesses};
+use crate::{Mrc, Op, Result};
+use std::collections::HashMap;
+
+/// 工作集覆盖的访问比例
+pub const WORKING_SET_SHARE: f64 = 0.9;
+
+/// 理论 LRU 命中率高于此值（百分比）时认为工作负载过于容易缓存
+const TRIVIAL_HIT_RATE: f64 = 99.0;
+/// 理论 LRU 命中率低于此值（百分比）时认为工作负载几乎无法缓存
+const HOPELESS_HIT_RATE: f64 = 1.0;
+/// 实际读比例与配置相差超过此值时告警
+const READ_RATIO_TOLERANCE: f64 = 0.05;
+
+/// 工作负载统计
+#[derive(Clone, Debug)]
+pub struct WorkloadStats {
+    /// 操作数
+    pub ops: usize,
+    /// 读操作数（含 peek、不存在的 key 与范围扫描）
+    pub reads: usize,
+    /// 写操作数（含插入新 key）
+    pub writes: usize,
+    /// 删除操作数
+    pub deletes: usize,
+    /// 各 key 的访问次数，降序
+    counts: Vec<u64>,
+    /// 精确计算的理论 LRU 重用距离
+    mrc: Mrc,
+}
+
+impl WorkloadStats {
+    /// 统计操作序列，范围扫描的每个 key 计为一次访问
+    pub fn compute<V>(ops: &[Op<V>]) -> Result<Self> {
+        let (mut reads, mut writes, mut deletes) = (0, 0, 0);
+        for op in ops {
+            match op {
+                Op::Write(..) | Op::Insert(..) => writes += 1,
+                Op::Delete(_) => deletes += 1,
+                _ => reads += 1,
+            }
+        }
+        let mut per_key: HashMap<usize, u64> = HashMap::new();
+        for (key, access) in accesses(ops) {
+            if access != Access::Delete {
+                *per_key.entry(key).or_default() += 1;
+            }
+        }
+        let mut counts: Vec<u64> = per_key.into_values().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Self {
+            ops: ops.len(),
+            reads,
+            writes,
+            deletes,
+            counts,
+            mrc: Mrc::compute(ops, 1.0)?,
+        })
+    }
+
+    /// 实际读比例 [0, 1]
+    #[inline]
+    pub fn read_ratio(&self) -> f64 {
+        self.reads as f64 / self.ops.max(1) as f64
+    }
+
+    /// 访问过的不同 key 数
+    #[inline]
+    pub fn unique_keys(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// 最热的 `k` 个 key 占全部访问的比例 [0, 1]
+    pub fn top_share(&self, k: usize) -> f64 {
+        let total: u64 = self.counts.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        self.counts.iter().take(k).sum::<u64>() as f64 / total as f64
+    }
+
+    /// 覆盖 `share` 比例访问所需的最少 key 数，即理论工作集大小
+    pub fn working_set(&self, share: f64) -> usize {
+        let total: u64 = self.counts.iter().sum();
+        let target = total as f64 * share;
+        let mut acc = 0;
+        for (i, &c) in self.counts.iter().enumerate() {
+            if acc as f64 >= target {
+                return i;
+            }
+            acc += c;
+        }
+        self.counts.len()
+    }
+
+    /// 读请求重用距离的 `q` 分位数，首次访问不计入；没有重用时为 None
+    #[inline]
+    pub fn reuse_distance(&self, q: f64) -> Option<u64> {
+        self.mrc.quantile(q)
+    }
+
+    /// 首次访问的读请求比例 [0, 1]，任何容量都无法命中
+    #[inline]
+    pub fn cold_ratio(&self) -> f64 {
+        self.mrc.miss_ratio(u64::MAX)
+    }
+
+    /// 容量为 `capacity` 的理论 LRU 命中率（百分比）
+    #[inline]
+    pub fn lru_hit_rate(&self, capacity: u64) -> f64 {
+        self.mrc.hit_rate(capacity)
+    }
+
+    /// 检查可能的配置错误，`read_ratio` 为配置的读比例（追踪文件回放时传 None）
+    pub fn warnings(&self, capacity: u64, read_ratio: Option<f64>) -> Vec<String> {
+        let mut out = Vec::new();
+        let unique = self.unique_keys();
+        if unique as u64 <= capacity {
+            out.push(format!(
+                "all {unique} accessed keys fit in capacity {capacity}: eviction never happens"
+            ));
+        }
+        let hit_rate = self.lru_hit_rate(capacity);
+        if hit_rate >= TRIVIAL_HIT_RATE {
+            out.push(format!(
+                "theoretical LRU hit rate {hit_rate:.2}% at capacity {capacity}: workload is trivially cacheable (zipf s too high?)"
+            ));
+        } else if self.reads > 0 && hit_rate < HOPELESS_HIT_RATE {
+            out.push(format!(
+                "theoretical LRU hit rate {hit_rate:.2}% at capacity {capacity}: almost nothing is cacheable"
+            ));
+        }
+        if let Some(expected) = read_ratio {
+            let actual = self.read_ratio();
+            if (actual - expected).abs() > READ_RATIO_TOLERANCE {
+                out.push(format!("actual read ratio {actual:.3} differs from configured {expected}"));
+            }
+        }
+        out
+    }
+}
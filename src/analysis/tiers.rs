@@ -0,0 +1,130 @@
+//! 热度分层
+//! 按读请求次数把工作负载中访问过的不同 key 划为头部（前 1%）、中部（其后 9%）与尾部（其余 90%），
+//! 供 [`crate::CacheRunner`] 分层统计命中率。以实际请求次数而非采样 id 排序，打散、多阶段与各种分布下同样适用
+
+use crate::{Key, Op};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::AddAssign;
+
+/// 头部 key 占不同 key 数的比例
+pub const HEAD_SHARE: f64 = 0.01;
+/// 头部与中部合计占不同 key 数的比例
+pub const MIDDLE_SHARE: f64 = 0.10;
+
+/// 热度层
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PopularityTier {
+    /// 最热的 1%
+    Head,
+    /// 其后的 9%
+    Middle,
+    /// 其余 90%
+    Tail,
+}
+
+impl PopularityTier {
+    /// 全部热度层，按热度降序，下标与 [`crate::RunStats::tiers`] 一致
+    pub const ALL: [Self; 3] = [Self::Head, Self::Middle, Self::Tail];
+
+    /// 输出名称
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Head => "head",
+            Self::Middle => "middle",
+            Self::Tail => "tail",
+        }
+    }
+}
+
+impl fmt::Display for PopularityTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// 单个热度层的读命中统计
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TierStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl TierStats {
+    /// 命中率（百分比）
+    #[inline]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64 * 100.0
+        }
+    }
+}
+
+impl AddAssign for TierStats {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.hits += rhs.hits;
+        self.misses += rhs.misses;
+    }
+}
+
+/// key id 到热度层的映射
+#[derive(Clone, Debug, Default)]
+pub struct PopularityTiers {
+    tiers: HashMap<usize, PopularityTier>,
+    /// 各层的 key 数
+    sizes: [usize; 3],
+}
+
+impl PopularityTiers {
+    /// 统计 `ops` 中各 key 的读请求次数（范围扫描的每个 key 计一次）并分层，次数相同时 id 小者在前
+    pub fn compute<V, K: Key>(ops: &[Op<V, K>]) -> Self {
+        let mut counts: HashMap<usize, u64> = HashMap::new();
+        for op in ops {
+            if matches!(op, Op::Write(..) | Op::Insert(..) | Op::Delete(_)) {
+                continue;
+            }
+            for key in op.keys() {
+                *counts.entry(key.id()).or_default() += 1;
+            }
+        }
+        let mut ranked: Vec<(usize, u64)> = counts.into_iter().collect();
+        ranked.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let n = ranked.len();
+        let head = (n as f64 * HEAD_SHARE).ceil() as usize;
+        let middle = ((n as f64 * MIDDLE_SHARE).ceil() as usize).max(head);
+        let mut sizes = [0; 3];
+        let tiers = ranked
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (id, _))| {
+                let tier = match rank {
+                    r if r < head => PopularityTier::Head,
+                    r if r < middle => PopularityTier::Middle,
+                    _ => PopularityTier::Tail,
+                };
+                sizes[tier as usize] += 1;
+                (id, tier)
+            })
+            .collect();
+        Self { tiers, sizes }
+    }
+
+    /// `key` 所在的层，未出现在统计中的 key 归入尾部
+    #[inline]
+    pub fn tier(&self, key: usize) -> PopularityTier {
+        self.tiers.get(&key).copied().unwrap_or(PopularityTier::Tail)
+    }
+
+    /// 层内的 key 数
+    #[inline]
+    pub fn size(&self, tier: PopularityTier) -> usize {
+        self.sizes[tier as usize]
+    }
+}
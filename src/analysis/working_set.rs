@@ -0,0 +1,56 @@
+//! 工作集大小
+//! 滑动窗口内访问过的不同 key 数随时间的变化，以窗口内各 key 的出现次数精确计数，进出窗口时增减。
+//! 与缓存容量对照：窗口工作集远大于容量时，任何淘汰策略都难以命中
+
+use crate::config::WORKING_SET_POINTS;
+use crate::{Key, Op};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 单个采样点
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct WorkingSetPoint {
+    /// 已执行的操作数
+    pub op: usize,
+    /// 最近 `window` 个操作访问过的不同 key 数
+    pub unique: usize,
+}
+
+/// 以最近 `window` 个操作为窗口，在整个序列上均匀采样约 [`WORKING_SET_POINTS`] 个点；
+/// 范围扫描的每个 key 都计入，删除不计入，窗口未满时统计已执行的全部操作
+pub fn working_set_timeline<V, K: Key>(ops: &[Op<V, K>], window: usize) -> Vec<WorkingSetPoint> {
+    let window = window.max(1);
+    let every = (ops.len() / WORKING_SET_POINTS).max(1);
+    let mut counts: HashMap<usize, u32> = HashMap::new();
+    let mut points = Vec::with_capacity(ops.len() / every + 1);
+    for (i, op) in ops.iter().enumerate() {
+        for key in touched(op) {
+            *counts.entry(key).or_default() += 1;
+        }
+        if let Some(old) = i.checked_sub(window) {
+            for key in touched(&ops[old]) {
+                if let Some(n) = counts.get_mut(&key) {
+                    *n -= 1;
+                    if *n == 0 {
+                        counts.remove(&key);
+                    }
+                }
+            }
+        }
+        let op = i + 1;
+        if op % every == 0 || op == ops.len() {
+            points.push(WorkingSetPoint { op, unique: counts.len() });
+        }
+    }
+    points
+}
+
+/// 操作访问的 key id，删除不访问
+fn touched<V, K: Key>(op: &Op<V, K>) -> impl Iterator<Item = usize> + '_ {
+    let keys = match op {
+        Op::Delete(_) => None,
+        op => Some(op.keys().map(|k| k.id())),
+    };
+    keys.into_iter().flatten()
+}
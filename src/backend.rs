@@ -0,0 +1,390 @@
+//! 后端模拟模块
+//! 定义回源延迟模型：常量、均匀、对数正态、Pareto（长尾）与零延迟，
+//! 分别对应 RPC、磁盘、内存等不同后端
+
+use crate::config::{BACKEND_TIMEOUT, MAX_DELAY, MIN_DELAY};
+use crate::{AppError, Result, TimeSource};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub mod file;
+
+/// Pareto 延迟的上限为 scale 的倍数，避免极端样本让单次运行失去意义
+pub const PARETO_CAP: u32 = 1000;
+
+/// 回源延迟模型，实现此 trait 即可接入 [`crate::CacheRunner`]
+pub trait BackendModel {
+    /// 采样一次回源延迟
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration;
+
+    /// 完成一次对 `key` 的回源：默认在 `clock` 上等待采样的延迟，零延迟时不挂起；真实执行 I/O 的后端覆盖此方法
+    fn fetch<T: TimeSource, R: Rng + ?Sized>(
+        &self,
+        _key: usize,
+        clock: &T,
+        rng: &mut R,
+    ) -> impl Future<Output = Result<()>> {
+        let delay = self.sample(rng);
+        async move {
+            if !delay.is_zero() {
+                clock.sleep(delay).await;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 常量延迟，`Duration::ZERO` 即零延迟
+impl BackendModel for Duration {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, _rng: &mut R) -> Duration {
+        *self
+    }
+}
+
+/// 均匀延迟模型，延迟在 [min, max] 内均匀分布
+///
+/// 配置文件中时长写作 `"1ms"`、`"1500us"` 等
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LatencyModel {
+    #[serde(with = "humantime_serde")]
+    min: Duration,
+    #[serde(with = "humantime_serde")]
+    max: Duration,
+}
+
+impl Default for LatencyModel {
+    fn default() -> Self {
+        Self {
+            min: MIN_DELAY,
+            max: MAX_DELAY,
+        }
+    }
+}
+
+impl LatencyModel {
+    /// 创建构建器，初始值为默认延迟范围
+    pub fn builder() -> LatencyModelBuilder {
+        LatencyModelBuilder(Self::default())
+    }
+
+    /// 最小延迟
+    #[inline]
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// 最大延迟
+    #[inline]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+impl BackendModel for LatencyModel {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        // 纳秒数在 u64 内可表示约 584 年，as 转换安全
+        Duration::from_nanos(rng.random_range(self.min.as_nanos() as u64..=self.max.as_nanos() as u64))
+    }
+}
+
+/// 延迟模型构建器
+#[derive(Clone, Copy, Debug)]
+pub struct LatencyModelBuilder(LatencyModel);
+
+impl LatencyModelBuilder {
+    /// 设置最小延迟
+    pub fn min(mut self, min: Duration) -> Self {
+        self.0.min = min;
+        self
+    }
+
+    /// 设置最大延迟
+    pub fn max(mut self, max: Duration) -> Self {
+        self.0.max = max;
+        self
+    }
+
+    /// 校验并构建
+    pub fn build(self) -> Result<LatencyModel> {
+        let LatencyModel { min, max } = self.0;
+        if min > max {
+            return Err(AppError::Config(format!("latency min {min:?} > max {max:?}")));
+        }
+        Ok(self.0)
+    }
+}
+
+/// 对数正态延迟，适合磁盘等多数请求集中、少量偏慢的后端
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogNormalLatency {
+    /// 中位数
+    #[serde(with = "humantime_serde")]
+    pub median: Duration,
+    /// ln 延迟的标准差
+    pub sigma: f64,
+}
+
+impl BackendModel for LogNormalLatency {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let z: f64 = rng.sample(StandardNormal);
+        self.median.mul_f64((self.sigma * z).exp())
+    }
+}
+
+/// Pareto 长尾延迟，适合尾延迟显著的 RPC 后端；样本不超过 `scale * PARETO_CAP`
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParetoLatency {
+    /// 最小延迟
+    #[serde(with = "humantime_serde")]
+    pub scale: Duration,
+    /// 形状参数，越小尾部越重
+    pub shape: f64,
+}
+
+impl BackendModel for ParetoLatency {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        // u ∈ (0, 1]，避免除零
+        let u = 1.0 - rng.random::<f64>();
+        let factor = u.powf(-1.0 / self.shape).min(PARETO_CAP as f64);
+        self.scale.mul_f64(factor)
+    }
+}
+
+/// 可在配置中选择的后端延迟模型
+///
+/// 配置文件中写作 `[latency]` 表加 `kind` 字段，如 `kind = "pareto"`、`scale = "1ms"`、`shape = 1.5`；
+/// 环境变量与命令行写作 `zero`、`constant:1ms`、`uniform:1ms..2ms`、`lognormal:1ms:0.5`、`pareto:1ms:1.5`
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Backend {
+    /// 不等待，模拟纯内存后端
+    Zero,
+    /// 固定延迟
+    Constant {
+        #[serde(with = "humantime_serde")]
+        delay: Duration,
+    },
+    /// 均匀分布
+    Uniform(LatencyModel),
+    /// 对数正态分布
+    LogNormal(LogNormalLatency),
+    /// Pareto 长尾分布
+    Pareto(ParetoLatency),
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::Uniform(LatencyModel::default())
+    }
+}
+
+impl Backend {
+    /// 覆盖均匀分布的上下界，非均匀模型以默认范围为基础切换为均匀分布；均未指定时不变
+    pub fn with_bounds(self, min: Option<Duration>, max: Option<Duration>) -> Result<Self> {
+        if min.is_none() && max.is_none() {
+            return Ok(self);
+        }
+        let base = match self {
+            Self::Uniform(m) => m,
+            _ => LatencyModel::default(),
+        };
+        let m = LatencyModel::builder()
+            .min(min.unwrap_or(base.min))
+            .max(max.unwrap_or(base.max))
+            .build()?;
+        Ok(Self::Uniform(m))
+    }
+
+    /// 校验参数
+    pub fn validate(&self) -> Result<()> {
+        let ok = match *self {
+            Self::Zero | Self::Constant { .. } => true,
+            Self::Uniform(m) => m.min <= m.max,
+            Self::LogNormal(m) => m.sigma >= 0.0,
+            Self::Pareto(m) => m.shape > 0.0,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(AppError::Config(format!("invalid latency model `{self}`")))
+        }
+    }
+}
+
+impl BackendModel for Backend {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        match self {
+            Self::Zero => Duration::ZERO,
+            Self::Constant { delay } => *delay,
+            Self::Uniform(m) => m.sample(rng),
+            Self::LogNormal(m) => m.sample(rng),
+            Self::Pareto(m) => m.sample(rng),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = humantime::format_duration;
+        match *self {
+            Self::Zero => write!(f, "zero"),
+            Self::Constant { delay } => write!(f, "constant:{}", d(delay)),
+            Self::Uniform(m) => write!(f, "uniform:{}..{}", d(m.min), d(m.max)),
+            Self::LogNormal(m) => write!(f, "lognormal:{}:{}", d(m.median), m.sigma),
+            Self::Pareto(m) => write!(f, "pareto:{}:{}", d(m.scale), m.shape),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || AppError::Config(format!("invalid latency model `{s}`"));
+        let dur = |v: &str| humantime::parse_duration(v.trim()).map_err(|_| bad());
+        let num = |v: &str| v.trim().parse::<f64>().map_err(|_| bad());
+        let (kind, arg) = s.split_once(':').unwrap_or((s, ""));
+        let backend = match kind {
+            "zero" => Self::Zero,
+            "constant" => Self::Constant { delay: dur(arg)? },
+            "uniform" => {
+                let (min, max) = arg.split_once("..").ok_or_else(bad)?;
+                Self::Uniform(LatencyModel::builder().min(dur(min)?).max(dur(max)?).build()?)
+            }
+            "lognormal" => {
+                let (median, sigma) = arg.split_once(':').ok_or_else(bad)?;
+                Self::LogNormal(LogNormalLatency {
+                    median: dur(median)?,
+                    sigma: num(sigma)?,
+                })
+            }
+            "pareto" => {
+                let (scale, shape) = arg.split_once(':').ok_or_else(bad)?;
+                Self::Pareto(ParetoLatency {
+                    scale: dur(scale)?,
+                    shape: num(shape)?,
+                })
+            }
+            _ => return Err(bad()),
+        };
+        backend.validate()?;
+        Ok(backend)
+    }
+}
+
+/// 回源故障注入：每次加载以 `error_rate` 概率在正常延迟后失败，以 `timeout_rate` 概率等满 `timeout` 后超时，
+/// 失败后最多重试 `retries` 次；失败的加载不写入缓存
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Faults {
+    /// 出错概率
+    pub error_rate: f64,
+    /// 超时概率
+    pub timeout_rate: f64,
+    /// 超时等待时长
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+    /// 失败后的重试次数
+    pub retries: u32,
+}
+
+impl Default for Faults {
+    fn default() -> Self {
+        Self {
+            error_rate: 0.0,
+            timeout_rate: 0.0,
+            timeout: BACKEND_TIMEOUT,
+            retries: 0,
+        }
+    }
+}
+
+/// 单次回源故障
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// 正常延迟后返回错误
+    Error,
+    /// 等满超时时长
+    Timeout,
+}
+
+impl Faults {
+    /// 是否注入故障
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.error_rate > 0.0 || self.timeout_rate > 0.0
+    }
+
+    /// 采样一次回源是否失败；未注入故障时不消耗随机数，保证延迟序列不变
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Fault> {
+        if !self.enabled() {
+            return None;
+        }
+        let r = rng.random::<f64>();
+        if r < self.error_rate {
+            Some(Fault::Error)
+        } else if r < self.error_rate + self.timeout_rate {
+            Some(Fault::Timeout)
+        } else {
+            None
+        }
+    }
+
+    /// 校验概率范围
+    pub fn validate(&self) -> Result<()> {
+        let (e, t) = (self.error_rate, self.timeout_rate);
+        if (0.0..=1.0).contains(&e) && (0.0..=1.0).contains(&t) && e + t <= 1.0 {
+            Ok(())
+        } else {
+            Err(AppError::Config(format!("faults error_rate {e} + timeout_rate {t} must be within [0, 1]")))
+        }
+    }
+}
+
+/// 模拟一次带故障注入的对 `key` 的回源（含重试），在 `clock` 上等待，返回 (是否成功, 重试次数)；
+/// 后端 I/O 出错时返回错误
+pub async fn simulate_backend_fetch<B: BackendModel, T: TimeSource, R: Rng>(
+    model: &B,
+    faults: &Faults,
+    key: usize,
+    clock: &T,
+    rng: &mut R,
+) -> Result<(bool, u32)> {
+    for attempt in 0..=faults.retries {
+        match faults.sample(rng) {
+            None => {
+                simulate_backend_latency(model, key, clock, rng).await?;
+                return Ok((true, attempt));
+            }
+            Some(Fault::Error) => simulate_backend_latency(model, key, clock, rng).await?,
+            Some(Fault::Timeout) => clock.sleep(faults.timeout).await,
+        }
+    }
+    Ok((false, faults.retries))
+}
+
+/// 对 `key` 回源一次，见 [`BackendModel::fetch`]
+#[inline]
+pub async fn simulate_backend_latency<B: BackendModel, T: TimeSource, R: Rng>(
+    model: &B,
+    key: usize,
+    clock: &T,
+    rng: &mut R,
+) -> Result<()> {
+    model.fetch(key, clock, rng).await
+}
@@ -0,0 +1,112 @@
+//! 文件读取后端
+//! 回源时经 Compio 异步文件 I/O 从预先生成的数据文件读取一个块，测量真实异步 I/O 与缓存操作的交错，而非定时器睡眠。
+//! key 经 FNV 哈希映射到块，读取位置分散在整个文件中
+//!
+//! 数据文件小于可用内存时，多数读取落在操作系统页缓存上；需要测量磁盘延迟时使用远大于内存的文件
+
+use crate::distribution::fnv64;
+use crate::{AppError, BackendModel, FileBackendConfig, Result, TimeSource};
+use compio::buf::BufResult;
+use compio::fs::File;
+use compio::io::AsyncReadAtExt;
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::fs;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// 从数据文件读取块的回源后端
+#[derive(Debug)]
+pub struct FileBackend {
+    path: PathBuf,
+    block: usize,
+    blocks: u64,
+    /// 首次回源时在当前运行时中打开
+    file: OnceLock<File>,
+}
+
+impl FileBackend {
+    /// 准备数据文件：不存在或小于配置大小时重新生成，否则直接复用
+    pub fn new(cfg: &FileBackendConfig) -> Result<Self> {
+        let blocks = cfg.size / cfg.block.max(1) as u64;
+        if cfg.block == 0 || blocks == 0 {
+            return Err(AppError::Config(
+                "backend_file.block must be > 0 and backend_file.size >= block".into(),
+            ));
+        }
+        let path = cfg.path();
+        let len = blocks * cfg.block as u64;
+        if fs::metadata(&path).map_or(true, |m| m.len() < len) {
+            generate(&path, blocks, cfg.block)?;
+        }
+        Ok(Self {
+            path,
+            block: cfg.block,
+            blocks,
+            file: OnceLock::new(),
+        })
+    }
+
+    /// 数据文件路径
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 数据文件的块数
+    #[inline]
+    pub fn blocks(&self) -> u64 {
+        self.blocks
+    }
+
+    /// 读取 `key` 对应的块
+    async fn read(&self, key: usize) -> Result<()> {
+        let file = match self.file.get() {
+            Some(file) => file,
+            None => {
+                let file = File::open(&self.path).await?;
+                self.file.get_or_init(|| file)
+            }
+        };
+        let pos = fnv64(&(key as u64).to_le_bytes()) % self.blocks * self.block as u64;
+        let BufResult(res, _) = file.read_exact_at(Vec::with_capacity(self.block), pos).await;
+        Ok(res?)
+    }
+}
+
+/// 不采样延迟，耗时全部来自真实读取
+impl BackendModel for FileBackend {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, _rng: &mut R) -> Duration {
+        Duration::ZERO
+    }
+
+    #[inline]
+    fn fetch<T: TimeSource, R: Rng + ?Sized>(
+        &self,
+        key: usize,
+        _clock: &T,
+        _rng: &mut R,
+    ) -> impl Future<Output = Result<()>> {
+        self.read(key)
+    }
+}
+
+/// 以随机内容生成 `blocks` 个块，先写临时文件再改名，并发的运行不会读到不完整的文件
+fn generate(path: &Path, blocks: u64, block: usize) -> Result<()> {
+    let tmp = path.with_extension(format!("{}.tmp", process::id()));
+    let mut w = BufWriter::new(fs::File::create(&tmp)?);
+    let mut rng = SmallRng::seed_from_u64(blocks);
+    let mut buf = vec![0; block];
+    for _ in 0..blocks {
+        rng.fill_bytes(&mut buf);
+        w.write_all(&buf)?;
+    }
+    w.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
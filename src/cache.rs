@@ -1,278 +1,1735 @@
 //! 缓存抽象模块
 //! 定义缓存操作的统一接口
 
-use crate::config::*;
-use crate::error::{AppError, Result};
+use crate::{AppError, Backend, BackendModel, Config, CostModel, DiskTier, Progress, ProgressHook, Faults, Key, KeyDistribution, MmapOps, Scramble, LatencyRecorder, LoadCounter, Occupancy, PopularityTier, PopularityTiers, RealClock, TierStats, Result, SeriesPoint, SeriesRecorder, SizeSampler, TimeSource, Value, WarmupConfig, WarmupStrategy, WorkloadConfig, simulate_backend_fetch, simulate_backend_latency};
+use crate::config::{BUDGET_CHECK_EVERY, PROGRESS_EVERY};
+#[cfg(feature = "mini-moka")]
+use crate::CostFn;
+#[cfg(feature = "hashlink")]
 use hashlink::LruCache as HashlinkLruCache;
+#[cfg(feature = "lru")]
 use lru::LruCache;
+#[cfg(feature = "mini-moka")]
 use mini_moka::unsync::Cache as MokaCache;
+#[cfg(feature = "moka")]
+use moka::policy::EvictionPolicy;
+#[cfg(feature = "moka")]
+use moka::sync::{Cache as MokaSyncCache, CacheBuilder as MokaSyncBuilder};
+#[cfg(feature = "quick_cache")]
+use quick_cache::unsync::Cache as QuickCache;
+#[cfg(feature = "quick_cache")]
+use quick_cache::Weighter;
+#[cfg(feature = "schnellru")]
+use schnellru::LruMap as SchnellLruMap;
 use rand::prelude::*;
 use rand::rngs::SmallRng;
-use std::time::Duration;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fs;
+#[cfg(any(feature = "hashlink", feature = "lru", feature = "mini-moka", feature = "moka", feature = "quick_cache", feature = "schnellru"))]
+use std::hash::Hash;
+use std::marker::PhantomData;
+#[cfg(feature = "lru")]
+use std::num::NonZeroUsize;
+use std::ops::AddAssign;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// 操作类型枚举
+#[cfg(feature = "hashlink")]
+pub mod arc;
+pub mod baseline;
+#[cfg(feature = "dashmap")]
+pub mod capped;
+pub mod clock;
+pub mod concurrent;
+pub mod converge;
+pub mod cost;
+pub mod disk;
+pub mod fairness;
+pub mod fixed;
+pub mod future;
+pub mod gdsf;
+pub mod interrupt;
+pub mod kind;
+#[cfg(any(feature = "hashlink", feature = "lru"))]
+pub mod locked;
+pub mod lockstep;
+pub mod loads;
+pub mod micro;
+pub mod occupancy;
+pub mod progress;
+pub mod remote;
+pub mod s3fifo;
+#[cfg(feature = "lru")]
+pub mod sharded;
+pub mod sieve;
+pub mod time;
+pub mod ttl;
+
+/// 操作类型枚举，`V` 为缓存值类型，`K` 为键类型
 #[derive(Clone, Copy, Debug)]
-pub enum Op {
-    Read(usize),
-    Write(usize, usize),
+#[non_exhaustive]
+pub enum Op<V = usize, K = usize> {
+    /// 读取 key，未命中时回源并写入
+    Read(K),
+    /// 不更新访问顺序的读取，未命中时回源并写入
+    Peek(K),
+    /// 回源写入 (key, value)，更新 key 空间内已有的 key
+    Write(K, V),
+    /// 插入后端此前不存在的新 key，缓存中必然没有该 key
+    Insert(K, V),
+    /// 读取后端不存在的 key，未命中时回源得到空结果，是否缓存该结果由运行器决定
+    ReadMissing(K),
+    /// 失效 key：从缓存中移除，不回源
+    Delete(K),
+    /// 从起始 key 开始按 id 连续读取 `len` 个 key，经 [`CacheOps::get_many`] 批量查询，未命中的逐个回源并写入
+    Scan(K, usize),
+}
+
+impl<V, K> Op<V, K> {
+    /// 操作的 key，范围扫描为起始 key
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Read(k) | Self::Peek(k) | Self::ReadMissing(k) | Self::Delete(k) | Self::Scan(k, _) => k,
+            Self::Write(k, _) | Self::Insert(k, _) => k,
+        }
+    }
+}
+
+impl<V, K: Key> Op<V, K> {
+    /// 操作涉及的全部 key，范围扫描经 [`Key::successor`] 展开，超出 id 范围的部分截断
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        let (single, len) = match self {
+            Self::Scan(_, len) => (None, *len),
+            op => (Some(op.key().clone()), 0),
+        };
+        let start = self.key();
+        single.into_iter().chain((0..len).map_while(move |n| start.successor(n)))
+    }
+}
+
+/// 单个操作的结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Outcome {
+    /// 读命中
+    Hit,
+    /// 读未命中，已回源
+    Miss,
+    /// 读未命中，回源失败（含重试），未写入缓存
+    Failed,
+    /// 更新已有 key
+    Write,
+    /// 插入新 key
+    Insert,
+    /// 删除
+    Delete,
+    /// 范围扫描，各 key 的命中与未命中（含回源失败）次数
+    Scan { hits: u64, misses: u64 },
+}
+
+/// 单次运行结果
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RunStats {
+    /// 执行的操作数（范围扫描计一次）
+    pub ops: u64,
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数（含回源失败与磁盘层命中）
+    pub misses: u64,
+    /// 内存未命中后由磁盘层满足的次数（计入 `misses`），未启用磁盘层时为 0
+    pub disk_hits: u64,
+    /// 重试后仍失败的回源次数
+    pub errors: u64,
+    /// 回源重试次数
+    pub retries: u64,
+    /// 向后端发出的请求数：读未命中的回源（含重试，不含磁盘层命中）与写操作的写回
+    pub backend_calls: u64,
+    /// 不存在的 key 命中负缓存的次数（计入 `hits`）
+    pub negative_hits: u64,
+    /// 不存在的 key 未命中的次数（计入 `misses`）
+    pub negative_misses: u64,
+    /// 浪费的回源：本次运行中已成功加载过、随后又被淘汰而再次回源的次数
+    pub wasted_loads: u64,
+    /// 淘汰次数（含准入拒绝），缓存不支持 [`CacheOps::entry_count`] 时为 0
+    pub evictions: u64,
+    /// 删除操作次数
+    pub deletes: u64,
+    /// 更新已有 key 的写操作次数
+    pub updates: u64,
+    /// 插入新 key 的写操作次数
+    pub inserts: u64,
+    /// 插入前后观察到的最大常驻条目数，缓存不支持 [`CacheOps::entry_count`] 时为 0；
+    /// 分片 LRU 与 moka 取运行结束时的条目数
+    pub max_resident: u64,
+    /// 每隔 [`CacheRunner::with_occupancy`] 个操作采样的常驻条目数，未启用或缓存不提供条目数时无采样
+    pub occupancy: Occupancy,
+    /// 在缓存调用（get、peek、insert、remove）内的真实耗时
+    pub cache_time: Duration,
+    /// 等待回源（含磁盘层）的真实耗时，模拟时间模式下回源不真实等待
+    pub backend_time: Duration,
+    /// 运行耗时，按运行器的时间源计，模拟时间模式下为模拟时间
+    pub elapsed: Duration,
+    /// 按热度层（头部、中部、尾部）的读命中统计，运行器未设置 [`PopularityTiers`] 时全为 0
+    pub tiers: [TierStats; 3],
+    /// 到达 [`CacheRunner::with_deadline`] 的截止时间或收到中断后提前停止，统计只覆盖已完成的操作
+    pub partial: bool,
+}
+
+impl RunStats {
+    /// 读操作总数
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// 命中率（百分比）
+    #[inline]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64 * 100.0
+        }
+    }
+
+    /// 磁盘层命中率（百分比）：内存未命中中由磁盘层满足的比例
+    #[inline]
+    pub fn disk_hit_rate(&self) -> f64 {
+        if self.misses == 0 {
+            0.0
+        } else {
+            self.disk_hits as f64 / self.misses as f64 * 100.0
+        }
+    }
+
+    /// 热度层的读命中统计
+    #[inline]
+    pub fn tier(&self, tier: PopularityTier) -> TierStats {
+        self.tiers[tier as usize]
+    }
+
+    /// 吞吐量：每秒操作数
+    #[inline]
+    pub fn ops_per_sec(&self) -> f64 {
+        self.per_sec(self.ops)
+    }
+
+    /// 每秒未命中数
+    #[inline]
+    pub fn misses_per_sec(&self) -> f64 {
+        self.per_sec(self.misses)
+    }
+
+    /// 每秒后端请求数
+    #[inline]
+    pub fn backend_calls_per_sec(&self) -> f64 {
+        self.per_sec(self.backend_calls)
+    }
+
+    #[inline]
+    fn per_sec(&self, n: u64) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 { n as f64 / secs } else { 0.0 }
+    }
+
+    /// 每千个操作的淘汰次数，按 [`RunStats::ops`] 计
+    #[inline]
+    pub fn evictions_per_kop(&self) -> f64 {
+        if self.ops == 0 {
+            0.0
+        } else {
+            self.evictions as f64 * 1000.0 / self.ops as f64
+        }
+    }
+
+    /// 每个操作在缓存调用内的平均耗时（纳秒），按 [`RunStats::ops`] 计
+    #[inline]
+    pub fn cache_ns_per_op(&self) -> f64 {
+        if self.ops == 0 {
+            0.0
+        } else {
+            self.cache_time.as_nanos() as f64 / self.ops as f64
+        }
+    }
+
+    /// 过早淘汰率（百分比）：被淘汰后又被请求而重新回源的比例，以浪费的回源近似
+    #[inline]
+    pub fn premature_eviction_rate(&self) -> f64 {
+        if self.evictions == 0 {
+            0.0
+        } else {
+            (self.wasted_loads as f64 / self.evictions as f64 * 100.0).min(100.0)
+        }
+    }
+}
+
+/// 合并并发运行的统计：计数与耗时分量相加，运行耗时取最长者
+impl AddAssign for RunStats {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.ops += rhs.ops;
+        self.hits += rhs.hits;
+        self.misses += rhs.misses;
+        self.disk_hits += rhs.disk_hits;
+        self.errors += rhs.errors;
+        self.retries += rhs.retries;
+        self.backend_calls += rhs.backend_calls;
+        self.negative_hits += rhs.negative_hits;
+        self.negative_misses += rhs.negative_misses;
+        self.wasted_loads += rhs.wasted_loads;
+        self.evictions += rhs.evictions;
+        self.deletes += rhs.deletes;
+        self.updates += rhs.updates;
+        self.inserts += rhs.inserts;
+        self.max_resident = self.max_resident.max(rhs.max_resident);
+        self.occupancy += rhs.occupancy;
+        self.cache_time += rhs.cache_time;
+        self.backend_time += rhs.backend_time;
+        self.elapsed = self.elapsed.max(rhs.elapsed);
+        for (t, r) in self.tiers.iter_mut().zip(rhs.tiers) {
+            *t += r;
+        }
+        self.partial |= rhs.partial;
+    }
 }
 
 /// 缓存操作trait，统一接口
-pub trait CacheOps {
+///
+/// 为新的缓存实现此 trait 即可接入 [`CacheRunner`] 与 [`WarmupManager`]；
+/// 内置淘汰算法只实现 `usize` 键，第三方缓存对任意 `K: Hash + Eq` 实现
+pub trait CacheOps<V = usize, K = usize> {
     /// 获取缓存值
-    fn get(&mut self, key: &usize) -> Option<&usize>;
+    fn get(&mut self, key: &K) -> Option<&V>;
+    
+    /// 查询 key 是否存在，不更新访问顺序（不支持时退化为 get）
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
     
     /// 插入键值对
-    fn insert(&mut self, key: usize, value: usize);
+    fn insert(&mut self, key: K, value: V);
+
+    /// 移除 key，返回移除前是否存在；不支持删除的缓存保持默认的空操作
+    #[inline]
+    fn remove(&mut self, _key: &K) -> bool {
+        false
+    }
+
+    /// 按自身的淘汰策略淘汰一个条目并返回其 key，供 [`CostBounded`](cost::CostBounded) 按代价限额；缓存为空或不支持时返回 None
+    #[inline]
+    fn evict_one(&mut self) -> Option<K> {
+        None
+    }
+
+    /// 是否实现了 [`CacheOps::evict_one`]，[`CostBounded`](cost::CostBounded) 据此拒绝无法按代价淘汰的缓存
+    #[inline]
+    fn can_evict(&self) -> bool {
+        false
+    }
+
+    /// 批量查询，返回各 key 是否命中；默认逐个 `get`，支持批量接口的缓存可覆盖以摊薄单次开销
+    fn get_many(&mut self, keys: &[K]) -> Vec<bool> {
+        keys.iter().map(|k| self.get(k).is_some()).collect()
+    }
+
+    /// 按顺序批量插入；默认逐个 `insert`
+    fn insert_many(&mut self, entries: &[(K, V)])
+    where
+        K: Clone,
+        V: Clone,
+    {
+        for (key, value) in entries {
+            self.insert(key.clone(), value.clone());
+        }
+    }
     
+    /// 导出当前条目，按最久未使用到最近使用排序；不支持时返回 None
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        None
+    }
+    
+    /// 按顺序导入条目，最后一项成为最近使用
+    fn import_entries(&mut self, entries: &[(K, V)])
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.insert_many(entries);
+    }
+    
+    /// 当前条目数，供运行器按插入前后的差值统计淘汰；不支持或不精确时返回 None
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// 容量上限（条目数），供按容量折算占用率；无上限或未知时返回 None
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        None
+    }
+
     /// 获取缓存名称（用于日志）
     fn name(&self) -> &'static str;
 }
 
-impl CacheOps for HashlinkLruCache<usize, usize> {
+/// 借用运行，运行结束后仍可读取包装缓存的统计
+impl<V, K, C: CacheOps<V, K> + ?Sized> CacheOps<V, K> for &mut C {
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<&V> {
+        (**self).get(key)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        (**self).peek(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        (**self).insert(key, value);
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        (**self).remove(key)
+    }
+
+    #[inline]
+    fn evict_one(&mut self) -> Option<K> {
+        (**self).evict_one()
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        (**self).can_evict()
+    }
+
+    #[inline]
+    fn get_many(&mut self, keys: &[K]) -> Vec<bool> {
+        (**self).get_many(keys)
+    }
+
+    #[inline]
+    fn insert_many(&mut self, entries: &[(K, V)])
+    where
+        K: Clone,
+        V: Clone,
+    {
+        (**self).insert_many(entries);
+    }
+
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        (**self).export_entries()
+    }
+
+    fn import_entries(&mut self, entries: &[(K, V)])
+    where
+        K: Clone,
+        V: Clone,
+    {
+        (**self).import_entries(entries);
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        (**self).entry_count()
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        (**self).capacity_hint()
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+}
+
+#[cfg(feature = "hashlink")]
+impl<K: Hash + Eq, V> CacheOps<V, K> for HashlinkLruCache<K, V> {
     #[inline]
-    fn get(&mut self, key: &usize) -> Option<&usize> {
+    fn get(&mut self, key: &K) -> Option<&V> {
         self.get(key)
     }
     
     #[inline]
-    fn insert(&mut self, key: usize, value: usize) {
+    fn peek(&mut self, key: &K) -> bool {
+        HashlinkLruCache::peek(self, key).is_some()
+    }
+    
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
         self.insert(key, value);
     }
     
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        HashlinkLruCache::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn evict_one(&mut self) -> Option<K> {
+        self.remove_lru().map(|(k, _)| k)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+    
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Some(self.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+    
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(HashlinkLruCache::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(HashlinkLruCache::capacity(self))
+    }
+
     #[inline]
     fn name(&self) -> &'static str {
         "Hashlink LRU"
     }
 }
 
-impl CacheOps for LruCache<usize, usize> {
+/// 创建容量为 `capacity` 的 `lru::LruCache`，容量为 0 时返回配置错误
+#[cfg(feature = "lru")]
+pub fn lru_cache<K: Hash + Eq, V>(capacity: usize) -> Result<LruCache<K, V>> {
+    let capacity = NonZeroUsize::new(capacity).ok_or_else(|| AppError::Config("capacity must be > 0".into()))?;
+    Ok(LruCache::new(capacity))
+}
+
+#[cfg(feature = "lru")]
+impl<K: Hash + Eq, V> CacheOps<V, K> for LruCache<K, V> {
     #[inline]
-    fn get(&mut self, key: &usize) -> Option<&usize> {
+    fn get(&mut self, key: &K) -> Option<&V> {
         self.get(key)
     }
     
     #[inline]
-    fn insert(&mut self, key: usize, value: usize) {
+    fn peek(&mut self, key: &K) -> bool {
+        LruCache::peek(self, key).is_some()
+    }
+    
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
         self.push(key, value);
     }
     
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        self.pop(key).is_some()
+    }
+
+    #[inline]
+    fn evict_one(&mut self) -> Option<K> {
+        self.pop_lru().map(|(k, _)| k)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+    
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        // iter 为最近使用优先，反转得到 LRU 到 MRU
+        Some(self.iter().rev().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+    
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(LruCache::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(LruCache::cap(self).get())
+    }
+
     #[inline]
     fn name(&self) -> &'static str {
         "LRU"
     }
 }
 
-impl CacheOps for MokaCache<usize, usize> {
+#[cfg(feature = "mini-moka")]
+impl<K: Hash + Eq, V> CacheOps<V, K> for MokaCache<K, V> {
     #[inline]
-    fn get(&mut self, key: &usize) -> Option<&usize> {
+    fn get(&mut self, key: &K) -> Option<&V> {
         self.get(key)
     }
     
     #[inline]
-    fn insert(&mut self, key: usize, value: usize) {
+    fn peek(&mut self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+    
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
         self.insert(key, value);
     }
     
+    /// 顺序为哈希表顺序，频率统计不会导出
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        let present = self.contains_key(key);
+        self.invalidate(key);
+        present
+    }
+    
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Some(self.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+    
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        usize::try_from(MokaCache::entry_count(self)).ok()
+    }
+
+    /// 带权重构建时为权重上限而非条目数
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        self.policy().max_capacity().and_then(|c| usize::try_from(c).ok())
+    }
+
     #[inline]
     fn name(&self) -> &'static str {
         "Mini-Moka Unsync"
     }
 }
 
-/// 优化的 Mini-Moka 缓存构建器
+/// 以 [`CostWeighter`](cost::CostWeighter) 等 weigher 构建时按权重限额
+#[cfg(feature = "quick_cache")]
+impl<K: Hash + Eq, V, W: Weighter<K, V> + Clone> CacheOps<V, K> for QuickCache<K, V, W> {
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<&V> {
+        QuickCache::get(self, key)
+    }
+    
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        QuickCache::peek(self, key).is_some()
+    }
+    
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+    
+    /// 顺序为内部存储顺序
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        QuickCache::remove(self, key).is_some()
+    }
+    
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Some(self.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+    
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(QuickCache::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        usize::try_from(QuickCache::capacity(self)).ok()
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Quick Cache Unsync"
+    }
+}
+
+#[cfg(feature = "schnellru")]
+impl<K: Hash + Eq, V> CacheOps<V, K> for SchnellLruMap<K, V> {
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get(key).map(|v| &*v)
+    }
+    
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        SchnellLruMap::peek(self, key).is_some()
+    }
+    
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+    
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        SchnellLruMap::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn evict_one(&mut self) -> Option<K> {
+        self.pop_oldest().map(|(k, _)| k)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+    
+    fn export_entries(&self) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        // iter 为最近使用优先，反转得到 LRU 到 MRU
+        Some(self.iter().rev().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+    
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(SchnellLruMap::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(self.limiter().max_length() as usize)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Schnellru"
+    }
+}
+
+#[cfg(any(feature = "mini-moka", feature = "moka"))]
+/// 优化的 Mini-Moka / Moka 缓存构建器
+///
+/// mini-moka 0.10 的 TinyLFU 准入过滤始终开启，频率草图在条目达到容量一半时按容量自动分配，没有可调参数；
+/// 需要单独观察准入策略的贡献时，用 [`Self::sync_cache_builder`] 对比 moka 开关准入的结果
 pub struct OptimizedMokaCacheBuilder;
 
+#[cfg(feature = "moka")]
+impl OptimizedMokaCacheBuilder {
+    /// moka 同步缓存的构建器，可继续设置监听器等选项；`admission` 为 false 时改用纯 LRU 淘汰策略，
+    /// 不经 TinyLFU 准入过滤、不维护频率草图
+    pub fn sync_cache_builder<K, V>(capacity: u64, admission: bool) -> MokaSyncBuilder<K, V, MokaSyncCache<K, V>>
+    where
+        K: Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        let policy = if admission { EvictionPolicy::tiny_lfu() } else { EvictionPolicy::lru() };
+        MokaSyncCache::builder().max_capacity(capacity).eviction_policy(policy)
+    }
+}
+
+#[cfg(feature = "mini-moka")]
 impl OptimizedMokaCacheBuilder {
     /// 创建优化的 Mini-Moka 缓存
-    pub fn build_optimized_cache() -> MokaCache<usize, usize> {
+    pub fn build_optimized_cache(capacity: u64) -> MokaCache<usize, usize> {
         MokaCache::builder()
             // 预分配初始容量，减少动态扩容开销
-            .initial_capacity((CACHE_CAPACITY / 2) as usize)
+            .initial_capacity((capacity / 2) as usize)
             // 使用权重感知，基于实际内存大小
             .weigher(|_key, _value: &usize| -> u32 {
                 // 每个 usize 条目的权重（内存字节数）
                 std::mem::size_of::<(usize, usize)>() as u32
             })
             // 设置最大权重容量（字节）
-            .max_capacity((CACHE_CAPACITY as u32 * std::mem::size_of::<(usize, usize)>() as u32) as u64)
+            .max_capacity(capacity * std::mem::size_of::<(usize, usize)>() as u64)
+            .build()
+    }
+
+    /// 创建按条目实际字节数（键大小 + [`Value::weight`]）加权的缓存，`max_bytes` 为字节预算
+    pub fn build_weighted_cache<K: Hash + Eq, V: Value>(max_bytes: u64) -> MokaCache<K, V> {
+        MokaCache::builder()
+            // 权重按字节计，u32 放不下的值按最大权重处理
+            .weigher(|_key, value: &V| -> u32 {
+                u32::try_from(std::mem::size_of::<K>() + value.weight()).unwrap_or(u32::MAX)
+            })
+            // 设置最大权重容量（字节）
+            .max_capacity(max_bytes)
             .build()
     }
     
-    /// 创建带过期策略的缓存（适用于长时间运行的场景）
-    pub fn build_cache_with_expiration() -> MokaCache<usize, usize> {
+    /// 创建按 `cost` 计算条目代价的缓存，`budget` 为代价预算
+    pub fn build_cost_cache(budget: u64, cost: CostFn) -> MokaCache<usize, usize> {
+        MokaCache::builder().weigher(cost).max_capacity(budget).build()
+    }
+
+    /// 创建带过期策略的缓存：写入 `ttl` 后或 `tti` 内未被访问即过期
+    pub fn build_cache_with_expiration(capacity: u64, ttl: Duration, tti: Duration) -> MokaCache<usize, usize> {
         MokaCache::builder()
-            .initial_capacity((CACHE_CAPACITY / 2) as usize)
+            .initial_capacity((capacity / 2) as usize)
             .weigher(|_key, _value: &usize| -> u32 {
                 std::mem::size_of::<(usize, usize)>() as u32
             })
-            .max_capacity((CACHE_CAPACITY as u32 * std::mem::size_of::<(usize, usize)>() as u32) as u64)
-            // 设置TTL和TTI以优化内存使用
-            .time_to_live(Duration::from_secs(600)) // 10分钟TTL
-            .time_to_idle(Duration::from_secs(120)) // 2分钟TTI
+            .max_capacity(capacity * std::mem::size_of::<(usize, usize)>() as u64)
+            .time_to_live(ttl)
+            .time_to_idle(tti)
             .build()
     }
     
     /// 创建高性能缓存（仅使用基本优化）
-    pub fn build_high_performance_cache() -> MokaCache<usize, usize> {
+    pub fn build_high_performance_cache<K: Hash + Eq, V: Clone>(capacity: u64) -> MokaCache<K, V> {
         MokaCache::builder()
             // 预分配初始容量，减少动态扩容开销
-            .initial_capacity((CACHE_CAPACITY / 2) as usize)
+            .initial_capacity((capacity / 2) as usize)
             // 保持简单的条目计数，避免权重计算开销
-            .max_capacity(CACHE_CAPACITY)
+            .max_capacity(capacity)
             .build()
     }
 }
 
-/// 模拟后端延迟
-#[inline]
-pub async fn simulate_backend_latency(rng: &mut SmallRng) {
-    let delay_ns = rng.random_range(MIN_DELAY_US * 1000..=MAX_DELAY_US * 1000);
-    compio::time::sleep(Duration::from_nanos(delay_ns)).await;
-}
+/// 不存在于后端的 key 的 id 标记位
+const MISSING_BIT: usize = 1 << (usize::BITS - 1);
 
-/// 工作负载生成器
-pub struct WorkloadGenerator {
-    rng: StdRng,
+/// 工作负载生成器，随机数生成器 `R` 缺省为以配置种子初始化的 [`StdRng`]
+pub struct WorkloadGenerator<R = StdRng> {
+    cfg: WorkloadConfig,
+    rng: R,
+    /// 已生成的新 key 数，跨阶段递增保证不重复
+    inserted: usize,
 }
 
 impl WorkloadGenerator {
     /// 创建新的工作负载生成器
-    pub fn new(seed: u64) -> Self {
+    pub fn new(cfg: WorkloadConfig) -> Self {
         Self {
-            rng: StdRng::seed_from_u64(seed),
+            rng: StdRng::seed_from_u64(cfg.seed),
+            cfg,
+            inserted: 0,
         }
     }
-    
-    /// 生成工作负载操作序列
-    pub fn generate(&mut self) -> Result<Vec<Op>> {
-        let zipf = rand_distr::Zipf::new(TOTAL_KEYS as f64, ZIPF_S)
-            .map_err(|e| AppError::ZipfCreate(e.to_string()))?;
-        
-        let mut ops = Vec::with_capacity(WORKLOAD_SIZE);
-        
-        for _ in 0..WORKLOAD_SIZE {
-            let key = zipf.sample(&mut self.rng) as usize;
-            let is_read = self.rng.random::<f64>() < READ_RATIO;
+
+    /// 读取 [`Self::save`] 保存的工作负载，遇到未知操作时报错而非跳过，保证回放与保存时一致
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Op>> {
+        let file = MmapOps::open(path)?;
+        let view = file.view();
+        (0..file.len())
+            .map(|i| view.get(i).ok_or_else(|| AppError::InvalidWorkload(format!("unknown op at {i}"))))
+            .collect()
+    }
+}
+
+impl<R: RngCore> WorkloadGenerator<R> {
+    /// 替换随机数生成器，如用 `rand_chacha` 得到跨平台与 rand 版本一致的序列；
+    /// 配置中的种子此后只用于打散映射
+    pub fn with_rng<S: RngCore>(self, rng: S) -> WorkloadGenerator<S> {
+        WorkloadGenerator {
+            cfg: self.cfg,
+            rng,
+            inserted: self.inserted,
+        }
+    }
+
+    /// 配置指纹：相同配置生成的操作序列逐位一致，可据此复用已保存的工作负载
+    pub fn fingerprint(&self) -> Result<u64> {
+        let json = serde_json::to_vec(&self.cfg)?;
+        Ok(crate::distribution::fnv64(&json))
+    }
+
+    /// 生成工作负载并以二进制格式写入 `path`，返回生成的操作序列
+    pub fn save(&mut self, path: impl AsRef<Path>) -> Result<Vec<Op>> {
+        let ops = self.generate()?;
+        crate::save_ops(path, &ops)?;
+        Ok(ops)
+    }
+
+    /// 从缓存目录读取与当前配置指纹匹配的工作负载，不存在时生成并写入
+    ///
+    /// 文件名为 `workload-<指纹>.bin`，配置变化后自动生成新文件；文件可拷贝到其他机器逐位回放
+    pub fn cached(&mut self, dir: impl AsRef<Path>) -> Result<Vec<Op>> {
+        let dir = dir.as_ref();
+        let path = dir.join(format!("workload-{:016x}.bin", self.fingerprint()?));
+        if path.exists() {
+            return WorkloadGenerator::load(&path);
+        }
+        fs::create_dir_all(dir)?;
+        // 先写临时文件再改名，避免并发运行读到写了一半的文件
+        let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+        let ops = self.save(&tmp)?;
+        fs::rename(&tmp, &path)?;
+        Ok(ops)
+    }
+
+    /// 生成工作负载操作序列（按配置的分布采样 key，配置多个阶段时热点逐阶段迁移）
+    ///
+    /// 所有生成方法都先采样整数 id，再由 [`Key::from_id`] 转为键类型 `K`
+    pub fn generate<V: Value, K: Key>(&mut self) -> Result<Vec<Op<V, K>>> {
+        let phases = self.cfg.phases;
+        if phases > 1 {
+            // 各阶段向上取整，截掉多出的部分，最后一个阶段承担余数
+            let mut ops = self.generate_phased(phases, self.cfg.size.div_ceil(phases))?;
+            ops.truncate(self.cfg.size);
+            return Ok(ops);
+        }
+        self.generate_with_params(self.cfg.zipf_s, self.cfg.total_keys)
+    }
+
+    /// 分阶段生成：每个阶段重新随机排列采样 id 到 key 的映射，模拟热点漂移
+    ///
+    /// 第一阶段使用原始映射（与 [`Self::generate`] 的单阶段一致），共生成 `phases * ops_per_phase` 个操作
+    pub fn generate_phased<V: Value, K: Key>(
+        &mut self,
+        phases: usize,
+        ops_per_phase: usize,
+    ) -> Result<Vec<Op<V, K>>> {
+        let n = self.cfg.total_keys;
+        let mut dist = self.cfg.distribution.build(n, self.cfg.zipf_s)?;
+        // perm[id] 为该 id 当前对应的 key，id 从 1 开始；打散时以打散映射为初始排列
+        let mut perm = match self.scramble(n) {
+            Some(scramble) => scramble.into_perm(),
+            None => (0..=n).collect(),
+        };
+        let sizes = SizeSampler::new(&self.cfg.value_size)?;
+        let mut ops = Vec::with_capacity(phases * ops_per_phase);
+        for phase in 0..phases {
+            if phase > 0 {
+                perm[1..].shuffle(&mut self.rng);
+            }
+            let mut permuted = Permuted { inner: &mut *dist, perm: &perm };
+            ops.extend(self.generate_n(ops_per_phase, &sizes, &mut permuted));
+        }
+        Ok(ops)
+    }
+
+    /// 以指定的 Zipf 参数与 Key 空间、按配置的分布生成操作序列，其余参数沿用配置
+    pub fn generate_with_params<V: Value, K: Key>(&mut self, s: f64, total_keys: usize) -> Result<Vec<Op<V, K>>> {
+        let mut dist = self.cfg.distribution.build(total_keys, s)?;
+        match self.scramble(total_keys) {
+            Some(scramble) => {
+                let perm = scramble.into_perm();
+                self.generate_from(&mut Permuted { inner: &mut *dist, perm: &perm })
+            }
+            None => self.generate_from(&mut *dist),
+        }
+    }
+
+    /// 启用打散时 [1, n] 上的映射
+    fn scramble(&self, n: usize) -> Option<Scramble> {
+        self.cfg.scrambled().then(|| Scramble::new(n, self.cfg.seed))
+    }
+
+    /// 使用自定义分布采样 key 生成操作序列
+    ///
+    /// 采样值按 `as usize` 截断为 key，负数与 NaN 映射为 0。
+    /// 值大小分布来自追踪文件且无法加载时返回错误
+    pub fn generate_with<V: Value, K: Key, D: Distribution<f64>>(&mut self, dist: &D) -> Result<Vec<Op<V, K>>> {
+        self.generate_from(&mut Sampled(dist))
+    }
+
+    /// 按 [`KeyDistribution`] 采样 key 生成操作序列
+    ///
+    /// 配置了扫描时，扫描读计入总操作数，扫描 key 从 `total_keys + 1` 开始，不与分布采样重叠
+    pub fn generate_from<V: Value, K: Key, D: KeyDistribution + ?Sized>(
+        &mut self,
+        dist: &mut D,
+    ) -> Result<Vec<Op<V, K>>> {
+        let sizes = SizeSampler::new(&self.cfg.value_size)?;
+        Ok(self.generate_n(self.cfg.size, &sizes, dist))
+    }
+
+    /// 生成 `size` 个操作，key 从 `dist` 采样，写入值的长度按 `sizes` 采样
+    fn generate_n<V: Value, K: Key, D: KeyDistribution + ?Sized>(
+        &mut self,
+        size: usize,
+        sizes: &SizeSampler,
+        dist: &mut D,
+    ) -> Vec<Op<V, K>> {
+        let mut ops = Vec::with_capacity(size);
+        let mut hot = 0;
+
+        while ops.len() < size {
+            if let Some(scan) = &self.cfg.scan
+                && hot == scan.every
+            {
+                hot = 0;
+                let start = self.cfg.total_keys + 1;
+                let len = scan.len.min(size - ops.len());
+                ops.extend((start..start + len).map(|id| Op::Read(K::from_id(id))));
+                continue;
+            }
+            hot += 1;
+            let id = dist.sample(&mut self.rng);
+            let is_read = self.rng.random::<f64>() < self.cfg.read_ratio;
             
-            if is_read {
-                ops.push(Op::Read(key));
+            if self.cfg.is_missing(id) {
+                // 不存在的 key 置最高位，与预热、扫描 key 不重叠；不会被写入，写操作同样按读处理
+                ops.push(Op::ReadMissing(K::from_id(id | MISSING_BIT)));
+                continue;
+            }
+            let key = K::from_id(id);
+            // 仅在启用删除时消耗随机数，保证默认工作负载不变
+            if self.cfg.delete_ratio > 0.0 && self.rng.random::<f64>() < self.cfg.delete_ratio {
+                ops.push(Op::Delete(key));
+                continue;
+            }
+            if is_read && self.cfg.range_ratio > 0.0 && self.rng.random::<f64>() < self.cfg.range_ratio {
+                // 仅在启用范围扫描时消耗随机数；范围截断到 key 空间末尾
+                let len = self.cfg.range_len.min((self.cfg.total_keys + 1).saturating_sub(id)).max(1);
+                ops.push(Op::Scan(key, len));
+            } else if is_read {
+                // 仅在启用 peek 时消耗随机数，保证默认工作负载不变
+                let peek = self.cfg.peek_ratio > 0.0 && self.rng.random::<f64>() < self.cfg.peek_ratio;
+                ops.push(if peek { Op::Peek(key) } else { Op::Read(key) });
+            } else if self.cfg.insert_ratio > 0.0 && self.rng.random::<f64>() < self.cfg.insert_ratio {
+                // 同上，仅在启用插入时消耗随机数；新 key 排在扫描 key 之后
+                let scan_len = self.cfg.scan.as_ref().map_or(0, |s| s.len);
+                let id = self.cfg.total_keys + scan_len + 1 + self.inserted;
+                self.inserted += 1;
+                dist.inserted(id);
+                ops.push(Op::Insert(K::from_id(id), V::generate_sized(&mut self.rng, sizes)));
             } else {
-                let value = self.rng.random::<u32>() as usize;
-                ops.push(Op::Write(key, value));
+                if self.cfg.read_modify_write {
+                    ops.push(Op::Read(key.clone()));
+                }
+                ops.push(Op::Write(key, V::generate_sized(&mut self.rng, sizes)));
             }
         }
-        
-        Ok(ops)
+        // 读-改-写一次追加两个操作，可能超出一个
+        ops.truncate(size);
+        ops
     }
 }
 
-/// 预热管理器
-pub struct WarmupManager {
-    rng: StdRng,
+/// 把 `rand` 的连续分布适配为 [`KeyDistribution`]
+struct Sampled<'a, D>(&'a D);
+
+impl<D: Distribution<f64>> KeyDistribution for Sampled<'_, D> {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        self.0.sample(rng) as usize
+    }
+}
+
+/// 按排列映射采样结果，用于分阶段的热点迁移
+struct Permuted<'a, D: ?Sized> {
+    inner: &'a mut D,
+    perm: &'a [usize],
+}
+
+impl<D: KeyDistribution + ?Sized> KeyDistribution for Permuted<'_, D> {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        let id = self.inner.sample(rng);
+        // 排列之外的 id（如 Latest 分布采到的新插入 key）原样保留
+        self.perm.get(id).copied().unwrap_or(id)
+    }
+
+    #[inline]
+    fn inserted(&mut self, id: usize) {
+        self.inner.inserted(id);
+    }
+}
+
+/// 预热管理器，随机数生成器 `R` 缺省为以配置种子初始化的 [`StdRng`]
+pub struct WarmupManager<R = StdRng> {
+    cfg: WarmupConfig,
+    rng: R,
 }
 
 impl Default for WarmupManager {
     fn default() -> Self {
-        Self::new()
+        Self::new(WarmupConfig::default())
     }
 }
 
 impl WarmupManager {
     /// 创建新的预热管理器
-    pub fn new() -> Self {
+    pub fn new(cfg: WarmupConfig) -> Self {
         Self {
-            rng: StdRng::seed_from_u64(bench::WARMUP_SEED),
+            rng: StdRng::seed_from_u64(cfg.seed),
+            cfg,
         }
     }
-    
-    /// 生成预热操作序列
-    pub fn generate_warmup_ops(&mut self) -> Result<Vec<Op>> {
-        let zipf = rand_distr::Zipf::new((CACHE_CAPACITY * 2) as f64, ZIPF_S)
-            .map_err(|e| AppError::ZipfCreate(e.to_string()))?;
+
+    /// 执行缓存预热（不模拟回源延迟）
+    pub fn warmup_cache<V: Value, K: Key, C: CacheOps<V, K>>(cache: &mut C, warmup_ops: &[Op<V, K>]) {
+        for step in warmup_ops.iter().flat_map(Op::warmup_steps) {
+            match step {
+                WarmupStep::Get(key) => {
+                    cache.get(key);
+                }
+                WarmupStep::Peek(key) => {
+                    cache.peek(key);
+                }
+                WarmupStep::Insert(key, val) => cache.insert(key, val),
+                WarmupStep::Remove(key) => {
+                    cache.remove(key);
+                }
+                WarmupStep::GetMany(keys) => {
+                    cache.get_many(&keys);
+                }
+            }
+        }
+    }
+}
+
+/// 预热回放一个操作时对缓存的单次调用，同步与异步预热按同一计划执行
+pub(crate) enum WarmupStep<'a, V, K> {
+    Get(&'a K),
+    Peek(&'a K),
+    Insert(K, V),
+    Remove(&'a K),
+    GetMany(Vec<K>),
+}
+
+impl<V: Value, K: Key> Op<V, K> {
+    /// 预热回放该操作时依次执行的缓存调用
+    pub(crate) fn warmup_steps(&self) -> impl Iterator<Item = WarmupStep<'_, V, K>> {
+        let (step, extra) = match self {
+            Self::Read(key) => {
+                // 模拟读取后的访问模式
+                let id = key.id();
+                let extra = (id % 10 == 0).then(|| {
+                    let extra = id.wrapping_add(1000);
+                    WarmupStep::Insert(K::from_id(extra), V::load(extra))
+                });
+                (WarmupStep::Get(key), extra)
+            }
+            Self::Peek(key) => (WarmupStep::Peek(key), None),
+            Self::ReadMissing(key) => (WarmupStep::Get(key), None),
+            Self::Write(key, val) | Self::Insert(key, val) => (WarmupStep::Insert(key.clone(), val.clone()), None),
+            Self::Delete(key) => (WarmupStep::Remove(key), None),
+            Self::Scan(..) => (WarmupStep::GetMany(self.keys().collect()), None),
+        };
+        [Some(step), extra].into_iter().flatten()
+    }
+}
+
+impl<R: RngCore> WarmupManager<R> {
+    /// 替换随机数生成器，配置中的种子此后不再使用
+    pub fn with_rng<S: RngCore>(self, rng: S) -> WarmupManager<S> {
+        WarmupManager { cfg: self.cfg, rng }
+    }
+
+    /// 生成预热操作序列；`TracePrefix` 策略无工作负载可回放，结果为空
+    pub fn generate_warmup_ops<V: Value, K: Key>(&mut self) -> Result<Vec<Op<V, K>>> {
+        self.generate_warmup_ops_for(&[])
+    }
+
+    /// 按策略生成预热操作序列，`TracePrefix` 取 `workload` 的前 `size` 个操作，
+    /// 其中的读取改为回源写入，与运行时未命中后的填充一致
+    pub fn generate_warmup_ops_for<V: Value, K: Key>(&mut self, workload: &[Op<V, K>]) -> Result<Vec<Op<V, K>>> {
+        let size = self.cfg.size as usize;
+        // 热度排名从 1 开始，与工作负载的 Zipf 采样一致
+        let hottest = size.min(self.cfg.key_space as usize);
+        let ops = match self.cfg.strategy {
+            WarmupStrategy::Zipf => return self.zipf_ops(),
+            WarmupStrategy::MostPopularFirst => (1..=hottest)
+                .rev()
+                .map(|rank| {
+                    let id = self.rank_to_id(rank);
+                    self.write_op(id)
+                })
+                .collect(),
+            WarmupStrategy::SequentialFill => (1..=size).map(|id| self.write_op(id)).collect(),
+            WarmupStrategy::TracePrefix => workload[..size.min(workload.len())]
+                .iter()
+                .flat_map(|op| match op {
+                    Op::Read(_) | Op::Peek(_) | Op::Scan(..) => {
+                        op.keys().map(|key| Op::Write(key.clone(), V::load(key.id()))).collect()
+                    }
+                    _ => vec![op.clone()],
+                })
+                .collect(),
+            WarmupStrategy::None => Vec::new(),
+        };
+        Ok(ops)
+    }
+
+    /// 热度排名对应的 key id，工作负载启用打散时与其映射一致
+    #[inline]
+    fn rank_to_id(&self, rank: usize) -> usize {
+        self.cfg.scramble.as_ref().map_or(rank, |s| s.map(rank))
+    }
+
+    fn write_op<V: Value, K: Key>(&mut self, id: usize) -> Op<V, K> {
+        Op::Write(K::from_id(id), V::generate(&mut self.rng))
+    }
+
+    /// Zipf 策略：按热度随机写入，偶尔加入读操作
+    fn zipf_ops<V: Value, K: Key>(&mut self) -> Result<Vec<Op<V, K>>> {
+        let zipf = rand_distr::Zipf::new(self.cfg.key_space as f64, self.cfg.zipf_s)?;
         
-        let mut ops = Vec::with_capacity(bench::WARMUP_SIZE as usize);
+        let mut ops = Vec::with_capacity(self.cfg.size as usize);
         
-        for _ in 0..bench::WARMUP_SIZE {
-            let key = zipf.sample(&mut self.rng) as usize;
-            let value = self.rng.random::<u32>() as usize;
-            ops.push(Op::Write(key, value));
-            
-            // 偶尔加入读操作
-            if self.rng.random::<f64>() < 0.2 {
+        for _ in 0..self.cfg.size {
+            let (key, val, read) = self.zipf_step::<V, K>(&zipf);
+            if read {
+                ops.push(Op::Write(key.clone(), val));
                 ops.push(Op::Read(key));
+            } else {
+                ops.push(Op::Write(key, val));
             }
         }
         
         Ok(ops)
     }
-    
-    /// 执行缓存预热
-    pub async fn warmup_cache<C: CacheOps>(
-        &mut self,
-        cache: &mut C,
-        warmup_ops: &[Op],
-    ) -> Result<()> {
-        for op in warmup_ops {
-            match op {
-                Op::Read(key) => {
-                    cache.get(key);
-                    // 模拟读取后的访问模式
-                    if *key % 10 == 0 {
-                        cache.insert(*key + 1000, *key + 1000);
-                    }
-                }
-                Op::Write(key, val) => {
-                    cache.insert(*key, *val);
-                }
-            }
+
+    /// Zipf 策略的单步：采样 key 与值，并决定是否在写入后追加一次读取
+    fn zipf_step<V: Value, K: Key>(&mut self, zipf: &rand_distr::Zipf<f64>) -> (K, V, bool) {
+        let rank = zipf.sample(&mut self.rng) as usize;
+        let key = K::from_id(self.rank_to_id(rank));
+        let val = V::generate(&mut self.rng);
+        // 偶尔加入读操作
+        (key, val, self.rng.random::<f64>() < 0.2)
+    }
+}
+
+/// 通用缓存运行器，未命中与写入时按 `B` 模拟回源延迟，在时间源 `T` 上等待与计时，
+/// 每次运行新建 `R` 采样回源延迟与故障
+#[derive(Clone, Debug)]
+pub struct CacheRunner<B = Backend, T = RealClock, R = SmallRng> {
+    latency: B,
+    clock: T,
+    /// 与类型参数无关的设置，替换类型参数时整体转移
+    opts: RunnerOpts,
+    rng: PhantomData<fn() -> R>,
+}
+
+/// 运行器的非泛型设置，缺省值即不启用对应功能
+#[derive(Clone, Debug, Default)]
+struct RunnerOpts {
+    faults: Faults,
+    negative_cache: bool,
+    /// 回源随机数种子，缺省每次运行随机
+    seed: Option<u64>,
+    /// 按 key 的回源计数，缺省不统计
+    loads: Option<Arc<LoadCounter>>,
+    /// 两级缓存的磁盘层，缺省不启用
+    disk: Option<Arc<DiskTier>>,
+    /// 按热度分层统计命中率，缺省不统计
+    tiers: Option<Arc<PopularityTiers>>,
+    /// 占用率采样间隔（操作数），缺省不采样
+    occupancy_every: Option<usize>,
+    /// 进度回调，缺省不报告
+    progress: Option<ProgressHook>,
+    /// 截止时间，到达后停止运行并标记为部分结果，缺省不限时
+    deadline: Option<Instant>,
+    /// 条目代价模型，缺省按条目数限额
+    cost: CostModel,
+    /// 分片缓存的哈希种子，缺省每次随机
+    shard_seed: Option<u64>,
+}
+
+// 手写而非派生，避免要求 `R: Default`
+impl<B: Default, T: Default, R> Default for CacheRunner<B, T, R> {
+    fn default() -> Self {
+        Self {
+            latency: B::default(),
+            clock: T::default(),
+            opts: RunnerOpts::default(),
+            rng: PhantomData,
         }
-        Ok(())
     }
 }
 
-/// 通用缓存运行器
-pub struct CacheRunner;
+impl<B: BackendModel> CacheRunner<B> {
+    /// 使用指定的后端延迟模型创建运行器，不注入故障，使用真实时钟
+    pub fn new(latency: B) -> Self {
+        Self {
+            latency,
+            clock: RealClock::default(),
+            opts: RunnerOpts::default(),
+            rng: PhantomData,
+        }
+    }
+}
 
 impl CacheRunner {
-    /// 运行缓存测试
-    pub async fn run_cache<C: CacheOps>(
+    /// 按配置的延迟模型、故障注入与负缓存策略创建运行器，使用真实时钟
+    pub fn from_config(cfg: &Config) -> Self {
+        let mut runner = Self::new(cfg.latency)
+            .with_faults(cfg.faults)
+            .with_negative_cache(cfg.negative_cache)
+            .with_seed(cfg.backend_seed)
+            .with_cost(cfg.cost);
+        if let Some(seed) = cfg.shard_seed() {
+            runner = runner.with_shard_seed(seed);
+        }
+        if cfg.progress {
+            runner = runner.with_progress(ProgressHook::stderr(PROGRESS_EVERY));
+        }
+        if let Some(budget) = cfg.budget {
+            runner = runner.with_deadline(Instant::now() + budget);
+        }
+        runner
+    }
+}
+
+impl<B: BackendModel, T: TimeSource, R: RngCore + SeedableRng> CacheRunner<B, T, R> {
+    /// 替换时间源，如用 [`crate::MockClock`] 驱动确定性测试
+    pub fn with_clock<U: TimeSource>(self, clock: U) -> CacheRunner<B, U, R> {
+        CacheRunner {
+            latency: self.latency,
+            clock,
+            opts: self.opts,
+            rng: PhantomData,
+        }
+    }
+
+    /// 替换回源后端，如用 [`crate::FileBackend`] 真实读取文件
+    pub fn with_backend<C: BackendModel>(self, latency: C) -> CacheRunner<C, T, R> {
+        CacheRunner {
+            latency,
+            clock: self.clock,
+            opts: self.opts,
+            rng: PhantomData,
+        }
+    }
+
+    /// 替换回源随机数生成器的类型，每次运行以种子（未设置时随机）新建
+    pub fn with_rng<S: RngCore + SeedableRng>(self) -> CacheRunner<B, T, S> {
+        CacheRunner {
+            latency: self.latency,
+            clock: self.clock,
+            opts: self.opts,
+            rng: PhantomData,
+        }
+    }
+
+    /// 按 key 统计读未命中触发的回源（含失败与重试前的首次尝试，每次回源计一次）
+    pub fn with_load_counter(mut self, loads: Arc<LoadCounter>) -> Self {
+        self.opts.loads = Some(loads);
+        self
+    }
+
+    /// 启用磁盘层：内存未命中时先读磁盘层，未命中再回源，回源结果写入磁盘层
+    pub fn with_disk_tier(mut self, disk: Arc<DiskTier>) -> Self {
+        self.opts.disk = Some(disk);
+        self
+    }
+
+    /// 按热度层分别统计读命中，结果见 [`RunStats::tiers`]
+    pub fn with_tiers(mut self, tiers: Arc<PopularityTiers>) -> Self {
+        self.opts.tiers = Some(tiers);
+        self
+    }
+
+    /// 每 `every` 个操作采样一次常驻条目数，结果见 [`RunStats::occupancy`]
+    pub fn with_occupancy(mut self, every: usize) -> Self {
+        self.opts.occupancy_every = Some(every.max(1));
+        self
+    }
+
+    /// 运行中按 `hook` 的间隔报告进度，结束时再报告一次
+    pub fn with_progress(mut self, hook: ProgressHook) -> Self {
+        self.opts.progress = Some(hook);
+        self
+    }
+
+    /// 进度回调
+    #[inline]
+    pub fn progress(&self) -> Option<&ProgressHook> {
+        self.opts.progress.as_ref()
+    }
+
+    /// 到达 `deadline` 后停止运行，已完成部分的统计标记为 [`RunStats::partial`]；
+    /// 同一运行器上的多次运行（如参数扫描的各点）共享截止时间
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.opts.deadline = Some(deadline);
+        self
+    }
+
+    /// 按 `cost` 的代价预算（容量乘以平均代价）而非条目数限额，见 [`CacheKind::run`](crate::CacheKind::run)
+    pub fn with_cost(mut self, cost: CostModel) -> Self {
+        self.opts.cost = cost;
+        self
+    }
+
+    /// 条目代价模型
+    #[inline]
+    pub fn cost(&self) -> CostModel {
+        self.opts.cost
+    }
+
+    /// 是否已过截止时间或已收到中断（见 [`install_interrupt_handler`](interrupt::install_interrupt_handler)）
+    #[inline]
+    pub fn expired(&self) -> bool {
+        interrupt::interrupted() || self.opts.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// 固定回源延迟与故障采样的种子，配合模拟时钟使结果可复现
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.opts.seed = Some(seed);
+        self
+    }
+
+    /// 固定分片缓存的哈希种子与分片数，使分片方式在各次运行中一致，见 [`ShardedLru::seeded`](crate::ShardedLru::seeded)
+    pub fn with_shard_seed(mut self, seed: u64) -> Self {
+        self.opts.shard_seed = Some(seed);
+        self
+    }
+
+    /// 分片缓存的哈希种子
+    #[inline]
+    pub fn shard_seed(&self) -> Option<u64> {
+        self.opts.shard_seed
+    }
+
+    /// 时间源
+    #[inline]
+    pub fn clock(&self) -> &T {
+        &self.clock
+    }
+
+    /// 设置回源故障注入
+    pub fn with_faults(mut self, faults: Faults) -> Self {
+        self.opts.faults = faults;
+        self
+    }
+
+    /// 设置是否缓存不存在的 key 的空结果，关闭时每次都重新回源
+    pub fn with_negative_cache(mut self, enabled: bool) -> Self {
+        self.opts.negative_cache = enabled;
+        self
+    }
+
+    /// 运行缓存测试，返回命中统计
+    pub async fn run_cache<V: Value, K: Key, C: CacheOps<V, K>>(
+        &self,
+        cache: C,
+        ops: &[Op<V, K>],
+    ) -> Result<RunStats> {
+        self.run(cache, ops, None::<fn(&Op<V, K>, Outcome, Duration)>).await
+    }
+
+    /// 运行缓存测试，每个操作完成后以 (操作, 结果, 耗时) 调用 `observer`
+    pub async fn run_cache_observed<V: Value, K: Key, C: CacheOps<V, K>>(
+        &self,
+        cache: C,
+        ops: &[Op<V, K>],
+        observer: impl FnMut(&Op<V, K>, Outcome, Duration),
+    ) -> Result<RunStats> {
+        self.run(cache, ops, Some(observer)).await
+    }
+
+    /// 运行缓存测试，同时按读写分别记录每个操作的延迟直方图
+    pub async fn run_cache_latency<V: Value, K: Key, C: CacheOps<V, K>>(
+        &self,
+        cache: C,
+        ops: &[Op<V, K>],
+    ) -> Result<(RunStats, LatencyRecorder)> {
+        let mut rec = LatencyRecorder::new()?;
+        let stats = self.run(cache, ops, Some(|_: &Op<V, K>, o, d| rec.record(o, d))).await?;
+        Ok((stats, rec))
+    }
+
+    /// 运行缓存测试，同时每 `every` 个操作记录一次命中率
+    pub async fn run_cache_series<V: Value, K: Key, C: CacheOps<V, K>>(
+        &self,
+        cache: C,
+        ops: &[Op<V, K>],
+        every: usize,
+    ) -> Result<(RunStats, Vec<SeriesPoint>)> {
+        let mut rec = SeriesRecorder::new(every);
+        let stats = self.run(cache, ops, Some(|_: &Op<V, K>, o, _| rec.record(o))).await?;
+        Ok((stats, rec.finish()))
+    }
+
+    /// 从迭代器回放操作（如内存映射的工作负载），无需先收集为切片
+    pub async fn run_cache_iter<V: Value, K: Key, C: CacheOps<V, K>>(
+        &self,
+        cache: C,
+        ops: impl IntoIterator<Item = Op<V, K>>,
+    ) -> Result<RunStats> {
+        self.run(cache, ops, None::<fn(&Op<V, K>, Outcome, Duration)>).await
+    }
+
+    async fn run<V: Value, K: Key, C: CacheOps<V, K>, O: Borrow<Op<V, K>>>(
+        &self,
         mut cache: C,
-        ops: &[Op],
-    ) -> Result<(u64, u64)> {
-        let mut backend_rng = SmallRng::from_seed(rand::random());
-        let mut hits = 0u64;
-        let mut misses = 0u64;
+        ops: impl IntoIterator<Item = O>,
+        mut observer: Option<impl FnMut(&Op<V, K>, Outcome, Duration)>,
+    ) -> Result<RunStats> {
+        let mut backend_rng = self.backend_rng();
+        let mut stats = RunStats::default();
+        let mut loaded = HashSet::new();
+        let occupancy_every = self.opts.occupancy_every.filter(|_| cache.entry_count().is_some());
+        stats.occupancy.capacity = cache.capacity_hint().map(|c| c as u64);
+        let ops = ops.into_iter();
+        let total = ops.size_hint().1;
+        let started = Instant::now();
+        let begin = self.clock.now();
         
         for op in ops {
-            match op {
-                Op::Read(key) => {
-                    if cache.get(key).is_some() {
-                        hits += 1;
+            if self.out_of_time(&mut stats) {
+                break;
+            }
+            let op = op.borrow();
+            stats.ops += 1;
+            // 无观察者时不计时
+            let start = observer.is_some().then(|| self.clock.now());
+            let outcome = match op {
+                Op::Read(key) | Op::Peek(key) => {
+                    let hit = timed(&mut stats.cache_time, || match op {
+                        Op::Peek(_) => cache.peek(key),
+                        _ => cache.get(key).is_some(),
+                    });
+                    self.record_tier(&mut stats, key.id(), hit);
+                    if hit {
+                        stats.hits += 1;
+                        Outcome::Hit
                     } else {
-                        misses += 1;
-                        simulate_backend_latency(&mut backend_rng).await;
-                        cache.insert(*key, *key);
+                        stats.misses += 1;
+                        if self.fetch(key.id(), true, &mut stats, &mut backend_rng, &mut loaded).await? {
+                            insert_tracked(&mut cache, key.clone(), V::load(key.id()), true, &mut stats);
+                            Outcome::Miss
+                        } else {
+                            Outcome::Failed
+                        }
+                    }
+                }
+                Op::ReadMissing(key) => {
+                    let hit = timed(&mut stats.cache_time, || cache.get(key).is_some());
+                    self.record_tier(&mut stats, key.id(), hit);
+                    if hit {
+                        stats.hits += 1;
+                        stats.negative_hits += 1;
+                        Outcome::Hit
+                    } else {
+                        stats.misses += 1;
+                        stats.negative_misses += 1;
+                        if self.fetch(key.id(), self.opts.negative_cache, &mut stats, &mut backend_rng, &mut loaded).await? {
+                            if self.opts.negative_cache {
+                                // 以最小长度的值作为负缓存标记
+                                insert_tracked(&mut cache, key.clone(), V::sized(key.id(), 0), true, &mut stats);
+                            }
+                            Outcome::Miss
+                        } else {
+                            Outcome::Failed
+                        }
                     }
                 }
                 Op::Write(key, val) => {
-                    simulate_backend_latency(&mut backend_rng).await;
-                    cache.insert(*key, *val);
+                    self.write_back(key.id(), &mut stats, &mut backend_rng).await?;
+                    insert_tracked(&mut cache, key.clone(), val.clone(), false, &mut stats);
+                    stats.updates += 1;
+                    Outcome::Write
+                }
+                Op::Insert(key, val) => {
+                    self.write_back(key.id(), &mut stats, &mut backend_rng).await?;
+                    insert_tracked(&mut cache, key.clone(), val.clone(), true, &mut stats);
+                    stats.inserts += 1;
+                    Outcome::Insert
                 }
+                Op::Delete(key) => {
+                    timed(&mut stats.cache_time, || cache.remove(key));
+                    self.invalidate(key.id());
+                    // 失效后重新加载不算浪费的回源
+                    loaded.remove(&key.id());
+                    stats.deletes += 1;
+                    Outcome::Delete
+                }
+                Op::Scan(..) => {
+                    let keys: Vec<K> = op.keys().collect();
+                    let (mut hits, mut misses) = (0, 0);
+                    let found = timed(&mut stats.cache_time, || cache.get_many(&keys));
+                    for (key, hit) in keys.iter().zip(found) {
+                        self.record_tier(&mut stats, key.id(), hit);
+                        if hit {
+                            hits += 1;
+                            continue;
+                        }
+                        misses += 1;
+                        if self.fetch(key.id(), true, &mut stats, &mut backend_rng, &mut loaded).await? {
+                            insert_tracked(&mut cache, key.clone(), V::load(key.id()), true, &mut stats);
+                        }
+                    }
+                    stats.hits += hits;
+                    stats.misses += misses;
+                    Outcome::Scan { hits, misses }
+                }
+            };
+            if let (Some(f), Some(start)) = (observer.as_mut(), start) {
+                f(op, outcome, self.clock.now().saturating_sub(start));
             }
+            if let Some(every) = occupancy_every
+                && stats.ops % every as u64 == 0
+            {
+                stats.occupancy.record(cache.entry_count().unwrap_or(0));
+            }
+            self.report_progress(&stats, total, started, false);
         }
+        self.report_progress(&stats, total, started, true);
+        stats.elapsed = self.clock.now().saturating_sub(begin);
         
-        Ok((hits, misses))
+        Ok(stats)
     }
-    
-    /// 计算命中率
+
+    /// 每 [`BUDGET_CHECK_EVERY`] 个操作检查一次截止时间与中断，已过时标记为部分结果
     #[inline]
-    pub fn calculate_hit_rate(hits: u64, misses: u64) -> f64 {
-        let total = hits + misses;
-        if total == 0 {
-            0.0
-        } else {
-            hits as f64 / total as f64 * 100.0
+    pub(crate) fn out_of_time(&self, stats: &mut RunStats) -> bool {
+        if stats.ops.is_multiple_of(BUDGET_CHECK_EVERY) && self.expired() {
+            stats.partial = true;
+        }
+        stats.partial
+    }
+
+    /// 每完成回调间隔个操作报告一次进度；`last` 为运行结束，此前未恰好报告过时补报一次
+    #[inline]
+    pub(crate) fn report_progress(&self, stats: &RunStats, total: Option<usize>, started: Instant, last: bool) {
+        if let Some(hook) = &self.opts.progress
+            && stats.ops.is_multiple_of(hook.every() as u64) != last
+        {
+            hook.report(&Progress::new(stats, total, started));
+        }
+    }
+
+    /// 回源随机数生成器，设置了种子时可复现
+    fn backend_rng(&self) -> R {
+        match self.opts.seed {
+            Some(seed) => R::seed_from_u64(seed),
+            None => R::from_rng(&mut rand::rng()),
         }
     }
-}
\ No newline at end of file
+
+    /// 未命中时先查磁盘层，再回源 `key`（含重试），累加重试与失败次数，返回是否成功；
+    /// `keep` 为回源结果是否写入磁盘层，与是否写入内存缓存一致，等待时间计入 [`RunStats::backend_time`]
+    ///
+    /// `loaded` 记录本次运行中成功回源并写入缓存的 key，重复回源计入 [`RunStats::wasted_loads`]
+    async fn fetch(
+        &self,
+        key: usize,
+        keep: bool,
+        stats: &mut RunStats,
+        rng: &mut R,
+        loaded: &mut HashSet<usize>,
+    ) -> Result<bool> {
+        let start = self.clock.now();
+        let ok = self.fetch_tiers(key, keep, stats, rng, loaded).await;
+        stats.backend_time += self.clock.now().saturating_sub(start);
+        ok
+    }
+
+    async fn fetch_tiers(
+        &self,
+        key: usize,
+        keep: bool,
+        stats: &mut RunStats,
+        rng: &mut R,
+        loaded: &mut HashSet<usize>,
+    ) -> Result<bool> {
+        if let Some(disk) = &self.opts.disk
+            && disk.get(key).await?
+        {
+            stats.disk_hits += 1;
+            return Ok(true);
+        }
+        if let Some(loads) = &self.opts.loads {
+            loads.record(key);
+        }
+        let (ok, retries) = simulate_backend_fetch(&self.latency, &self.opts.faults, key, &self.clock, rng).await?;
+        stats.retries += u64::from(retries);
+        stats.backend_calls += 1 + u64::from(retries);
+        if !ok {
+            stats.errors += 1;
+            return Ok(false);
+        }
+        // 结果不写入缓存时不会被淘汰，不记录加载
+        if !keep {
+            return Ok(true);
+        }
+        if !loaded.insert(key) {
+            stats.wasted_loads += 1;
+        }
+        if let Some(disk) = &self.opts.disk {
+            disk.insert(key).await?;
+        }
+        Ok(true)
+    }
+
+    /// 写操作先写回后端，等待时间计入 [`RunStats::backend_time`]，再使磁盘层的副本失效
+    async fn write_back(&self, key: usize, stats: &mut RunStats, rng: &mut R) -> Result<()> {
+        let start = self.clock.now();
+        simulate_backend_latency(&self.latency, key, &self.clock, rng).await?;
+        stats.backend_time += self.clock.now().saturating_sub(start);
+        stats.backend_calls += 1;
+        self.invalidate(key);
+        Ok(())
+    }
+
+    /// 按 key 所在的热度层记录一次读的结果
+    #[inline]
+    fn record_tier(&self, stats: &mut RunStats, key: usize, hit: bool) {
+        if let Some(tiers) = &self.opts.tiers {
+            let t = &mut stats.tiers[tiers.tier(key) as usize];
+            if hit {
+                t.hits += 1;
+            } else {
+                t.misses += 1;
+            }
+        }
+    }
+
+    /// 后端数据更新或删除后，从磁盘层移除过期的副本
+    #[inline]
+    fn invalidate(&self, key: usize) {
+        if let Some(disk) = &self.opts.disk {
+            disk.remove(key);
+        }
+    }
+}
+
+/// 插入并按前后条目数之差统计淘汰；`absent` 为插入前 key 是否必然不存在，
+/// 更新时不确定 key 是否仍在缓存中，只计入条目数的净减少。容量为 0 的缓存（如 [`crate::NoCache`]）不统计
+#[inline]
+fn insert_tracked<V, K, C: CacheOps<V, K>>(cache: &mut C, key: K, value: V, absent: bool, stats: &mut RunStats) {
+    let start = Instant::now();
+    let Some(before) = cache.entry_count().filter(|_| cache.capacity_hint() != Some(0)) else {
+        cache.insert(key, value);
+        stats.cache_time += start.elapsed();
+        return;
+    };
+    let added = usize::from(absent);
+    cache.insert(key, value);
+    let after = cache.entry_count().unwrap_or(before + added);
+    stats.cache_time += start.elapsed();
+    stats.evictions += (before + added).saturating_sub(after) as u64;
+    stats.max_resident = stats.max_resident.max(before.max(after) as u64);
+}
+
+/// 执行缓存调用，耗时累加到 `time`
+#[inline]
+fn timed<R>(time: &mut Duration, f: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let out = f();
+    *time += start.elapsed();
+    out
+}
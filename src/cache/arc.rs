@@ -0,0 +1,150 @@
+//! ARC（自适应替换缓存）
+//! T1 保存只访问过一次的条目，T2 保存访问过多次的条目，B1/B2 记录二者淘汰的 key；
+//! 命中 B1 增大 T1 目标大小 p，命中 B2 减小 p，从而在近期性与频率之间自适应
+
+use crate::{AppError, CacheOps, Result};
+use hashlink::{LinkedHashMap, LinkedHashSet};
+
+/// ARC 缓存，各链表队头为最久未使用，队尾为最近使用
+pub struct ArcCache<V> {
+    t1: LinkedHashMap<usize, V>,
+    t2: LinkedHashMap<usize, V>,
+    b1: LinkedHashSet<usize>,
+    b2: LinkedHashSet<usize>,
+    /// T1 的目标大小
+    p: usize,
+    capacity: usize,
+}
+
+impl<V> ArcCache<V> {
+    /// 创建指定容量的缓存
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        Ok(Self {
+            t1: LinkedHashMap::with_capacity(capacity),
+            t2: LinkedHashMap::with_capacity(capacity),
+            b1: LinkedHashSet::with_capacity(capacity),
+            b2: LinkedHashSet::with_capacity(capacity),
+            p: 0,
+            capacity,
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.t1.is_empty() && self.t2.is_empty()
+    }
+
+    /// 当前 T1 目标大小
+    #[inline]
+    pub fn target(&self) -> usize {
+        self.p
+    }
+
+    /// 按目标大小从 T1 或 T2 淘汰一个条目，key 转入对应的幽灵链表；
+    /// 删除后缓存可能未满而目录已满，此时不淘汰
+    fn replace(&mut self, in_b2: bool) {
+        if self.len() < self.capacity {
+            return;
+        }
+        let t1 = self.t1.len();
+        if t1 > 0 && (t1 > self.p || (in_b2 && t1 == self.p)) {
+            if let Some((k, _)) = self.t1.pop_front() {
+                self.b1.insert(k);
+            }
+        } else if let Some((k, _)) = self.t2.pop_front() {
+            self.b2.insert(k);
+        } else if let Some((k, _)) = self.t1.pop_front() {
+            self.b1.insert(k);
+        }
+    }
+}
+
+impl<V> CacheOps<V> for ArcCache<V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        if let Some(v) = self.t1.remove(key) {
+            self.t2.insert(*key, v);
+            return self.t2.back().map(|(_, v)| v);
+        }
+        self.t2.to_back(key).map(|v| &*v)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.t1.contains_key(key) || self.t2.contains_key(key)
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        let c = self.capacity;
+        // 已缓存：更新值并视为再次访问
+        if self.t1.remove(&key).is_some() || self.t2.contains_key(&key) {
+            self.t2.insert(key, value);
+            return;
+        }
+        // 幽灵命中：δ 按移除该 key 之前的链表长度计算，所在链表非空
+        if self.b1.contains(&key) {
+            let delta = (self.b2.len() / self.b1.len()).max(1);
+            self.b1.remove(&key);
+            self.p = (self.p + delta).min(c);
+            self.replace(false);
+            self.t2.insert(key, value);
+            return;
+        }
+        if self.b2.contains(&key) {
+            let delta = (self.b1.len() / self.b2.len()).max(1);
+            self.b2.remove(&key);
+            self.p = self.p.saturating_sub(delta);
+            self.replace(true);
+            self.t2.insert(key, value);
+            return;
+        }
+        let l1 = self.t1.len() + self.b1.len();
+        if l1 >= c {
+            if self.t1.len() < c {
+                self.b1.pop_front();
+                self.replace(false);
+            } else {
+                self.t1.pop_front();
+            }
+        } else {
+            let total = l1 + self.t2.len() + self.b2.len();
+            if total >= c {
+                if total >= 2 * c {
+                    self.b2.pop_front();
+                }
+                self.replace(false);
+            }
+        }
+        self.t1.insert(key, value);
+    }
+
+    /// 只移出 T1/T2，幽灵链表不受影响
+    #[inline]
+    fn remove(&mut self, key: &usize) -> bool {
+        self.t1.remove(key).is_some() || self.t2.remove(key).is_some()
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(ArcCache::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(self.capacity)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "ARC"
+    }
+}
@@ -0,0 +1,73 @@
+//! 基准锚点
+//! 不淘汰的 `HashMap`（命中率上限）与永远未命中的空缓存（命中率下限）
+
+use crate::CacheOps;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// 无容量限制、从不淘汰的缓存
+impl<K: Hash + Eq, V> CacheOps<V, K> for HashMap<K, V> {
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        HashMap::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(HashMap::len(self))
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Unbounded HashMap"
+    }
+}
+
+/// 不保存任何条目，每次读取都回源
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoCache;
+
+impl<K, V> CacheOps<V, K> for NoCache {
+    #[inline]
+    fn get(&mut self, _key: &K) -> Option<&V> {
+        None
+    }
+
+    #[inline]
+    fn peek(&mut self, _key: &K) -> bool {
+        false
+    }
+
+    #[inline]
+    fn insert(&mut self, _key: K, _value: V) {}
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "No Cache"
+    }
+}
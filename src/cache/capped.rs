@@ -0,0 +1,159 @@
+//! 并发 map 伪缓存
+//! 直接用 DashMap 存放数据，条目数超出容量时手工清理一批，是许多应用起步时的做法；
+//! 作为“只用并发 map 行不行”的基线，与真正的缓存对比命中率与争用开销
+
+use crate::config::MAP_SWEEP_KEEP;
+use crate::{AppError, ConcurrentCacheOps, Result};
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use parking_lot::Mutex;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// 超出容量时的清理方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapEviction {
+    /// 按插入顺序清理最早插入的条目；删除后重新插入的 key 仍按首次插入的位置清理，是近似 FIFO
+    Fifo,
+    /// 随机清理
+    Random,
+}
+
+/// DashMap 加手工清理：插入后条目数超出容量时清理到容量的 [`MAP_SWEEP_KEEP`]，
+/// 读取不记录访问，同一时刻只有一个线程清理，其余线程照常读写，条目数可短暂超出容量
+pub struct CappedDashMap<V> {
+    map: DashMap<usize, V>,
+    /// 条目数，随插入与删除增减，免去 `DashMap::len` 逐个锁分片
+    len: AtomicUsize,
+    /// 插入顺序，只在 FIFO 清理时记录
+    order: Mutex<VecDeque<usize>>,
+    eviction: MapEviction,
+    capacity: usize,
+    sweeping: AtomicBool,
+    /// 清理次数，兼作随机清理的种子
+    sweeps: AtomicU64,
+}
+
+impl<V> CappedDashMap<V> {
+    /// 创建指定容量与清理方式的缓存
+    pub fn new(capacity: usize, eviction: MapEviction) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        Ok(Self {
+            map: DashMap::with_capacity(capacity),
+            len: AtomicUsize::new(0),
+            order: Mutex::new(VecDeque::with_capacity(capacity)),
+            eviction,
+            capacity,
+            sweeping: AtomicBool::new(false),
+            sweeps: AtomicU64::new(0),
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 删除 key 并更新条目数，返回是否存在
+    #[inline]
+    fn remove_counted(&self, key: &usize) -> bool {
+        let removed = self.map.remove(key).is_some();
+        if removed {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        removed
+    }
+
+    /// 已进行的清理次数
+    #[inline]
+    pub fn sweeps(&self) -> u64 {
+        self.sweeps.load(Ordering::Relaxed)
+    }
+
+    /// 清理到容量的 [`MAP_SWEEP_KEEP`]，至少保留一个条目；已有线程在清理时直接返回
+    fn sweep(&self) {
+        if self.sweeping.swap(true, Ordering::Acquire) {
+            return;
+        }
+        let target = ((self.capacity as f64 * MAP_SWEEP_KEEP) as usize).max(1);
+        let excess = self.len().saturating_sub(target);
+        let seed = self.sweeps.fetch_add(1, Ordering::Relaxed);
+        match self.eviction {
+            MapEviction::Fifo => {
+                let mut order = self.order.lock();
+                let mut removed = 0;
+                while removed < excess {
+                    let Some(key) = order.pop_front() else { break };
+                    // 已删除的 key 在队列中残留，跳过
+                    if self.remove_counted(&key) {
+                        removed += 1;
+                    }
+                }
+            }
+            MapEviction::Random => {
+                let mut keys: Vec<usize> = self.map.iter().map(|e| *e.key()).collect();
+                let (victims, _) = keys.partial_shuffle(&mut SmallRng::seed_from_u64(seed), excess);
+                for key in victims {
+                    self.remove_counted(key);
+                }
+            }
+        }
+        self.sweeping.store(false, Ordering::Release);
+    }
+}
+
+impl<V: Clone + Send + Sync> ConcurrentCacheOps<V> for CappedDashMap<V> {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<V> {
+        self.map.get(key).map(|v| v.clone())
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn insert(&self, key: usize, value: V) {
+        let len = match self.map.entry(key) {
+            Entry::Occupied(mut e) => {
+                e.insert(value);
+                return;
+            }
+            // 持有分片锁时计数，保证并发删除同一 key 的减计数在其后，计数不会下溢
+            Entry::Vacant(e) => {
+                let _guard = e.insert(value);
+                self.len.fetch_add(1, Ordering::Relaxed) + 1
+            }
+        };
+        if self.eviction == MapEviction::Fifo {
+            self.order.lock().push_back(key);
+        }
+        if len > self.capacity {
+            self.sweep();
+        }
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> bool {
+        self.remove_counted(key)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        match self.eviction {
+            MapEviction::Fifo => "DashMap (FIFO sweep)",
+            MapEviction::Random => "DashMap (random sweep)",
+        }
+    }
+}
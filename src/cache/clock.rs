@@ -0,0 +1,144 @@
+//! CLOCK（二次机会）淘汰算法
+//! 条目放在环形数组中，命中置引用位；淘汰时指针绕环移动，
+//! 引用位为 1 的条目清零后跳过，淘汰第一个引用位为 0 的条目。
+//! 删除与 [`CacheOps::evict_one`] 原地清空槽位并记入空闲表，其余条目在环上的位置不变
+
+use crate::{AppError, CacheOps, Result};
+use std::collections::HashMap;
+
+struct Slot<V> {
+    key: usize,
+    value: V,
+    referenced: bool,
+}
+
+/// CLOCK 缓存，介于 FIFO 与 LRU 之间的经典基线
+pub struct Clock<V> {
+    map: HashMap<usize, usize>,
+    slots: Vec<Option<Slot<V>>>,
+    /// 已清空的槽位，插入时优先复用
+    free: Vec<usize>,
+    hand: usize,
+    capacity: usize,
+}
+
+impl<V> Clock<V> {
+    /// 创建指定容量的缓存
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        Ok(Self {
+            map: HashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            hand: 0,
+            capacity,
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 转动指针直到找到可淘汰的槽位，跳过空槽位；调用前需至少有一个条目
+    fn victim(&mut self) -> usize {
+        loop {
+            let i = self.hand;
+            self.hand = (self.hand + 1) % self.slots.len();
+            match &mut self.slots[i] {
+                Some(slot) if slot.referenced => slot.referenced = false,
+                Some(_) => return i,
+                None => {}
+            }
+        }
+    }
+
+    /// 清空槽位并记入空闲表
+    fn clear(&mut self, i: usize) -> Option<usize> {
+        let slot = self.slots[i].take()?;
+        self.map.remove(&slot.key);
+        self.free.push(i);
+        Some(slot.key)
+    }
+}
+
+impl<V> CacheOps<V> for Clock<V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        let slot = self.slots[*self.map.get(key)?].as_mut()?;
+        slot.referenced = true;
+        Some(&slot.value)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        if let Some(slot) = self.map.get(&key).and_then(|&i| self.slots[i].as_mut()) {
+            slot.value = value;
+            slot.referenced = true;
+            return;
+        }
+        let i = if let Some(i) = self.free.pop() {
+            i
+        } else if self.slots.len() < self.capacity {
+            self.slots.push(None);
+            self.slots.len() - 1
+        } else {
+            // 无空闲槽位时所有槽位均有条目
+            let i = self.victim();
+            if let Some(old) = self.slots[i].take() {
+                self.map.remove(&old.key);
+            }
+            i
+        };
+        self.slots[i] = Some(Slot {
+            key,
+            value,
+            referenced: false,
+        });
+        self.map.insert(key, i);
+    }
+
+    fn remove(&mut self, key: &usize) -> bool {
+        self.map.get(key).copied().and_then(|i| self.clear(i)).is_some()
+    }
+
+    fn evict_one(&mut self) -> Option<usize> {
+        if self.map.is_empty() {
+            return None;
+        }
+        let i = self.victim();
+        self.clear(i)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(Clock::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(self.capacity)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "CLOCK"
+    }
+}
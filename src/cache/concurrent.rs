@@ -0,0 +1,195 @@
+//! 线程安全缓存
+//! 定义共享引用访问的缓存接口、接入单线程运行器的适配器，以及多线程争用运行
+
+use crate::{AppError, BackendModel, BenchRuntime, CacheOps, CacheRunner, Op, Result, RunStats, TimeSource, Value};
+#[cfg(feature = "moka")]
+use moka::sync::Cache as MokaSyncCache;
+use rand::{RngCore, SeedableRng};
+use std::thread;
+
+/// 线程安全缓存 trait，所有操作只需共享引用，读取返回值的副本
+pub trait ConcurrentCacheOps<V>: Send + Sync {
+    /// 获取缓存值
+    fn get(&self, key: &usize) -> Option<V>;
+
+    /// 查询 key 是否存在，不更新访问顺序（不支持时退化为 get）
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// 插入键值对
+    fn insert(&self, key: usize, value: V);
+
+    /// 移除 key，返回移除前是否存在
+    fn remove(&self, key: &usize) -> bool;
+
+    /// 批量查询，返回各 key 是否命中；默认逐个 `get`
+    fn get_many(&self, keys: &[usize]) -> Vec<bool> {
+        keys.iter().map(|k| self.get(k).is_some()).collect()
+    }
+
+    /// 按顺序批量插入；默认逐个 `insert`
+    fn insert_many(&self, entries: &[(usize, V)])
+    where
+        V: Clone,
+    {
+        for (key, value) in entries {
+            self.insert(*key, value.clone());
+        }
+    }
+
+    /// 获取缓存名称（用于日志）
+    fn name(&self) -> &'static str;
+}
+
+#[cfg(feature = "moka")]
+impl<V: Clone + Send + Sync + 'static> ConcurrentCacheOps<V> for MokaSyncCache<usize, V> {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) {
+        self.insert(key, value);
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> bool {
+        MokaSyncCache::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Moka Sync"
+    }
+}
+
+impl<V, C: ConcurrentCacheOps<V>> ConcurrentCacheOps<V> for &C {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<V> {
+        (**self).get(key)
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        (**self).peek(key)
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) {
+        (**self).insert(key, value);
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> bool {
+        (**self).remove(key)
+    }
+
+    #[inline]
+    fn get_many(&self, keys: &[usize]) -> Vec<bool> {
+        (**self).get_many(keys)
+    }
+
+    #[inline]
+    fn insert_many(&self, entries: &[(usize, V)])
+    where
+        V: Clone,
+    {
+        (**self).insert_many(entries);
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+}
+
+impl<B: BackendModel + Sync, T: TimeSource + Sync, R: RngCore + SeedableRng> CacheRunner<B, T, R> {
+    /// 多线程共享同一缓存：每个线程在自己的 Compio 运行时中执行 `workloads` 中的一份，返回合计统计
+    pub fn run_cache_threads<V, C>(&self, cache: &C, workloads: &[Vec<Op<V>>]) -> Result<RunStats>
+    where
+        V: Value + Send + Sync,
+        C: ConcurrentCacheOps<V>,
+    {
+        thread::scope(|s| {
+            let handles: Vec<_> = workloads
+                .iter()
+                .map(|ops| {
+                    s.spawn(move || {
+                        let runtime = BenchRuntime::new()?;
+                        runtime.block_on(self.run_cache(ConcurrentAdapter::new(cache), ops))
+                    })
+                })
+                .collect();
+            let mut total = RunStats::default();
+            for h in handles {
+                let stats = h
+                    .join()
+                    .map_err(|_| AppError::CacheOperation("worker thread panicked".into()))??;
+                total += stats;
+            }
+            Ok(total)
+        })
+    }
+}
+
+/// 把线程安全缓存适配为 [`CacheOps`]，缓存最近一次读到的值以便返回引用
+pub struct ConcurrentAdapter<C, V> {
+    cache: C,
+    last: Option<V>,
+}
+
+impl<C, V> ConcurrentAdapter<C, V> {
+    /// 包装线程安全缓存
+    pub fn new(cache: C) -> Self {
+        Self { cache, last: None }
+    }
+}
+
+impl<V, C: ConcurrentCacheOps<V>> CacheOps<V> for ConcurrentAdapter<C, V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        self.last = self.cache.get(key);
+        self.last.as_ref()
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.cache.peek(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: usize, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &usize) -> bool {
+        self.cache.remove(key)
+    }
+
+    #[inline]
+    fn get_many(&mut self, keys: &[usize]) -> Vec<bool> {
+        self.cache.get_many(keys)
+    }
+
+    #[inline]
+    fn insert_many(&mut self, entries: &[(usize, V)])
+    where
+        V: Clone,
+    {
+        self.cache.insert_many(entries);
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.cache.name()
+    }
+}
@@ -0,0 +1,69 @@
+//! 预热收敛检测
+//! 按 Zipf 预热流持续写入，直到相邻窗口的命中率变化低于阈值；收敛所需的操作数衡量各缓存的预热速度
+
+use crate::{CacheKind, CacheOps, Convergence, Key, Op, Result, Value, WarmupConfig, WarmupManager};
+use serde::Serialize;
+
+/// 收敛检测结果
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Converged {
+    /// 预热写入次数，未收敛时为上限
+    pub ops: usize,
+    /// 最后一个窗口的命中率（百分比）
+    pub hit_rate: f64,
+    /// 是否在上限内收敛
+    pub converged: bool,
+}
+
+impl WarmupManager {
+    /// 收敛模式预热：按 Zipf 策略持续写入，写入前 key 已存在计为命中，相邻两个窗口的命中率之差低于阈值时停止
+    ///
+    /// 以相同配置、`size` 取 [`Converged::ops`] 生成的预热序列与此处写入的完全一致
+    pub fn converge<V: Value, K: Key, C: CacheOps<V, K>>(
+        &mut self,
+        cache: &mut C,
+        conv: &Convergence,
+    ) -> Result<Converged> {
+        let zipf = rand_distr::Zipf::new(self.cfg.key_space as f64, self.cfg.zipf_s)?;
+        let (mut ops, mut hits, mut hit_rate) = (0, 0u64, 0.0);
+        let mut prev: Option<f64> = None;
+        while ops < conv.max_ops {
+            let (key, val, read) = self.zipf_step(&zipf);
+            if cache.peek(&key) {
+                hits += 1;
+            }
+            if read {
+                Self::warmup_cache(cache, &[Op::Write(key.clone(), val), Op::Read(key)]);
+            } else {
+                Self::warmup_cache(cache, &[Op::Write(key, val)]);
+            }
+            ops += 1;
+            if ops % conv.window == 0 {
+                hit_rate = hits as f64 * 100.0 / conv.window as f64;
+                hits = 0;
+                if prev.is_some_and(|p| (hit_rate - p).abs() < conv.epsilon) {
+                    return Ok(Converged {
+                        ops,
+                        hit_rate,
+                        converged: true,
+                    });
+                }
+                prev = Some(hit_rate);
+            }
+        }
+        Ok(Converged {
+            ops,
+            hit_rate,
+            converged: false,
+        })
+    }
+}
+
+impl CacheKind {
+    /// 以指定容量新建缓存，测量按 `warmup` 预热至收敛所需的操作数
+    pub fn converge(self, capacity: u64, warmup: WarmupConfig, conv: &Convergence) -> Result<Converged> {
+        let mut cache = self.build(capacity)?;
+        WarmupManager::new(warmup).converge(&mut cache.as_mut(), conv)
+    }
+}
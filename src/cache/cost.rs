@@ -0,0 +1,179 @@
+//! 条目代价
+//! 按代价总和（如字节数）而非条目数限额，比较各缓存按大小淘汰的效果：moka、mini-moka 与 quick_cache
+//! 以原生 weigher 施加代价，其余缓存经 [`CostBounded`] 包装，超出预算时按各自的淘汰顺序逐个淘汰
+
+use crate::value::key_len;
+use crate::{AppError, CacheOps, Result};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+
+/// 条目代价函数，代价至少为 1
+pub type CostFn<K = usize, V = usize> = fn(&K, &V) -> u32;
+
+/// 条目的固定开销（字节）：key 与值头部
+pub const ENTRY_HEADER: usize = size_of::<usize>() + size_of::<Bytes>();
+
+/// 每个条目代价为 1
+#[inline]
+pub fn unit_cost(_key: &usize, _value: &usize) -> u32 {
+    1
+}
+
+/// 条目字节数：固定开销加上回源值 [`Bytes`] 的负载长度，同一 key 代价固定
+#[inline]
+pub fn sized_cost(key: &usize, _value: &usize) -> u32 {
+    (ENTRY_HEADER + key_len(*key)) as u32
+}
+
+/// 为不支持 weigher 的缓存按代价限额：记录常驻条目的代价，总和超出预算时经 [`CacheOps::evict_one`]
+/// 按内层缓存自身的淘汰顺序淘汰。内层缓存的条目数上限宜不小于可能出现的不同 key 数，使其只按代价淘汰；
+/// 内层缓存仍因条目数上限自行淘汰时，按常驻条目校正已记录的代价
+pub struct CostBounded<C, K = usize, V = usize> {
+    inner: C,
+    cost: CostFn<K, V>,
+    budget: u64,
+    used: u64,
+    costs: HashMap<K, u32>,
+}
+
+impl<C: CacheOps<V, K>, K, V> CostBounded<C, K, V> {
+    /// 以 `budget` 为代价预算包装 `inner`，`inner` 未实现 [`CacheOps::evict_one`] 时返回错误
+    pub fn new(inner: C, cost: CostFn<K, V>, budget: u64) -> Result<Self> {
+        if !inner.can_evict() {
+            return Err(AppError::Config(format!("{} cannot evict by cost", inner.name())));
+        }
+        Ok(Self {
+            inner,
+            cost,
+            budget,
+            used: 0,
+            costs: HashMap::new(),
+        })
+    }
+}
+
+impl<C, K, V> CostBounded<C, K, V> {
+    /// 常驻条目的代价总和
+    #[inline]
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// 代价预算
+    #[inline]
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+}
+
+impl<C: CacheOps<V, K>, K: Hash + Eq + Clone, V> CostBounded<C, K, V> {
+    /// 移除 key 后扣除其代价
+    #[inline]
+    fn release(&mut self, key: &K) {
+        if let Some(c) = self.costs.remove(key) {
+            self.used -= u64::from(c);
+        }
+    }
+
+    /// 只保留内层缓存中仍常驻的条目代价，并重新累计代价总和
+    fn reconcile(&mut self) {
+        let inner = &mut self.inner;
+        self.costs.retain(|k, _| inner.peek(k));
+        self.used = self.costs.values().map(|&c| u64::from(c)).sum();
+    }
+}
+
+impl<C: CacheOps<V, K>, K: Hash + Eq + Clone, V> CacheOps<V, K> for CostBounded<C, K, V> {
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.inner.get(key)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &K) -> bool {
+        self.inner.peek(key)
+    }
+
+    /// 单个条目超出预算时与 weigher 的行为一致，插入后随即被淘汰
+    fn insert(&mut self, key: K, value: V) {
+        let c = (self.cost)(&key, &value);
+        let old = self.costs.insert(key.clone(), c);
+        if let Some(old) = old {
+            self.used -= u64::from(old);
+        }
+        self.used += u64::from(c);
+        let before = self.inner.entry_count();
+        self.inner.insert(key, value);
+        // 条目数未按预期增长说明内层缓存自行淘汰了条目
+        if let (Some(before), Some(after)) = (before, self.inner.entry_count())
+            && after < before + usize::from(old.is_none())
+        {
+            self.reconcile();
+        }
+        while self.used > self.budget {
+            match self.inner.evict_one() {
+                Some(victim) => self.release(&victim),
+                // 内层缓存已空而记录仍超出预算，说明记录与常驻条目不一致，校正后不再超出
+                None => {
+                    self.reconcile();
+                    break;
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        let removed = self.inner.remove(key);
+        if removed {
+            self.release(key);
+        }
+        removed
+    }
+
+    fn evict_one(&mut self) -> Option<K> {
+        let victim = self.inner.evict_one()?;
+        self.release(&victim);
+        Some(victim)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        self.inner.entry_count()
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        self.inner.capacity_hint()
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+/// 把 [`CostFn`] 用作 quick_cache 的 weigher
+#[cfg(feature = "quick_cache")]
+pub struct CostWeighter<K = usize, V = usize>(pub CostFn<K, V>);
+
+#[cfg(feature = "quick_cache")]
+impl<K, V> Clone for CostWeighter<K, V> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+#[cfg(feature = "quick_cache")]
+impl<K, V> quick_cache::Weighter<K, V> for CostWeighter<K, V> {
+    #[inline]
+    fn weight(&self, key: &K, value: &V) -> u64 {
+        u64::from((self.0)(key, value))
+    }
+}
@@ -0,0 +1,187 @@
+//! 磁盘层
+//! 两级缓存的第二级：内存缓存未命中时先查磁盘上的存储，再回源。条目以定长块存放在数据文件中，按 LRU 淘汰，
+//! 读写经 Compio 异步文件 I/O，对应 CDN 节点、页缓存之下的本地磁盘
+//!
+//! 磁盘层不预热，新建即为空；回源成功的结果写入磁盘层，后端数据更新或删除时从磁盘层移除
+
+use crate::config::DISK_HEADER;
+use crate::{AppError, DiskTierConfig, Result};
+use compio::buf::BufResult;
+use compio::fs::{File, OpenOptions};
+use compio::io::{AsyncReadAtExt, AsyncWriteAtExt};
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{env, fs, process};
+
+/// 进程内的实例编号，与进程号一起构成临时文件名
+static INSTANCE: AtomicU64 = AtomicU64::new(0);
+
+/// 磁盘层，可经 `Arc` 在多个运行器间共享
+#[derive(Debug)]
+pub struct DiskTier {
+    file: File,
+    path: PathBuf,
+    /// 数据文件为临时文件，释放时删除
+    temp: bool,
+    block: usize,
+    index: Mutex<Index>,
+}
+
+impl DiskTier {
+    /// 创建数据文件，已存在时清空
+    pub async fn open(cfg: &DiskTierConfig) -> Result<Self> {
+        if cfg.capacity == 0 || cfg.block < DISK_HEADER {
+            return Err(AppError::Config(format!(
+                "disk.capacity must be > 0 and disk.block >= {DISK_HEADER}"
+            )));
+        }
+        let (path, temp) = match &cfg.path {
+            Some(path) => (path.clone(), false),
+            None => {
+                let instance = INSTANCE.fetch_add(1, Ordering::Relaxed);
+                (env::temp_dir().join(format!("lru-bench-{}-{instance}.disk", process::id())), true)
+            }
+        };
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path).await?;
+        Ok(Self {
+            file,
+            path,
+            temp,
+            block: cfg.block,
+            index: Mutex::new(Index::new(cfg.capacity)),
+        })
+    }
+
+    /// 读取 `key` 的块，不在磁盘层时返回 false
+    pub async fn get(&self, key: usize) -> Result<bool> {
+        let Some(slot) = self.index.lock().get(key) else {
+            return Ok(false);
+        };
+        let BufResult(res, buf) = self.file.read_exact_at(Vec::with_capacity(self.block), self.offset(slot)).await;
+        res?;
+        // 读取期间槽位可能被并发的写入复用，以首部的 key 校验
+        Ok(buf.get(..DISK_HEADER) == Some(&(key as u64).to_le_bytes()[..]))
+    }
+
+    /// 写入 `key` 的块，已满时淘汰最久未使用的条目
+    pub async fn insert(&self, key: usize) -> Result<()> {
+        let slot = self.index.lock().insert(key);
+        let mut buf = vec![0; self.block];
+        buf[..DISK_HEADER].copy_from_slice(&(key as u64).to_le_bytes());
+        let mut file = &self.file;
+        let BufResult(res, _) = file.write_all_at(buf, self.offset(slot)).await;
+        Ok(res?)
+    }
+
+    /// 移除 `key`，返回是否存在
+    #[inline]
+    pub fn remove(&self, key: usize) -> bool {
+        self.index.lock().remove(key)
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.index.lock().slots.len()
+    }
+
+    /// 是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 数据文件路径
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    fn offset(&self, slot: u64) -> u64 {
+        slot * self.block as u64
+    }
+}
+
+impl Drop for DiskTier {
+    fn drop(&mut self) {
+        if self.temp {
+            // 删除失败只留下临时文件，不影响结果
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// LRU 索引：key 到块号，按最近访问的序号排序
+#[derive(Debug)]
+struct Index {
+    capacity: u64,
+    /// key -> (块号, 访问序号)
+    slots: HashMap<usize, (u64, u64)>,
+    /// 访问序号 -> key，最小者最久未使用
+    order: BTreeMap<u64, usize>,
+    /// 移除后空出的块
+    free: Vec<u64>,
+    /// 已分配过的块数
+    used: u64,
+    tick: u64,
+}
+
+impl Index {
+    fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            slots: HashMap::new(),
+            order: BTreeMap::new(),
+            free: Vec::new(),
+            used: 0,
+            tick: 0,
+        }
+    }
+
+    /// 查找并标记为最近使用
+    fn get(&mut self, key: usize) -> Option<u64> {
+        self.tick += 1;
+        let (slot, tick) = self.slots.get_mut(&key)?;
+        self.order.remove(tick);
+        *tick = self.tick;
+        self.order.insert(self.tick, key);
+        Some(*slot)
+    }
+
+    /// 为 `key` 分配块，已存在时复用
+    fn insert(&mut self, key: usize) -> u64 {
+        if let Some(slot) = self.get(key) {
+            return slot;
+        }
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None if self.used < self.capacity => {
+                self.used += 1;
+                self.used - 1
+            }
+            // 容量非零，块已分配完时必有可淘汰的条目
+            None => self.evict().unwrap_or_default(),
+        };
+        self.slots.insert(key, (slot, self.tick));
+        self.order.insert(self.tick, key);
+        slot
+    }
+
+    fn remove(&mut self, key: usize) -> bool {
+        let Some((slot, tick)) = self.slots.remove(&key) else {
+            return false;
+        };
+        self.order.remove(&tick);
+        self.free.push(slot);
+        true
+    }
+
+    /// 淘汰最久未使用的条目，返回空出的块
+    fn evict(&mut self) -> Option<u64> {
+        let (_, key) = self.order.pop_first()?;
+        self.slots.remove(&key).map(|(slot, _)| slot)
+    }
+}
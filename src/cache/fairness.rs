@@ -0,0 +1,54 @@
+//! 容量公平性
+//! 各实现对“容量”的解释不同：mini-moka 按权重、分片 LRU 按分片取整、moka 在后台批量淘汰。
+//! 比较命中率前检查运行中实际达到的最大常驻条目数，与配置容量不符时各缓存实际上在不同容量下比较
+
+use crate::RunStats;
+use std::fmt;
+
+/// 最大常驻条目数低于可达上限超过此比例时视为未填满
+pub const FILL_TOLERANCE: f64 = 0.05;
+
+/// 实际容量与配置容量不符
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CapacityFit {
+    /// 常驻条目数超过配置容量
+    Overfilled { max_resident: u64, capacity: u64 },
+    /// 访问过的 key 足以填满，常驻条目数却明显低于容量
+    Underfilled { max_resident: u64, reachable: u64 },
+}
+
+impl CapacityFit {
+    /// 检查 `stats` 中的最大常驻条目数；`reachable` 为容量与访问过的不同 key 数取小（见 [`crate::CacheKind::resident`]），
+    /// 缓存不提供条目数时不检查
+    ///
+    /// mini-moka 0.10 删除条目不减计数，含删除的负载下偏大
+    pub fn check(stats: &RunStats, capacity: u64, reachable: u64) -> Option<Self> {
+        let max_resident = stats.max_resident;
+        if max_resident == 0 {
+            return None;
+        }
+        if max_resident > capacity {
+            return Some(Self::Overfilled { max_resident, capacity });
+        }
+        if (max_resident as f64) < reachable as f64 * (1.0 - FILL_TOLERANCE) {
+            return Some(Self::Underfilled { max_resident, reachable });
+        }
+        None
+    }
+}
+
+impl fmt::Display for CapacityFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Overfilled { max_resident, capacity } => {
+                write!(f, "held up to {max_resident} entries, above capacity {capacity}")
+            }
+            Self::Underfilled { max_resident, reachable } => write!(
+                f,
+                "held at most {max_resident} of {reachable} reachable entries ({:.1}%)",
+                max_resident as f64 * 100.0 / reachable as f64
+            ),
+        }
+    }
+}
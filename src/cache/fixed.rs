@@ -0,0 +1,173 @@
+//! 编译期容量 LRU
+//! 链表节点存放在一次性分配的定长堆数组中，用下标代替指针，没有逐节点堆分配；
+//! 数组不放在栈上，容量较大时也不会栈溢出
+
+use crate::CacheOps;
+use std::collections::HashMap;
+use std::iter::repeat_with;
+
+/// 空下标
+const NIL: u32 = u32::MAX;
+
+/// 容量为 `N` 的数组实现 LRU，`head` 为最近使用，`tail` 为最久未使用
+pub struct FixedLru<V, const N: usize> {
+    map: HashMap<usize, u32>,
+    /// 以下数组长度均为 `N`
+    keys: Box<[usize]>,
+    vals: Box<[Option<V>]>,
+    prev: Box<[u32]>,
+    next: Box<[u32]>,
+    head: u32,
+    tail: u32,
+    /// 删除后空出的槽位，经 `next` 串成链表
+    free: u32,
+    len: u32,
+}
+
+impl<V, const N: usize> Default for FixedLru<V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, const N: usize> FixedLru<V, N> {
+    /// 容量必须非零且下标可用 u32 表示
+    const VALID: () = assert!(N > 0 && N < NIL as usize);
+
+    /// 创建空缓存，数组与索引表一次性在堆上预分配
+    pub fn new() -> Self {
+        let () = Self::VALID;
+        Self {
+            map: HashMap::with_capacity(N),
+            keys: vec![0; N].into_boxed_slice(),
+            vals: repeat_with(|| None).take(N).collect(),
+            prev: vec![NIL; N].into_boxed_slice(),
+            next: vec![NIL; N].into_boxed_slice(),
+            head: NIL,
+            tail: NIL,
+            free: NIL,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn unlink(&mut self, i: u32) {
+        let (p, n) = (self.prev[i as usize], self.next[i as usize]);
+        if p == NIL {
+            self.head = n;
+        } else {
+            self.next[p as usize] = n;
+        }
+        if n == NIL {
+            self.tail = p;
+        } else {
+            self.prev[n as usize] = p;
+        }
+    }
+
+    #[inline]
+    fn push_front(&mut self, i: u32) {
+        self.prev[i as usize] = NIL;
+        self.next[i as usize] = self.head;
+        if self.head == NIL {
+            self.tail = i;
+        } else {
+            self.prev[self.head as usize] = i;
+        }
+        self.head = i;
+    }
+
+    #[inline]
+    fn touch(&mut self, i: u32) {
+        if self.head != i {
+            self.unlink(i);
+            self.push_front(i);
+        }
+    }
+}
+
+impl<V, const N: usize> CacheOps<V> for FixedLru<V, N> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        let i = *self.map.get(key)?;
+        self.touch(i);
+        self.vals[i as usize].as_ref()
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: usize, value: V) {
+        if let Some(&i) = self.map.get(&key) {
+            self.vals[i as usize] = Some(value);
+            self.touch(i);
+            return;
+        }
+        let i = if self.free != NIL {
+            let i = self.free;
+            self.free = self.next[i as usize];
+            self.len += 1;
+            i
+        } else if (self.len as usize) < N {
+            // 无空闲槽位时已用槽位连续
+            self.len += 1;
+            self.len - 1
+        } else {
+            // 已满，复用最久未使用的槽位
+            let i = self.tail;
+            self.unlink(i);
+            self.map.remove(&self.keys[i as usize]);
+            i
+        };
+        self.keys[i as usize] = key;
+        self.vals[i as usize] = Some(value);
+        self.push_front(i);
+        self.map.insert(key, i);
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &usize) -> bool {
+        let Some(i) = self.map.remove(key) else {
+            return false;
+        };
+        self.unlink(i);
+        self.vals[i as usize] = None;
+        self.next[i as usize] = self.free;
+        self.free = i;
+        self.len -= 1;
+        true
+    }
+
+    fn export_entries(&self) -> Option<Vec<(usize, V)>>
+    where
+        V: Clone,
+    {
+        let mut out = Vec::with_capacity(self.len as usize);
+        let mut i = self.tail;
+        while i != NIL {
+            if let Some(v) = &self.vals[i as usize] {
+                out.push((self.keys[i as usize], v.clone()));
+            }
+            i = self.prev[i as usize];
+        }
+        Some(out)
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(self.len as usize)
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Fixed LRU"
+    }
+}
@@ -0,0 +1,369 @@
+//! 异步缓存
+//! 定义读写可挂起的缓存接口，接入 `moka::future` 等异步缓存，在 Compio 运行时上直接 await；
+//! 并提供缓存旁路加载器模式，比较原生单飞与手工模拟的回源次数
+
+use super::WarmupStep;
+use crate::{
+    BackendModel, CacheRunner, ConcurrentCacheOps, Op, Outcome, Result, RunStats, TimeSource, Value, WarmupManager,
+};
+use futures_util::future::join_all;
+use rand::{RngCore, SeedableRng};
+use std::collections::HashSet;
+#[cfg(feature = "moka")]
+use moka::future::Cache as MokaFutureCache;
+use std::future::{Future, ready};
+use std::time::{Duration, Instant};
+
+/// 异步缓存 trait，所有操作只需共享引用，读取返回值的副本
+pub trait AsyncCacheOps<V> {
+    /// 获取缓存值
+    fn get(&self, key: &usize) -> impl Future<Output = Option<V>>;
+
+    /// 查询 key 是否存在，不更新访问顺序（不支持时退化为 get）
+    fn peek(&self, key: &usize) -> impl Future<Output = bool> {
+        async move { self.get(key).await.is_some() }
+    }
+
+    /// 插入键值对
+    fn insert(&self, key: usize, value: V) -> impl Future<Output = ()>;
+
+    /// 移除 key，返回移除前是否存在
+    fn remove(&self, key: &usize) -> impl Future<Output = bool>;
+
+    /// 缓存旁路加载：命中时直接返回，未命中时执行 `load` 并写入其结果（`None` 不写入）
+    ///
+    /// 默认实现手工模拟，同一 key 的并发未命中会各自加载；原生支持的缓存应合并为单次加载
+    fn get_or_load(&self, key: usize, load: impl Future<Output = Option<V>>) -> impl Future<Output = Option<V>>
+    where
+        V: Clone,
+    {
+        async move {
+            if let Some(v) = self.get(&key).await {
+                return Some(v);
+            }
+            let v = load.await?;
+            self.insert(key, v.clone()).await;
+            Some(v)
+        }
+    }
+
+    /// 获取缓存名称（用于日志）
+    fn name(&self) -> &'static str;
+}
+
+#[cfg(feature = "moka")]
+impl<V: Clone + Send + Sync + 'static> AsyncCacheOps<V> for MokaFutureCache<usize, V> {
+    #[inline]
+    fn get(&self, key: &usize) -> impl Future<Output = Option<V>> {
+        MokaFutureCache::get(self, key)
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> impl Future<Output = bool> {
+        ready(self.contains_key(key))
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) -> impl Future<Output = ()> {
+        MokaFutureCache::insert(self, key, value)
+    }
+
+    #[inline]
+    async fn remove(&self, key: &usize) -> bool {
+        MokaFutureCache::remove(self, key).await.is_some()
+    }
+
+    /// 原生单飞：同一 key 的并发未命中只执行一次 `load`
+    #[inline]
+    fn get_or_load(&self, key: usize, load: impl Future<Output = Option<V>>) -> impl Future<Output = Option<V>> {
+        self.optionally_get_with(key, load)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Moka Future"
+    }
+}
+
+/// 把线程安全缓存适配为 [`AsyncCacheOps`]，加载由默认实现手工模拟
+pub struct AsyncAdapter<C>(pub C);
+
+impl<V, C: ConcurrentCacheOps<V>> AsyncCacheOps<V> for AsyncAdapter<C> {
+    #[inline]
+    fn get(&self, key: &usize) -> impl Future<Output = Option<V>> {
+        ready(self.0.get(key))
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> impl Future<Output = bool> {
+        ready(self.0.peek(key))
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) -> impl Future<Output = ()> {
+        self.0.insert(key, value);
+        ready(())
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> impl Future<Output = bool> {
+        ready(self.0.remove(key))
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl<B: BackendModel, T: TimeSource, R: RngCore + SeedableRng> CacheRunner<B, T, R> {
+    /// 运行异步缓存，语义与 [`CacheRunner::run_cache`] 一致
+    pub async fn run_async_cache<V: Value, C: AsyncCacheOps<V>>(&self, cache: &C, ops: &[Op<V>]) -> Result<RunStats> {
+        self.run_async(cache, ops, None::<fn(&Op<V>, Outcome, Duration)>).await
+    }
+
+    /// 同 [`Self::run_async_cache`]，每个操作完成后以 (操作, 结果, 耗时) 调用 `observer`
+    pub async fn run_async_cache_observed<V: Value, C: AsyncCacheOps<V>>(
+        &self,
+        cache: &C,
+        ops: &[Op<V>],
+        observer: impl FnMut(&Op<V>, Outcome, Duration),
+    ) -> Result<RunStats> {
+        self.run_async(cache, ops, Some(observer)).await
+    }
+
+    async fn run_async<V: Value, C: AsyncCacheOps<V>>(
+        &self,
+        cache: &C,
+        ops: &[Op<V>],
+        mut observer: Option<impl FnMut(&Op<V>, Outcome, Duration)>,
+    ) -> Result<RunStats> {
+        let mut backend_rng = self.backend_rng();
+        let mut stats = RunStats::default();
+        let mut loaded = HashSet::new();
+        let started = Instant::now();
+        let begin = self.clock.now();
+
+        for op in ops {
+            if self.out_of_time(&mut stats) {
+                break;
+            }
+            stats.ops += 1;
+            // 无观察者时不计时
+            let start = observer.is_some().then(|| self.clock.now());
+            let outcome = match op {
+                Op::Read(key) | Op::Peek(key) => {
+                    let hit = match op {
+                        Op::Peek(_) => timed(&mut stats.cache_time, cache.peek(key)).await,
+                        _ => timed(&mut stats.cache_time, cache.get(key)).await.is_some(),
+                    };
+                    self.record_tier(&mut stats, *key, hit);
+                    if hit {
+                        stats.hits += 1;
+                        Outcome::Hit
+                    } else {
+                        stats.misses += 1;
+                        if self.fetch(*key, true, &mut stats, &mut backend_rng, &mut loaded).await? {
+                            timed(&mut stats.cache_time, cache.insert(*key, V::load(*key))).await;
+                            Outcome::Miss
+                        } else {
+                            Outcome::Failed
+                        }
+                    }
+                }
+                Op::ReadMissing(key) => {
+                    let hit = timed(&mut stats.cache_time, cache.get(key)).await.is_some();
+                    self.record_tier(&mut stats, *key, hit);
+                    if hit {
+                        stats.hits += 1;
+                        stats.negative_hits += 1;
+                        Outcome::Hit
+                    } else {
+                        stats.misses += 1;
+                        stats.negative_misses += 1;
+                        if self.fetch(*key, self.opts.negative_cache, &mut stats, &mut backend_rng, &mut loaded).await? {
+                            if self.opts.negative_cache {
+                                timed(&mut stats.cache_time, cache.insert(*key, V::sized(*key, 0))).await;
+                            }
+                            Outcome::Miss
+                        } else {
+                            Outcome::Failed
+                        }
+                    }
+                }
+                Op::Write(key, val) | Op::Insert(key, val) => {
+                    self.write_back(*key, &mut stats, &mut backend_rng).await?;
+                    timed(&mut stats.cache_time, cache.insert(*key, val.clone())).await;
+                    count_write(op, &mut stats);
+                    match op {
+                        Op::Insert(..) => Outcome::Insert,
+                        _ => Outcome::Write,
+                    }
+                }
+                Op::Delete(key) => {
+                    timed(&mut stats.cache_time, cache.remove(key)).await;
+                    self.invalidate(*key);
+                    loaded.remove(key);
+                    stats.deletes += 1;
+                    Outcome::Delete
+                }
+                Op::Scan(..) => {
+                    let (mut hits, mut misses) = (0, 0);
+                    for key in op.keys() {
+                        let hit = timed(&mut stats.cache_time, cache.get(&key)).await.is_some();
+                        self.record_tier(&mut stats, key, hit);
+                        if hit {
+                            hits += 1;
+                        } else {
+                            misses += 1;
+                            if self.fetch(key, true, &mut stats, &mut backend_rng, &mut loaded).await? {
+                                timed(&mut stats.cache_time, cache.insert(key, V::load(key))).await;
+                            }
+                        }
+                    }
+                    stats.hits += hits;
+                    stats.misses += misses;
+                    Outcome::Scan { hits, misses }
+                }
+            };
+            if let (Some(f), Some(start)) = (observer.as_mut(), start) {
+                f(op, outcome, self.clock.now().saturating_sub(start));
+            }
+            self.report_progress(&stats, Some(ops.len()), started, false);
+        }
+        self.report_progress(&stats, Some(ops.len()), started, true);
+        stats.elapsed = self.clock.now().saturating_sub(begin);
+
+        Ok(stats)
+    }
+
+    /// 以加载器模式运行：读取经 [`AsyncCacheOps::get_or_load`] 回源，`workloads` 中的每份在同一线程上并发执行
+    ///
+    /// 只有真正执行了加载器的读取计为未命中，因此单飞合并的等待者计为命中；`peek` 按普通读取处理
+    pub async fn run_async_loader<V: Value, C: AsyncCacheOps<V>>(
+        &self,
+        cache: &C,
+        workloads: &[Vec<Op<V>>],
+    ) -> Result<RunStats> {
+        let tasks = workloads.iter().map(|ops| self.load_task(cache, ops));
+        let mut total = RunStats::default();
+        for stats in join_all(tasks).await {
+            total += stats?;
+        }
+        Ok(total)
+    }
+
+    async fn load_task<V: Value, C: AsyncCacheOps<V>>(&self, cache: &C, ops: &[Op<V>]) -> Result<RunStats> {
+        let mut backend_rng = self.backend_rng();
+        let mut stats = RunStats::default();
+        let mut loaded = HashSet::new();
+        let begin = self.clock.now();
+
+        for op in ops {
+            stats.ops += 1;
+            let missing = match op {
+                Op::Read(_) | Op::Peek(_) | Op::Scan(..) => false,
+                Op::ReadMissing(_) => true,
+                Op::Write(key, val) | Op::Insert(key, val) => {
+                    self.write_back(*key, &mut stats, &mut backend_rng).await?;
+                    timed(&mut stats.cache_time, cache.insert(*key, val.clone())).await;
+                    count_write(op, &mut stats);
+                    continue;
+                }
+                Op::Delete(key) => {
+                    timed(&mut stats.cache_time, cache.remove(key)).await;
+                    self.invalidate(*key);
+                    loaded.remove(key);
+                    stats.deletes += 1;
+                    continue;
+                }
+            };
+            for key in op.keys() {
+                let (mut ran, mut failure, mut fetching) = (false, None, Duration::ZERO);
+                let begin = Instant::now();
+                let load = async {
+                    ran = true;
+                    let keep = !missing || self.opts.negative_cache;
+                    let fetch_start = Instant::now();
+                    let fetched = self.fetch(key, keep, &mut stats, &mut backend_rng, &mut loaded).await;
+                    fetching = fetch_start.elapsed();
+                    match fetched {
+                        Ok(true) if missing => self.opts.negative_cache.then(|| V::sized(key, 0)),
+                        Ok(true) => Some(V::load(key)),
+                        Ok(false) => None,
+                        Err(e) => {
+                            failure = Some(e);
+                            None
+                        }
+                    }
+                };
+                cache.get_or_load(key, load).await;
+                // 回源时间按运行器的时钟计入 backend_time，这里扣除其实际耗时；等待其他任务的加载计入缓存调用
+                stats.cache_time += begin.elapsed().saturating_sub(fetching);
+                if let Some(e) = failure {
+                    return Err(e);
+                }
+                self.record_tier(&mut stats, key, !ran);
+                match (ran, missing) {
+                    (false, false) => stats.hits += 1,
+                    (false, true) => {
+                        stats.hits += 1;
+                        stats.negative_hits += 1;
+                    }
+                    (true, false) => stats.misses += 1,
+                    (true, true) => {
+                        stats.misses += 1;
+                        stats.negative_misses += 1;
+                    }
+                }
+            }
+        }
+        stats.elapsed = self.clock.now().saturating_sub(begin);
+
+        Ok(stats)
+    }
+}
+
+impl WarmupManager {
+    /// 异步缓存的预热，语义与 [`WarmupManager::warmup_cache`] 一致
+    pub async fn warmup_async_cache<V: Value, C: AsyncCacheOps<V>>(cache: &C, warmup_ops: &[Op<V>]) {
+        for step in warmup_ops.iter().flat_map(Op::warmup_steps) {
+            match step {
+                WarmupStep::Get(key) => {
+                    cache.get(key).await;
+                }
+                WarmupStep::Peek(key) => {
+                    cache.peek(key).await;
+                }
+                WarmupStep::Insert(key, val) => cache.insert(key, val).await,
+                WarmupStep::Remove(key) => {
+                    cache.remove(key).await;
+                }
+                WarmupStep::GetMany(keys) => {
+                    for key in &keys {
+                        cache.get(key).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 按写操作类型分别计数
+#[inline]
+fn count_write<V>(op: &Op<V>, stats: &mut RunStats) {
+    if let Op::Insert(..) = op {
+        stats.inserts += 1;
+    } else {
+        stats.updates += 1;
+    }
+}
+
+/// 等待缓存调用，耗时累加到 `time`
+#[inline]
+async fn timed<F: Future>(time: &mut Duration, f: F) -> F::Output {
+    let start = Instant::now();
+    let out = f.await;
+    *time += start.elapsed();
+    out
+}
@@ -0,0 +1,152 @@
+//! GDSF（Greedy-Dual-Size-Frequency）淘汰算法
+//! 条目优先级 H = L + 访问次数 / 代价，淘汰优先级最低的条目并把膨胀值 L 提升到其优先级，
+//! 使长期未访问的条目随 L 增长逐渐老化；小而热的条目优先保留，适合 CDN 等大小差异悬殊的场景。
+//! 代价取自 [`CostFn`]，每个条目代价为 1 时退化为带老化的 LFU
+
+use crate::{AppError, CacheOps, CostFn, Result};
+use std::collections::{BTreeSet, HashMap};
+
+struct Entry<V> {
+    value: V,
+    cost: u32,
+    freq: u32,
+    /// 在优先队列中的位置
+    rank: Rank,
+}
+
+/// 优先级（非负浮点数的位模式，与数值同序）与插入序号，同优先级时先淘汰较早更新的条目
+type Rank = (u64, u64);
+
+/// GDSF 缓存，容量为代价预算
+pub struct Gdsf<V> {
+    map: HashMap<usize, Entry<V>>,
+    queue: BTreeSet<(Rank, usize)>,
+    cost: CostFn<usize, V>,
+    /// 膨胀值，为最近淘汰条目的优先级
+    inflation: f64,
+    seq: u64,
+    used: u64,
+    budget: u64,
+}
+
+impl<V> Gdsf<V> {
+    /// 以 `budget` 为代价预算、`cost` 计算条目代价创建缓存
+    pub fn new(budget: u64, cost: CostFn<usize, V>) -> Result<Self> {
+        if budget == 0 {
+            return Err(AppError::Config("gdsf budget must be > 0".into()));
+        }
+        Ok(Self {
+            map: HashMap::new(),
+            queue: BTreeSet::new(),
+            cost,
+            inflation: 0.0,
+            seq: 0,
+            used: 0,
+            budget,
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 常驻条目的代价总和
+    #[inline]
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// 按当前膨胀值计算访问 `freq` 次、代价 `cost` 的条目的位置
+    fn rank(&mut self, freq: u32, cost: u32) -> Rank {
+        self.seq += 1;
+        let priority = self.inflation + f64::from(freq) / f64::from(cost.max(1));
+        (priority.to_bits(), self.seq)
+    }
+
+    /// 访问一次：增加访问次数并按新优先级重新排队
+    fn touch(&mut self, key: usize) {
+        let Some(e) = self.map.get(&key) else { return };
+        let (old, freq, cost) = (e.rank, e.freq.saturating_add(1), e.cost);
+        let rank = self.rank(freq, cost);
+        self.queue.remove(&(old, key));
+        self.queue.insert((rank, key));
+        if let Some(e) = self.map.get_mut(&key) {
+            e.freq = freq;
+            e.rank = rank;
+        }
+    }
+
+    /// 移除条目并扣除其代价
+    fn take(&mut self, key: &usize) -> Option<Entry<V>> {
+        let e = self.map.remove(key)?;
+        self.queue.remove(&(e.rank, *key));
+        self.used -= u64::from(e.cost);
+        Some(e)
+    }
+}
+
+impl<V> CacheOps<V> for Gdsf<V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        self.touch(*key);
+        self.map.get(key).map(|e| &e.value)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// 更新已有条目计为一次访问；单个条目超出预算时不缓存
+    fn insert(&mut self, key: usize, value: V) {
+        let freq = self.take(&key).map_or(1, |e| e.freq.saturating_add(1));
+        let cost = (self.cost)(&key, &value);
+        if u64::from(cost) > self.budget {
+            return;
+        }
+        while self.used + u64::from(cost) > self.budget {
+            if self.evict_one().is_none() {
+                break;
+            }
+        }
+        let rank = self.rank(freq, cost);
+        self.queue.insert((rank, key));
+        self.map.insert(key, Entry { value, cost, freq, rank });
+        self.used += u64::from(cost);
+    }
+
+    fn remove(&mut self, key: &usize) -> bool {
+        self.take(key).is_some()
+    }
+
+    fn evict_one(&mut self) -> Option<usize> {
+        let ((bits, _), key) = self.queue.pop_first()?;
+        self.inflation = f64::from_bits(bits);
+        if let Some(e) = self.map.remove(&key) {
+            self.used -= u64::from(e.cost);
+        }
+        Some(key)
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(Gdsf::len(self))
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "GDSF"
+    }
+}
@@ -0,0 +1,28 @@
+//! 中断处理
+//! 长时间的扫描或场景运行中按下 Ctrl-C 时不直接退出：运行器在下一次检查截止时间时停止，
+//! 已完成部分标记为部分结果，照常写出报告；再次按下时立即退出
+
+use crate::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// 按 Ctrl-C 退出时的退出码（128 + SIGINT）
+pub const INTERRUPT_EXIT_CODE: i32 = 130;
+
+/// 安装 Ctrl-C 处理：首次中断只设置标志，第二次以 [`INTERRUPT_EXIT_CODE`] 立即退出；整个进程只能安装一次
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(INTERRUPT_EXIT_CODE);
+        }
+        eprintln!("\ninterrupt received, stopping and writing partial results (press Ctrl-C again to abort)");
+    })?;
+    Ok(())
+}
+
+/// 是否已收到中断
+#[inline]
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
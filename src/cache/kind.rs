@@ -0,0 +1,407 @@
+//! 按名称选择缓存实现
+//! 供命令行与参数扫描在运行时构建缓存
+
+use crate::{
+    AppError, BackendModel, CacheOps, CacheRunner, Clock, CostBounded, CostModel, Gdsf, LatencyRecorder, NoCache, Op, Outcome,
+    Result, RunStats, S3Fifo, Sieve, TimeSource, WarmupManager, unit_cost,
+};
+#[cfg(any(feature = "lru", feature = "moka"))]
+use crate::ConcurrentAdapter;
+#[cfg(feature = "hashlink")]
+use crate::ArcCache;
+#[cfg(any(feature = "mini-moka", feature = "moka"))]
+use crate::OptimizedMokaCacheBuilder;
+#[cfg(feature = "lru")]
+use crate::ShardedLru;
+#[cfg(feature = "hashlink")]
+use hashlink::LruCache as HashlinkLruCache;
+#[cfg(feature = "lru")]
+use crate::lru_cache;
+#[cfg(feature = "quick_cache")]
+use crate::CostWeighter;
+#[cfg(feature = "moka")]
+use moka::sync::{Cache as MokaSyncCache, CacheBuilder as MokaSyncBuilder};
+#[cfg(feature = "quick_cache")]
+use quick_cache::unsync::Cache as QuickCache;
+#[cfg(feature = "schnellru")]
+use schnellru::{ByLength, LruMap as SchnellLruMap};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+#[cfg(feature = "moka")]
+use std::sync::Arc;
+#[cfg(feature = "moka")]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 可按名称选择的缓存实现，配置与场景文件中写作 [`CacheKind::key`] 的名称
+///
+/// 第三方缓存由同名 Cargo feature 控制是否编译，未启用的实现不在 [`CacheKind::ALL`] 中，运行时报错
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum CacheKind {
+    Hashlink,
+    Lru,
+    MiniMoka,
+    MokaSync,
+    /// moka 关闭 TinyLFU 准入，纯 LRU 淘汰
+    MokaSyncLru,
+    QuickCache,
+    Schnellru,
+    ShardedLru,
+    S3Fifo,
+    Sieve,
+    Clock,
+    Arc,
+    /// 按大小与访问频率淘汰，见 [`Gdsf`]
+    Gdsf,
+    Unbounded,
+    NoCache,
+}
+
+impl CacheKind {
+    /// 已编译的全部实现
+    pub const ALL: &'static [Self] = &[
+        #[cfg(feature = "hashlink")]
+        Self::Hashlink,
+        #[cfg(feature = "lru")]
+        Self::Lru,
+        #[cfg(feature = "mini-moka")]
+        Self::MiniMoka,
+        #[cfg(feature = "moka")]
+        Self::MokaSync,
+        #[cfg(feature = "moka")]
+        Self::MokaSyncLru,
+        #[cfg(feature = "quick_cache")]
+        Self::QuickCache,
+        #[cfg(feature = "schnellru")]
+        Self::Schnellru,
+        #[cfg(feature = "lru")]
+        Self::ShardedLru,
+        Self::S3Fifo,
+        Self::Sieve,
+        Self::Clock,
+        #[cfg(feature = "hashlink")]
+        Self::Arc,
+        Self::Gdsf,
+        Self::Unbounded,
+        Self::NoCache,
+    ];
+
+    /// 已编译且支持按代价限额（[`CostModel`] 非 `unit`）的实现；分片 LRU 与 ARC 的淘汰策略依赖条目数容量，不支持；
+    /// S3-FIFO 的小队列按包装的条目数上限划分
+    pub const COST_AWARE: &'static [Self] = &[
+        #[cfg(feature = "hashlink")]
+        Self::Hashlink,
+        #[cfg(feature = "lru")]
+        Self::Lru,
+        #[cfg(feature = "mini-moka")]
+        Self::MiniMoka,
+        #[cfg(feature = "moka")]
+        Self::MokaSync,
+        #[cfg(feature = "moka")]
+        Self::MokaSyncLru,
+        #[cfg(feature = "quick_cache")]
+        Self::QuickCache,
+        #[cfg(feature = "schnellru")]
+        Self::Schnellru,
+        Self::S3Fifo,
+        Self::Sieve,
+        Self::Clock,
+        Self::Gdsf,
+        Self::Unbounded,
+        Self::NoCache,
+    ];
+
+    /// 包括未编译实现在内的全部名称，用于解析时区分拼写错误与未启用的 feature
+    const KNOWN: [Self; 15] = [
+        Self::Hashlink,
+        Self::Lru,
+        Self::MiniMoka,
+        Self::MokaSync,
+        Self::MokaSyncLru,
+        Self::QuickCache,
+        Self::Schnellru,
+        Self::ShardedLru,
+        Self::S3Fifo,
+        Self::Sieve,
+        Self::Clock,
+        Self::Arc,
+        Self::Gdsf,
+        Self::Unbounded,
+        Self::NoCache,
+    ];
+
+    /// 命令行中使用的名称
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Hashlink => "hashlink",
+            Self::Lru => "lru",
+            Self::MiniMoka => "mini-moka",
+            Self::MokaSync => "moka-sync",
+            Self::MokaSyncLru => "moka-sync-lru",
+            Self::QuickCache => "quick-cache",
+            Self::Schnellru => "schnellru",
+            Self::ShardedLru => "sharded-lru",
+            Self::S3Fifo => "s3-fifo",
+            Self::Sieve => "sieve",
+            Self::Clock => "clock",
+            Self::Arc => "arc",
+            Self::Gdsf => "gdsf",
+            Self::Unbounded => "unbounded",
+            Self::NoCache => "no-cache",
+        }
+    }
+
+    /// 编译该实现所需的 Cargo feature，内置实现为 None
+    pub fn feature(self) -> Option<&'static str> {
+        match self {
+            Self::Hashlink | Self::Arc => Some("hashlink"),
+            Self::Lru | Self::ShardedLru => Some("lru"),
+            Self::MiniMoka => Some("mini-moka"),
+            Self::MokaSync | Self::MokaSyncLru => Some("moka"),
+            Self::QuickCache => Some("quick_cache"),
+            Self::Schnellru => Some("schnellru"),
+            _ => None,
+        }
+    }
+
+    /// 是否已编译
+    #[inline]
+    pub fn available(self) -> bool {
+        Self::ALL.contains(&self)
+    }
+
+    /// 未编译实现的错误
+    pub(crate) fn unavailable(self) -> AppError {
+        let feature = self.feature().unwrap_or_default();
+        AppError::Config(format!(
+            "cache `{}` is not compiled in, rebuild with `--features {feature}`",
+            self.key()
+        ))
+    }
+
+    /// 运行结束时的常驻条目数上限（容量与访问过的不同 key 数取小），用于折算每条目内存
+    pub fn resident(self, capacity: u64, warmup_ops: &[Op], ops: &[Op]) -> u64 {
+        if self == Self::NoCache {
+            return 0;
+        }
+        let keys = warmup_ops.iter().chain(ops).map(Op::key).collect::<HashSet<_>>().len() as u64;
+        match self {
+            Self::Unbounded => keys,
+            _ => capacity.min(keys),
+        }
+    }
+
+    /// 以指定容量新建缓存，预热后运行工作负载；运行器设置了 [`CostModel`] 时按其代价预算限额
+    pub async fn run<B: BackendModel, T: TimeSource>(
+        self,
+        runner: &CacheRunner<B, T>,
+        capacity: u64,
+        warmup_ops: &[Op],
+        ops: &[Op],
+    ) -> Result<RunStats> {
+        self.run_with(runner, capacity, warmup_ops, ops, None::<fn(&Op, Outcome, Duration)>)
+            .await
+    }
+
+    /// 同 [`Self::run`]，每个操作（不含预热）完成后回调 `observer`
+    pub async fn run_observed<B: BackendModel, T: TimeSource>(
+        self,
+        runner: &CacheRunner<B, T>,
+        capacity: u64,
+        warmup_ops: &[Op],
+        ops: &[Op],
+        observer: impl FnMut(&Op, Outcome, Duration),
+    ) -> Result<RunStats> {
+        self.run_with(runner, capacity, warmup_ops, ops, Some(observer)).await
+    }
+
+    /// 同 [`Self::run`]，并记录读写延迟直方图
+    pub async fn run_latency<B: BackendModel, T: TimeSource>(
+        self,
+        runner: &CacheRunner<B, T>,
+        capacity: u64,
+        warmup_ops: &[Op],
+        ops: &[Op],
+    ) -> Result<(RunStats, LatencyRecorder)> {
+        let mut rec = LatencyRecorder::new()?;
+        let observer = |_: &Op, o, d| rec.record(o, d);
+        let stats = self.run_observed(runner, capacity, warmup_ops, ops, observer).await?;
+        Ok((stats, rec))
+    }
+
+    async fn run_with<B: BackendModel, T: TimeSource>(
+        self,
+        runner: &CacheRunner<B, T>,
+        capacity: u64,
+        warmup_ops: &[Op],
+        ops: &[Op],
+        observer: Option<impl FnMut(&Op, Outcome, Duration)>,
+    ) -> Result<RunStats> {
+        if runner.cost() != CostModel::Unit {
+            return self.run_cost_bounded(runner, capacity, warmup_ops, ops, observer).await;
+        }
+        let cap = capacity as usize;
+        match self {
+            #[cfg(feature = "hashlink")]
+            Self::Hashlink => warm_run(runner, HashlinkLruCache::new(cap), warmup_ops, ops, observer).await,
+            #[cfg(feature = "lru")]
+            Self::Lru => warm_run(runner, lru_cache(cap)?, warmup_ops, ops, observer).await,
+            #[cfg(feature = "mini-moka")]
+            Self::MiniMoka => {
+                let cache = OptimizedMokaCacheBuilder::build_high_performance_cache(capacity);
+                warm_run(runner, cache, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "moka")]
+            Self::MokaSync | Self::MokaSyncLru => {
+                let builder = OptimizedMokaCacheBuilder::sync_cache_builder(capacity, self == Self::MokaSync);
+                run_moka(runner, builder, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "quick_cache")]
+            Self::QuickCache => warm_run(runner, QuickCache::new(cap), warmup_ops, ops, observer).await,
+            #[cfg(feature = "schnellru")]
+            Self::Schnellru => {
+                let cap = u32::try_from(capacity)
+                    .map_err(|_| AppError::Config(format!("capacity {capacity} exceeds u32")))?;
+                warm_run(runner, SchnellLruMap::new(ByLength::new(cap)), warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "lru")]
+            Self::ShardedLru => {
+                // 逐次插入读取条目数需锁住全部分片，只在结束时读取
+                let cache = match runner.shard_seed() {
+                    Some(seed) => ShardedLru::seeded(cap, seed)?,
+                    None => ShardedLru::new(cap)?,
+                };
+                let mut stats = warm_run(runner, ConcurrentAdapter::new(&cache), warmup_ops, ops, observer).await?;
+                stats.max_resident = cache.len() as u64;
+                Ok(stats)
+            }
+            Self::S3Fifo => warm_run(runner, S3Fifo::new(cap)?, warmup_ops, ops, observer).await,
+            Self::Sieve => warm_run(runner, Sieve::new(cap)?, warmup_ops, ops, observer).await,
+            Self::Clock => warm_run(runner, Clock::new(cap)?, warmup_ops, ops, observer).await,
+            #[cfg(feature = "hashlink")]
+            Self::Arc => warm_run(runner, ArcCache::new(cap)?, warmup_ops, ops, observer).await,
+            Self::Gdsf => warm_run(runner, Gdsf::new(capacity, unit_cost)?, warmup_ops, ops, observer).await,
+            // 锚点不受容量限制
+            Self::Unbounded => warm_run(runner, HashMap::new(), warmup_ops, ops, observer).await,
+            Self::NoCache => warm_run(runner, NoCache, warmup_ops, ops, observer).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unavailable()),
+        }
+    }
+
+    /// 按代价预算运行：moka、mini-moka、quick_cache 以原生 weigher 施加代价，GDSF 本身按代价淘汰，其余经 [`CostBounded`] 包装
+    async fn run_cost_bounded<B: BackendModel, T: TimeSource>(
+        self,
+        runner: &CacheRunner<B, T>,
+        capacity: u64,
+        warmup_ops: &[Op],
+        ops: &[Op],
+        observer: Option<impl FnMut(&Op, Outcome, Duration)>,
+    ) -> Result<RunStats> {
+        let model = runner.cost();
+        let (cost, budget) = (model.cost_fn(), model.budget(capacity));
+        // 包装的缓存以出现过的不同 key 数为条目数上限，只按代价淘汰
+        let bound = Self::Unbounded.resident(capacity, warmup_ops, ops).max(1) as usize;
+        match self {
+            #[cfg(feature = "hashlink")]
+            Self::Hashlink => {
+                let cache = CostBounded::new(HashlinkLruCache::new(bound), cost, budget)?;
+                warm_run(runner, cache, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "lru")]
+            Self::Lru => warm_run(runner, CostBounded::new(lru_cache(bound)?, cost, budget)?, warmup_ops, ops, observer).await,
+            #[cfg(feature = "mini-moka")]
+            Self::MiniMoka => {
+                let cache = OptimizedMokaCacheBuilder::build_cost_cache(budget, cost);
+                warm_run(runner, cache, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "moka")]
+            Self::MokaSync | Self::MokaSyncLru => {
+                let builder = OptimizedMokaCacheBuilder::sync_cache_builder(budget, self == Self::MokaSync).weigher(cost);
+                run_moka(runner, builder, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "quick_cache")]
+            Self::QuickCache => {
+                let cache = QuickCache::with_weighter(capacity as usize, budget, CostWeighter(cost));
+                warm_run(runner, cache, warmup_ops, ops, observer).await
+            }
+            #[cfg(feature = "schnellru")]
+            Self::Schnellru => {
+                let bound = u32::try_from(bound).map_err(|_| AppError::Config(format!("{bound} keys exceed u32")))?;
+                let cache = CostBounded::new(SchnellLruMap::new(ByLength::new(bound)), cost, budget)?;
+                warm_run(runner, cache, warmup_ops, ops, observer).await
+            }
+            Self::S3Fifo => warm_run(runner, CostBounded::new(S3Fifo::new(bound)?, cost, budget)?, warmup_ops, ops, observer).await,
+            Self::Sieve => warm_run(runner, CostBounded::new(Sieve::new(bound)?, cost, budget)?, warmup_ops, ops, observer).await,
+            Self::Clock => warm_run(runner, CostBounded::new(Clock::new(bound)?, cost, budget)?, warmup_ops, ops, observer).await,
+            Self::Gdsf => warm_run(runner, Gdsf::new(budget, cost)?, warmup_ops, ops, observer).await,
+            // 锚点不受容量限制，与代价无关
+            Self::Unbounded => warm_run(runner, HashMap::new(), warmup_ops, ops, observer).await,
+            Self::NoCache => warm_run(runner, NoCache, warmup_ops, ops, observer).await,
+            _ if !self.available() => Err(self.unavailable()),
+            _ => {
+                let keys: Vec<_> = Self::COST_AWARE.iter().map(|k| k.key()).collect();
+                Err(AppError::Config(format!(
+                    "cache `{}` does not support cost model `{model}`, expected one of: {}",
+                    self.key(),
+                    keys.join(", ")
+                )))
+            }
+        }
+    }
+}
+
+impl FromStr for CacheKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let kind = Self::KNOWN.into_iter().find(|k| k.key() == s).ok_or_else(|| {
+            let keys: Vec<_> = Self::ALL.iter().map(|k| k.key()).collect();
+            AppError::Config(format!("unknown cache `{s}`, expected one of: {}", keys.join(", ")))
+        })?;
+        if kind.available() { Ok(kind) } else { Err(kind.unavailable()) }
+    }
+}
+
+/// 运行 moka 同步缓存：条目数为近似值，改由淘汰监听器计数，待处理任务执行后读取
+#[cfg(feature = "moka")]
+async fn run_moka<B: BackendModel, T: TimeSource>(
+    runner: &CacheRunner<B, T>,
+    builder: MokaSyncBuilder<usize, usize, MokaSyncCache<usize, usize>>,
+    warmup_ops: &[Op],
+    ops: &[Op],
+    observer: Option<impl FnMut(&Op, Outcome, Duration)>,
+) -> Result<RunStats> {
+    let evicted = Arc::new(AtomicU64::new(0));
+    let counter = evicted.clone();
+    let cache = builder
+        .eviction_listener(move |_, _, cause| {
+            if cause.was_evicted() {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .build();
+    let mut adapter = ConcurrentAdapter::new(cache.clone());
+    WarmupManager::warmup_cache(&mut adapter, warmup_ops);
+    cache.run_pending_tasks();
+    let base = evicted.load(Ordering::Relaxed);
+    let mut stats = runner.run(adapter, ops, observer).await?;
+    cache.run_pending_tasks();
+    stats.evictions = evicted.load(Ordering::Relaxed) - base;
+    stats.max_resident = cache.entry_count();
+    Ok(stats)
+}
+
+async fn warm_run<C: CacheOps, B: BackendModel, T: TimeSource>(
+    runner: &CacheRunner<B, T>,
+    mut cache: C,
+    warmup_ops: &[Op],
+    ops: &[Op],
+    observer: Option<impl FnMut(&Op, Outcome, Duration)>,
+) -> Result<RunStats> {
+    WarmupManager::warmup_cache(&mut cache, warmup_ops);
+    runner.run(cache, ops, observer).await
+}
@@ -0,0 +1,55 @@
+//! 回源计数
+//! 按 key 统计回源加载次数，衡量惊群（dogpile）下的重复加载；并提供惊群工作负载
+
+use crate::Op;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// 按 key 计数的回源次数，可在多任务、多线程间共享
+#[derive(Debug, Default)]
+pub struct LoadCounter {
+    loads: Mutex<HashMap<usize, u64>>,
+}
+
+impl LoadCounter {
+    /// 空计数器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次对 `key` 的回源
+    #[inline]
+    pub fn record(&self, key: usize) {
+        *self.loads.lock().entry(key).or_default() += 1;
+    }
+
+    /// `key` 的回源次数
+    pub fn get(&self, key: usize) -> u64 {
+        self.loads.lock().get(&key).copied().unwrap_or(0)
+    }
+
+    /// 回源总次数
+    pub fn total(&self) -> u64 {
+        self.loads.lock().values().sum()
+    }
+
+    /// 回源过的不同 key 数
+    pub fn distinct(&self) -> u64 {
+        self.loads.lock().len() as u64
+    }
+
+    /// 重复回源次数：每个 key 首次之后的回源
+    pub fn redundant(&self) -> u64 {
+        self.total() - self.distinct()
+    }
+
+    /// 清空计数
+    pub fn clear(&self) {
+        self.loads.lock().clear();
+    }
+}
+
+/// 惊群工作负载：`tasks` 份，每份连续读取同一冷 key `reads` 次，各任务同时开始
+pub fn dogpile_workloads(tasks: usize, key: usize, reads: usize) -> Vec<Vec<Op>> {
+    vec![vec![Op::Read(key); reads]; tasks]
+}
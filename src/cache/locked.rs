@@ -0,0 +1,179 @@
+//! 全局锁包装的单线程缓存
+//! 整个缓存由一把锁保护，是把单线程 LRU 用于多线程最直接的做法；
+//! 与分片 LRU、moka 的内部分片在多线程争用组中对比，量化全局锁的代价
+
+#[cfg(feature = "hashlink")]
+use crate::AppError;
+#[cfg(feature = "lru")]
+use crate::lru_cache;
+use crate::{CacheOps, ConcurrentCacheOps, Result};
+#[cfg(feature = "hashlink")]
+use hashlink::LruCache as HashlinkLruCache;
+#[cfg(feature = "lru")]
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+
+/// 保护缓存的锁：`read` 共享访问，`write` 独占访问；互斥锁两者均独占
+pub trait CacheLock: Send + Sync {
+    /// 被保护的缓存
+    type Cache;
+
+    fn read<R>(&self, f: impl FnOnce(&Self::Cache) -> R) -> R;
+
+    fn write<R>(&self, f: impl FnOnce(&mut Self::Cache) -> R) -> R;
+}
+
+impl<C: Send> CacheLock for Mutex<C> {
+    type Cache = C;
+
+    #[inline]
+    fn read<R>(&self, f: impl FnOnce(&C) -> R) -> R {
+        f(&self.lock())
+    }
+
+    #[inline]
+    fn write<R>(&self, f: impl FnOnce(&mut C) -> R) -> R {
+        f(&mut self.lock())
+    }
+}
+
+impl<C: Send + Sync> CacheLock for RwLock<C> {
+    type Cache = C;
+
+    #[inline]
+    fn read<R>(&self, f: impl FnOnce(&C) -> R) -> R {
+        f(&self.read())
+    }
+
+    #[inline]
+    fn write<R>(&self, f: impl FnOnce(&mut C) -> R) -> R {
+        f(&mut self.write())
+    }
+}
+
+/// 不更新访问顺序的只读查询，在共享访问下执行
+pub trait SharedRead {
+    fn contains(&self, key: &usize) -> bool;
+
+    /// 条目数
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "lru")]
+impl<V> SharedRead for LruCache<usize, V> {
+    #[inline]
+    fn contains(&self, key: &usize) -> bool {
+        LruCache::contains(self, key)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        LruCache::len(self)
+    }
+}
+
+#[cfg(feature = "hashlink")]
+impl<V> SharedRead for HashlinkLruCache<usize, V> {
+    #[inline]
+    fn contains(&self, key: &usize) -> bool {
+        self.contains_key(key)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        HashlinkLruCache::len(self)
+    }
+}
+
+/// 一把锁保护的单线程缓存：读取需更新访问顺序，`get` 取独占访问，
+/// 只有不更新顺序的 `peek` 走共享访问，读写锁下可并发执行
+pub struct Locked<L> {
+    lock: L,
+    name: &'static str,
+}
+
+impl<L: CacheLock<Cache: SharedRead>> Locked<L> {
+    /// 条目数
+    pub fn len(&self) -> usize {
+        self.lock.read(|c| c.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock.read(|c| c.is_empty())
+    }
+}
+
+/// 互斥锁保护的 `lru::LruCache`，所有操作串行
+#[cfg(feature = "lru")]
+pub type MutexLru<V> = Locked<Mutex<LruCache<usize, V>>>;
+
+#[cfg(feature = "lru")]
+impl<V> MutexLru<V> {
+    /// 创建指定容量的缓存
+    pub fn new(capacity: usize) -> Result<Self> {
+        Ok(Self {
+            lock: Mutex::new(lru_cache(capacity)?),
+            name: "Mutex<LruCache>",
+        })
+    }
+}
+
+/// 读写锁保护的 `hashlink::LruCache`
+#[cfg(feature = "hashlink")]
+pub type RwLockHashlink<V> = Locked<RwLock<HashlinkLruCache<usize, V>>>;
+
+#[cfg(feature = "hashlink")]
+impl<V> RwLockHashlink<V> {
+    /// 创建指定容量的缓存
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        Ok(Self {
+            lock: RwLock::new(HashlinkLruCache::new(capacity)),
+            name: "RwLock<hashlink::LruCache>",
+        })
+    }
+}
+
+impl<V: Clone + Send, L: CacheLock<Cache: CacheOps<V> + SharedRead>> ConcurrentCacheOps<V> for Locked<L> {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<V> {
+        self.lock.write(|c| c.get(key).cloned())
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        self.lock.read(|c| c.contains(key))
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) {
+        self.lock.write(|c| c.insert(key, value));
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> bool {
+        self.lock.write(|c| c.remove(key))
+    }
+
+    /// 整批只加锁一次
+    fn get_many(&self, keys: &[usize]) -> Vec<bool> {
+        self.lock.write(|c| c.get_many(keys))
+    }
+
+    /// 整批只加锁一次
+    fn insert_many(&self, entries: &[(usize, V)]) {
+        self.lock.write(|c| c.insert_many(entries));
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
@@ -0,0 +1,138 @@
+//! 同步对拍
+//! 在一次遍历中把同一个操作依次交给各个缓存，逐个读请求比较命中与否，
+//! 记录最先出现分歧的操作，用于解释不同淘汰策略的命中率差异。回放方式同 [`crate::replay_sync`]，不模拟回源
+
+use super::micro::step;
+use crate::{
+    AppError, CacheKind, CacheOps, Clock, Gdsf, Key, NoCache, Op, Result, RunStats, S3Fifo, Sieve, WarmupManager, unit_cost,
+};
+#[cfg(feature = "hashlink")]
+use crate::ArcCache;
+#[cfg(any(feature = "lru", feature = "moka"))]
+use crate::ConcurrentAdapter;
+#[cfg(any(feature = "mini-moka", feature = "moka"))]
+use crate::OptimizedMokaCacheBuilder;
+#[cfg(feature = "lru")]
+use crate::ShardedLru;
+#[cfg(feature = "hashlink")]
+use hashlink::LruCache as HashlinkLruCache;
+#[cfg(feature = "lru")]
+use crate::lru_cache;
+#[cfg(feature = "quick_cache")]
+use quick_cache::unsync::Cache as QuickCache;
+#[cfg(feature = "schnellru")]
+use schnellru::{ByLength, LruMap as SchnellLruMap};
+use std::collections::HashMap;
+
+/// 各缓存对同一读请求给出不同结果的位置
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Divergence {
+    /// 操作下标（不含预热）
+    pub op: usize,
+    /// 读取的 key id
+    pub key: usize,
+    /// 各缓存是否命中，顺序同 [`Lockstep::caches`]
+    pub hits: Vec<bool>,
+}
+
+/// 同步对拍结果
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Lockstep {
+    /// 参与对拍的缓存
+    pub caches: Vec<CacheKind>,
+    /// 各缓存的命中统计
+    pub stats: Vec<RunStats>,
+    /// 出现分歧的读请求数
+    pub divergent: u64,
+    /// 最先出现的若干分歧
+    pub first: Vec<Divergence>,
+}
+
+impl Lockstep {
+    /// 以相同容量新建各缓存，分别预热后同步回放 `ops`，保留最先出现的 `limit` 个分歧
+    pub fn run(caches: &[CacheKind], capacity: u64, warmup_ops: &[Op], ops: &[Op], limit: usize) -> Result<Self> {
+        if caches.len() < 2 {
+            return Err(AppError::Config("lockstep needs at least two caches".into()));
+        }
+        let mut built = caches
+            .iter()
+            .map(|kind| kind.build(capacity))
+            .collect::<Result<Vec<_>>>()?;
+        for cache in &mut built {
+            WarmupManager::warmup_cache(&mut cache.as_mut(), warmup_ops);
+        }
+        let mut stats = vec![RunStats::default(); caches.len()];
+        let mut reads: Vec<Vec<bool>> = vec![Vec::new(); caches.len()];
+        let (mut divergent, mut first) = (0, Vec::new());
+        for (i, op) in ops.iter().enumerate() {
+            for ((cache, stats), reads) in built.iter_mut().zip(&mut stats).zip(&mut reads) {
+                reads.clear();
+                step(cache.as_mut(), op, stats, |hit| reads.push(hit));
+            }
+            for (j, hit) in reads[0].iter().enumerate() {
+                if reads[1..].iter().all(|r| r.get(j) == Some(hit)) {
+                    continue;
+                }
+                divergent += 1;
+                if first.len() < limit {
+                    first.push(Divergence {
+                        op: i,
+                        // 范围扫描的第 j 个读请求读取起始 key 之后第 j 个 key
+                        key: op.keys().nth(j).map_or(op.key().id(), |k| k.id()),
+                        hits: reads.iter().map(|r| r.get(j).copied().unwrap_or(false)).collect(),
+                    });
+                }
+            }
+        }
+        for s in &mut stats {
+            s.ops = ops.len() as u64;
+        }
+        Ok(Self {
+            caches: caches.to_vec(),
+            stats,
+            divergent,
+            first,
+        })
+    }
+}
+
+impl CacheKind {
+    /// 以指定容量新建缓存，供对拍、收敛检测等逐个操作驱动的场景；分片 LRU 以固定种子构建，分片方式可复现
+    pub fn build(self, capacity: u64) -> Result<Box<dyn CacheOps>> {
+        let cap = capacity as usize;
+        Ok(match self {
+            #[cfg(feature = "hashlink")]
+            Self::Hashlink => Box::new(HashlinkLruCache::new(cap)),
+            #[cfg(feature = "lru")]
+            Self::Lru => Box::new(lru_cache(cap)?),
+            #[cfg(feature = "mini-moka")]
+            Self::MiniMoka => Box::new(OptimizedMokaCacheBuilder::build_high_performance_cache(capacity)),
+            #[cfg(feature = "moka")]
+            Self::MokaSync | Self::MokaSyncLru => Box::new(ConcurrentAdapter::new(
+                OptimizedMokaCacheBuilder::sync_cache_builder(capacity, self == Self::MokaSync).build(),
+            )),
+            #[cfg(feature = "quick_cache")]
+            Self::QuickCache => Box::new(QuickCache::new(cap)),
+            #[cfg(feature = "schnellru")]
+            Self::Schnellru => {
+                let cap = u32::try_from(capacity)
+                    .map_err(|_| AppError::Config(format!("capacity {capacity} exceeds u32")))?;
+                Box::new(SchnellLruMap::new(ByLength::new(cap)))
+            }
+            #[cfg(feature = "lru")]
+            Self::ShardedLru => Box::new(ConcurrentAdapter::new(ShardedLru::seeded(cap, 0)?)),
+            Self::S3Fifo => Box::new(S3Fifo::new(cap)?),
+            Self::Sieve => Box::new(Sieve::new(cap)?),
+            Self::Clock => Box::new(Clock::new(cap)?),
+            #[cfg(feature = "hashlink")]
+            Self::Arc => Box::new(ArcCache::new(cap)?),
+            Self::Gdsf => Box::new(Gdsf::new(capacity, unit_cost)?),
+            Self::Unbounded => Box::new(HashMap::new()),
+            Self::NoCache => Box::new(NoCache),
+            #[allow(unreachable_patterns)]
+            _ => return Err(self.unavailable()),
+        })
+    }
+}
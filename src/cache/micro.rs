@@ -0,0 +1,80 @@
+//! 微基准
+//! 同步回放操作序列，不经异步运行时、不模拟回源：未命中直接写入 [`Value::load`] 的值，
+//! 只测量缓存自身每个操作的耗时，与端到端的 [`crate::CacheRunner`] 场景互为补充
+
+use crate::{CacheOps, Key, Op, RunStats, Value};
+use std::time::Instant;
+
+/// 同步回放 `ops`，返回命中统计，[`RunStats::cache_time`] 与 [`RunStats::elapsed`] 均为整个回放的耗时
+///
+/// 不存在的 key 未命中时不写入（等同关闭负缓存），不统计淘汰
+pub fn replay_sync<V: Value, K: Key, C: CacheOps<V, K>>(cache: &mut C, ops: &[Op<V, K>]) -> RunStats {
+    let mut stats = RunStats::default();
+    let start = Instant::now();
+    for op in ops {
+        step(cache, op, &mut stats, |_| ());
+    }
+    stats.ops = ops.len() as u64;
+    stats.cache_time = start.elapsed();
+    stats.elapsed = stats.cache_time;
+    stats
+}
+
+/// 同步执行单个操作并累加统计，每个读请求（范围扫描按 key 顺序）的命中与否依次交给 `on_read`
+pub(crate) fn step<V: Value, K: Key, C: CacheOps<V, K> + ?Sized>(
+    cache: &mut C,
+    op: &Op<V, K>,
+    stats: &mut RunStats,
+    mut on_read: impl FnMut(bool),
+) {
+    match op {
+        Op::Read(key) | Op::Peek(key) => {
+            let hit = match op {
+                Op::Peek(_) => cache.peek(key),
+                _ => cache.get(key).is_some(),
+            };
+            on_read(hit);
+            if hit {
+                stats.hits += 1;
+            } else {
+                stats.misses += 1;
+                cache.insert(key.clone(), V::load(key.id()));
+            }
+        }
+        Op::ReadMissing(key) => {
+            let hit = cache.get(key).is_some();
+            on_read(hit);
+            if hit {
+                stats.hits += 1;
+                stats.negative_hits += 1;
+            } else {
+                stats.misses += 1;
+                stats.negative_misses += 1;
+            }
+        }
+        Op::Write(key, val) => {
+            cache.insert(key.clone(), val.clone());
+            stats.updates += 1;
+        }
+        Op::Insert(key, val) => {
+            cache.insert(key.clone(), val.clone());
+            stats.inserts += 1;
+        }
+        Op::Delete(key) => {
+            cache.remove(key);
+            stats.deletes += 1;
+        }
+        Op::Scan(..) => {
+            let keys: Vec<K> = op.keys().collect();
+            for (key, hit) in keys.iter().zip(cache.get_many(&keys)) {
+                on_read(hit);
+                if hit {
+                    stats.hits += 1;
+                } else {
+                    stats.misses += 1;
+                    cache.insert(key.clone(), V::load(key.id()));
+                }
+            }
+        }
+    }
+}
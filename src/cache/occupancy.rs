@@ -0,0 +1,51 @@
+//! 占用率采样
+//! 运行中每隔若干操作读取一次常驻条目数，平均值与最大值揭示因权重配置或惰性淘汰而填不满容量的缓存
+
+use serde::Serialize;
+use std::ops::AddAssign;
+
+/// 常驻条目数的采样统计
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Occupancy {
+    /// 采样次数
+    pub samples: u64,
+    /// 各次采样的条目数之和
+    pub sum: u64,
+    /// 采样到的最大条目数
+    pub max: u64,
+    /// 缓存报告的容量上限（条目数），见 [`crate::CacheOps::capacity_hint`]
+    pub capacity: Option<u64>,
+}
+
+impl Occupancy {
+    /// 记录一次采样
+    #[inline]
+    pub fn record(&mut self, entries: usize) {
+        self.samples += 1;
+        self.sum += entries as u64;
+        self.max = self.max.max(entries as u64);
+    }
+
+    /// 平均条目数，未采样时为 None
+    #[inline]
+    pub fn mean(&self) -> Option<f64> {
+        (self.samples > 0).then(|| self.sum as f64 / self.samples as f64)
+    }
+
+    /// 条目数占容量的百分比，容量未知或为 0 时为 None
+    #[inline]
+    pub fn percent(&self, entries: f64) -> Option<f64> {
+        self.capacity.filter(|&c| c > 0).map(|c| entries * 100.0 / c as f64)
+    }
+}
+
+/// 合并并发运行的采样，容量取首个已知值
+impl AddAssign for Occupancy {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.samples += rhs.samples;
+        self.sum += rhs.sum;
+        self.max = self.max.max(rhs.max);
+        self.capacity = self.capacity.or(rhs.capacity);
+    }
+}
@@ -0,0 +1,143 @@
+//! 运行进度
+//! 操作数达到百万级时一次运行可达数分钟，每隔若干操作回调一次已完成操作数、当前命中率与预计剩余时间
+
+use crate::RunStats;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 一次进度报告
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Progress {
+    /// 已完成的操作数
+    pub done: usize,
+    /// 总操作数，从迭代器回放且长度未知时为 None
+    pub total: Option<usize>,
+    pub hits: u64,
+    pub misses: u64,
+    /// 自开始运行起的真实耗时，模拟时间模式下同样按真实时间计
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// 以运行中的统计生成进度
+    pub fn new(stats: &RunStats, total: Option<usize>, started: Instant) -> Self {
+        Self {
+            done: stats.ops as usize,
+            total,
+            hits: stats.hits,
+            misses: stats.misses,
+            elapsed: started.elapsed(),
+        }
+    }
+
+    /// 当前命中率（百分比）
+    #[inline]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64 * 100.0
+        }
+    }
+
+    /// 按已完成部分的平均速度估计的剩余时间，总数未知或尚未完成任何操作时为 None
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        (self.done > 0).then(|| self.elapsed.mul_f64(total.saturating_sub(self.done) as f64 / self.done as f64))
+    }
+
+    /// 是否已完成全部操作
+    #[inline]
+    pub fn finished(&self) -> bool {
+        self.total.is_some_and(|t| self.done >= t)
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total.filter(|&t| t > 0) {
+            Some(total) => write!(
+                f,
+                "{}/{total} ops ({:.1}%)",
+                self.done,
+                self.done as f64 * 100.0 / total as f64
+            )?,
+            None => write!(f, "{} ops", self.done)?,
+        }
+        write!(f, "  hit rate {:.2}%", self.hit_rate())?;
+        if let Some(eta) = self.eta().filter(|_| !self.finished()) {
+            write!(f, "  eta {eta:.0?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 进度回调，运行中每 `every` 个操作调用一次，结束时再调用一次
+#[derive(Clone)]
+pub struct ProgressHook {
+    every: usize,
+    f: Arc<dyn Fn(&Progress) + Send + Sync>,
+}
+
+impl ProgressHook {
+    /// 每 `every` 个操作以当前进度调用 `f`
+    pub fn new(every: usize, f: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        Self {
+            every: every.max(1),
+            f: Arc::new(f),
+        }
+    }
+
+    /// 输出到标准错误：终端中在同一行刷新、完成时换行，重定向到文件时每次一行
+    pub fn stderr(every: usize) -> Self {
+        let tty = io::stderr().is_terminal();
+        Self::new(every, move |p| {
+            let mut err = io::stderr().lock();
+            // 进度输出失败不影响运行
+            let _ = match (tty, p.finished()) {
+                (true, false) => write!(err, "\r{p}\x1b[K"),
+                (true, true) => writeln!(err, "\r{p}\x1b[K"),
+                (false, _) => writeln!(err, "{p}"),
+            };
+        })
+    }
+
+    /// 回调间隔（操作数）
+    #[inline]
+    pub fn every(&self) -> usize {
+        self.every
+    }
+
+    /// 把一次运行的进度折算为整体进度：此前已完成 `done` 个操作，共 `total` 个，耗时自 `started` 起算；
+    /// 命中率仍为本次运行的命中率
+    pub fn within(&self, done: usize, total: usize, started: Instant) -> Self {
+        let f = self.f.clone();
+        Self {
+            every: self.every,
+            f: Arc::new(move |p: &Progress| {
+                f(&Progress {
+                    done: done + p.done,
+                    total: Some(total),
+                    elapsed: started.elapsed(),
+                    ..*p
+                })
+            }),
+        }
+    }
+
+    /// 报告一次进度
+    #[inline]
+    pub fn report(&self, progress: &Progress) {
+        (self.f)(progress);
+    }
+}
+
+impl fmt::Debug for ProgressHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressHook").field("every", &self.every).finish_non_exhaustive()
+    }
+}
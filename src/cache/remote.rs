@@ -0,0 +1,303 @@
+//! 外部缓存进程
+//! 经 Compio TCP 连接 Redis（RESP2）或 Memcached（文本协议），实现 [`AsyncCacheOps`]，
+//! 与进程内缓存运行同一工作负载；容量与淘汰策略由服务端决定（如 Redis 的 `maxmemory` 与 `maxmemory-policy`）
+//!
+//! 每个实例使用独立的 key 前缀，新建即为空缓存；旧实例写入的 key 留在服务端，建议使用专用实例
+
+use crate::{AppError, AsyncCacheOps, RemoteConfig, RemoteProtocol, Result, Value};
+use compio::buf::BufResult;
+use compio::io::{AsyncRead, AsyncWriteExt};
+use compio::net::TcpStream;
+use futures_util::lock::Mutex;
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::marker::PhantomData;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 每次读取至少预留的缓冲区大小
+const READ_CHUNK: usize = 16 * 1024;
+
+/// 进程内的实例编号，与进程号一起构成 key 前缀
+static INSTANCE: AtomicU64 = AtomicU64::new(0);
+
+/// 远程缓存，读取的值按返回的长度经 [`Value::sized`] 还原
+///
+/// 请求失败不会中断运行：读取按未命中处理并计数，运行结束后由 [`RemoteCache::check`] 报告
+pub struct RemoteCache<V> {
+    cfg: RemoteConfig,
+    conns: Vec<Mutex<Conn>>,
+    /// 下一个使用的连接
+    next: Cell<usize>,
+    prefix: String,
+    errors: Cell<u64>,
+    last_error: RefCell<Option<String>>,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<V> RemoteCache<V> {
+    /// 新建，连接在首次请求时建立
+    pub fn new(cfg: RemoteConfig) -> Result<Self> {
+        if cfg.connections == 0 {
+            return Err(AppError::Config("remote.connections must be > 0".into()));
+        }
+        let conns = (0..cfg.connections).map(|_| Mutex::new(Conn::default())).collect();
+        let instance = INSTANCE.fetch_add(1, Ordering::Relaxed);
+        Ok(Self {
+            cfg,
+            conns,
+            next: Cell::new(0),
+            prefix: format!("lru-bench:{}:{instance}:", process::id()),
+            errors: Cell::new(0),
+            last_error: RefCell::new(None),
+            _value: PhantomData,
+        })
+    }
+
+    /// 新建并立即探测服务端（Redis `PING`，Memcached `version`），连接失败时返回错误
+    pub async fn connect(cfg: RemoteConfig) -> Result<Self> {
+        let cache = Self::new(cfg)?;
+        let req = match cache.cfg.protocol {
+            RemoteProtocol::Redis => resp(&[b"PING"], None),
+            RemoteProtocol::Memcached => b"version\r\n".to_vec(),
+        };
+        let mut conn = cache.conns[0].lock().await;
+        conn.request(&cache.cfg.addr, req).await.map_err(|e| {
+            AppError::CacheOperation(format!("{}: {e}", cache.cfg))
+        })?;
+        drop(conn);
+        Ok(cache)
+    }
+
+    /// 失败的请求数
+    #[inline]
+    pub fn errors(&self) -> u64 {
+        self.errors.get()
+    }
+
+    /// 存在失败的请求时返回错误，附最后一次失败的原因
+    pub fn check(&self) -> Result<()> {
+        match (self.errors.get(), self.last_error.borrow().as_deref()) {
+            (0, _) => Ok(()),
+            (n, last) => Err(AppError::CacheOperation(format!(
+                "{}: {n} request(s) failed, last: {}",
+                self.cfg,
+                last.unwrap_or_default()
+            ))),
+        }
+    }
+
+    fn key(&self, key: usize) -> String {
+        format!("{}{key}", self.prefix)
+    }
+
+    /// 轮流选择连接发送请求，失败时记录错误并返回 None
+    async fn request(&self, req: Vec<u8>) -> Option<Reply> {
+        let i = self.next.get();
+        self.next.set((i + 1) % self.conns.len());
+        let mut conn = self.conns[i].lock().await;
+        match conn.request(&self.cfg.addr, req).await {
+            Ok(reply) => Some(reply),
+            Err(e) => {
+                self.errors.set(self.errors.get() + 1);
+                *self.last_error.borrow_mut() = Some(e.to_string());
+                None
+            }
+        }
+    }
+}
+
+impl<V: Value> AsyncCacheOps<V> for RemoteCache<V> {
+    async fn get(&self, key: &usize) -> Option<V> {
+        let k = self.key(*key);
+        let req = match self.cfg.protocol {
+            RemoteProtocol::Redis => resp(&[b"GET", k.as_bytes()], None),
+            RemoteProtocol::Memcached => format!("get {k}\r\n").into_bytes(),
+        };
+        match self.request(req).await? {
+            Reply::Bulk(len) => Some(V::sized(*key, len)),
+            _ => None,
+        }
+    }
+
+    async fn peek(&self, key: &usize) -> bool {
+        match self.cfg.protocol {
+            RemoteProtocol::Redis => {
+                let req = resp(&[b"EXISTS", self.key(*key).as_bytes()], None);
+                matches!(self.request(req).await, Some(Reply::Int(n)) if n > 0)
+            }
+            // 文本协议没有不更新访问顺序的查询
+            RemoteProtocol::Memcached => self.get(key).await.is_some(),
+        }
+    }
+
+    async fn insert(&self, key: usize, value: V) {
+        let k = self.key(key);
+        let len = value.payload_len();
+        let req = match self.cfg.protocol {
+            RemoteProtocol::Redis => resp(&[b"SET", k.as_bytes()], Some(len)),
+            RemoteProtocol::Memcached => {
+                let mut req = format!("set {k} 0 0 {len}\r\n").into_bytes();
+                req.resize(req.len() + len, 0);
+                req.extend_from_slice(b"\r\n");
+                req
+            }
+        };
+        self.request(req).await;
+    }
+
+    async fn remove(&self, key: &usize) -> bool {
+        let k = self.key(*key);
+        let req = match self.cfg.protocol {
+            RemoteProtocol::Redis => resp(&[b"DEL", k.as_bytes()], None),
+            RemoteProtocol::Memcached => format!("delete {k}\r\n").into_bytes(),
+        };
+        matches!(self.request(req).await, Some(Reply::Int(n)) if n > 0)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        match self.cfg.protocol {
+            RemoteProtocol::Redis => "Redis",
+            RemoteProtocol::Memcached => "Memcached",
+        }
+    }
+}
+
+/// 服务端响应，只保留运行器关心的部分
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reply {
+    /// 成功的状态行
+    Ok,
+    /// key 不存在
+    Nil,
+    /// 整数（Redis 的 `:n`，Memcached 的 DELETED / NOT_FOUND 记为 1 / 0）
+    Int(i64),
+    /// 取到的值及其长度，内容已丢弃
+    Bulk(usize),
+}
+
+/// 单条连接，出错后断开，下一次请求时重连
+#[derive(Default)]
+struct Conn {
+    stream: Option<TcpStream>,
+    /// 已读取未消费的响应字节
+    buf: Vec<u8>,
+}
+
+impl Conn {
+    /// 发送请求并读取一个响应，失败时断开连接
+    async fn request(&mut self, addr: &str, req: Vec<u8>) -> io::Result<Reply> {
+        let reply = self.round_trip(addr, req).await;
+        if reply.is_err() {
+            self.stream = None;
+            self.buf.clear();
+        }
+        reply
+    }
+
+    async fn round_trip(&mut self, addr: &str, req: Vec<u8>) -> io::Result<Reply> {
+        if self.stream.is_none() {
+            let stream = TcpStream::connect(addr).await?;
+            stream.set_nodelay(true)?;
+            self.stream = Some(stream);
+        }
+        let stream = self.stream.as_mut().ok_or(io::ErrorKind::NotConnected)?;
+        let BufResult(res, _) = stream.write_all(req).await;
+        res?;
+        self.reply().await
+    }
+
+    /// 解析一个响应，RESP2 与 Memcached 文本协议的首字符互不冲突
+    async fn reply(&mut self) -> io::Result<Reply> {
+        let line = self.line().await?;
+        let int = |s: &str| s.trim().parse::<i64>().map_err(|_| protocol_error(&line));
+        match line.as_bytes().first() {
+            Some(b'+') => return Ok(Reply::Ok),
+            Some(b'-') => return Err(io::Error::other(line[1..].to_owned())),
+            Some(b':') => return Ok(Reply::Int(int(&line[1..])?)),
+            Some(b'_') => return Ok(Reply::Nil),
+            Some(b'$') => {
+                let len = int(&line[1..])?;
+                let Ok(len) = usize::try_from(len) else {
+                    return Ok(Reply::Nil);
+                };
+                self.skip(len).await?;
+                return Ok(Reply::Bulk(len));
+            }
+            _ => {}
+        }
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["STORED"] => Ok(Reply::Ok),
+            ["END"] => Ok(Reply::Nil),
+            ["DELETED"] => Ok(Reply::Int(1)),
+            ["NOT_FOUND"] => Ok(Reply::Int(0)),
+            ["VERSION", ..] => Ok(Reply::Ok),
+            ["VALUE", _, _, len, ..] => {
+                let len = usize::try_from(int(len)?).map_err(|_| protocol_error(&line))?;
+                self.skip(len).await?;
+                match self.line().await?.as_str() {
+                    "END" => Ok(Reply::Bulk(len)),
+                    other => Err(protocol_error(other)),
+                }
+            }
+            ["ERROR" | "CLIENT_ERROR" | "SERVER_ERROR", ..] => Err(io::Error::other(line.clone())),
+            _ => Err(protocol_error(&line)),
+        }
+    }
+
+    /// 读取一行，不含行尾的 `\r\n`
+    async fn line(&mut self) -> io::Result<String> {
+        loop {
+            if let Some(end) = self.buf.windows(2).position(|w| w == b"\r\n") {
+                let line = String::from_utf8_lossy(&self.buf[..end]).into_owned();
+                self.buf.drain(..end + 2);
+                return Ok(line);
+            }
+            self.fill().await?;
+        }
+    }
+
+    /// 丢弃 `len` 字节的值及其后的 `\r\n`
+    async fn skip(&mut self, len: usize) -> io::Result<()> {
+        while self.buf.len() < len + 2 {
+            self.fill().await?;
+        }
+        self.buf.drain(..len + 2);
+        Ok(())
+    }
+
+    /// 从连接追加读取到缓冲区
+    async fn fill(&mut self) -> io::Result<()> {
+        let stream = self.stream.as_mut().ok_or(io::ErrorKind::NotConnected)?;
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.reserve(READ_CHUNK);
+        let BufResult(res, buf) = stream.read(buf).await;
+        self.buf = buf;
+        if res? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+}
+
+/// 按 RESP2 编码命令，`payload` 为追加的全零值参数的长度
+fn resp(args: &[&[u8]], payload: Option<usize>) -> Vec<u8> {
+    let n = args.len() + usize::from(payload.is_some());
+    let mut req = format!("*{n}\r\n").into_bytes();
+    for arg in args {
+        req.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        req.extend_from_slice(arg);
+        req.extend_from_slice(b"\r\n");
+    }
+    if let Some(len) = payload {
+        req.extend_from_slice(format!("${len}\r\n").as_bytes());
+        req.resize(req.len() + len, 0);
+        req.extend_from_slice(b"\r\n");
+    }
+    req
+}
+
+fn protocol_error(line: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("unexpected reply `{line}`"))
+}
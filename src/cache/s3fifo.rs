@@ -0,0 +1,214 @@
+//! S3-FIFO 淘汰算法
+//! 小队列 S（约 10% 容量）接纳新条目，主队列 M 保存被再次访问过的条目，
+//! 幽灵队列 G 只记录从 S 淘汰的 key，命中幽灵的 key 直接进入 M。
+//! 队列记录带写入序号，删除只移除索引，出队时跳过序号不符的失效记录
+
+use crate::{AppError, CacheOps, Result};
+use std::collections::{HashMap, VecDeque};
+use std::mem::take;
+
+/// 访问频次上限
+const MAX_FREQ: u8 = 3;
+
+/// 小队列占总容量的比例（分母）
+const SMALL_RATIO: usize = 10;
+
+/// 失效记录超过容量的倍数时压缩队列（分子）
+const COMPACT_RATIO: usize = 2;
+
+struct Entry<V> {
+    value: V,
+    freq: u8,
+    /// 写入序号，与队列记录相同时记录有效
+    seq: u64,
+    /// 是否位于主队列
+    main: bool,
+}
+
+/// S3-FIFO 缓存
+pub struct S3Fifo<V> {
+    map: HashMap<usize, Entry<V>>,
+    /// 队列记录 key 与写入序号，序号用于识别已失效的记录
+    small: VecDeque<(usize, u64)>,
+    main: VecDeque<(usize, u64)>,
+    /// 小队列中的有效条目数
+    small_len: usize,
+    /// 幽灵 key 到写入序号
+    ghost: HashMap<usize, u64>,
+    ghost_queue: VecDeque<(usize, u64)>,
+    seq: u64,
+    capacity: usize,
+    small_cap: usize,
+    /// 幽灵队列长度，与主队列容量相同
+    ghost_cap: usize,
+}
+
+impl<V> S3Fifo<V> {
+    /// 创建指定容量的缓存
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        let small_cap = (capacity / SMALL_RATIO).max(1);
+        Ok(Self {
+            map: HashMap::with_capacity(capacity),
+            small: VecDeque::new(),
+            main: VecDeque::new(),
+            small_len: 0,
+            ghost: HashMap::new(),
+            ghost_queue: VecDeque::new(),
+            seq: 0,
+            capacity,
+            small_cap,
+            ghost_cap: (capacity - small_cap).max(1),
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 队列记录是否仍指向常驻条目
+    #[inline]
+    fn live(&self, (key, seq): (usize, u64)) -> bool {
+        self.map.get(&key).is_some_and(|e| e.seq == seq)
+    }
+
+    /// 淘汰一个条目并返回其 key，缓存为空时返回 None
+    fn evict(&mut self) -> Option<usize> {
+        loop {
+            if self.small_len >= self.small_cap || self.small_len == self.map.len() {
+                let (key, seq) = self.small.pop_front()?;
+                let Some(e) = self.map.get_mut(&key).filter(|e| e.seq == seq) else {
+                    continue;
+                };
+                self.small_len -= 1;
+                if e.freq > 1 {
+                    e.freq = 0;
+                    e.main = true;
+                    self.main.push_back((key, seq));
+                } else {
+                    self.map.remove(&key);
+                    self.remember(key);
+                    return Some(key);
+                }
+            } else {
+                let (key, seq) = self.main.pop_front()?;
+                let Some(e) = self.map.get_mut(&key).filter(|e| e.seq == seq) else {
+                    continue;
+                };
+                if e.freq > 0 {
+                    e.freq -= 1;
+                    self.main.push_back((key, seq));
+                } else {
+                    self.map.remove(&key);
+                    return Some(key);
+                }
+            }
+        }
+    }
+
+    /// 失效记录过多时（只删除不淘汰会持续累积）丢弃队列中的失效记录，均摊 O(1)
+    fn compact(&mut self) {
+        if self.small.len() + self.main.len() <= COMPACT_RATIO * self.capacity.max(self.map.len()) {
+            return;
+        }
+        let (mut small, mut main) = (take(&mut self.small), take(&mut self.main));
+        small.retain(|&r| self.live(r));
+        main.retain(|&r| self.live(r));
+        (self.small, self.main) = (small, main);
+    }
+
+    /// 记入幽灵队列
+    fn remember(&mut self, key: usize) {
+        self.seq += 1;
+        self.ghost.insert(key, self.seq);
+        self.ghost_queue.push_back((key, self.seq));
+        while self.ghost_queue.len() > self.ghost_cap {
+            let Some((k, seq)) = self.ghost_queue.pop_front() else {
+                break;
+            };
+            if self.ghost.get(&k) == Some(&seq) {
+                self.ghost.remove(&k);
+            }
+        }
+    }
+}
+
+impl<V> CacheOps<V> for S3Fifo<V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        let e = self.map.get_mut(key)?;
+        e.freq = (e.freq + 1).min(MAX_FREQ);
+        Some(&e.value)
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        if let Some(e) = self.map.get_mut(&key) {
+            e.value = value;
+            e.freq = (e.freq + 1).min(MAX_FREQ);
+            return;
+        }
+        if self.map.len() >= self.capacity {
+            self.evict();
+        }
+        self.seq += 1;
+        let main = self.ghost.remove(&key).is_some();
+        if main {
+            self.main.push_back((key, self.seq));
+        } else {
+            self.small.push_back((key, self.seq));
+            self.small_len += 1;
+        }
+        self.map.insert(key, Entry { value, freq: 0, seq: self.seq, main });
+        self.compact();
+    }
+
+    /// 只移除索引，队列中的记录出队时跳过；删除不进入幽灵队列
+    fn remove(&mut self, key: &usize) -> bool {
+        let Some(e) = self.map.remove(key) else {
+            return false;
+        };
+        if !e.main {
+            self.small_len -= 1;
+        }
+        true
+    }
+
+    #[inline]
+    fn evict_one(&mut self) -> Option<usize> {
+        self.evict()
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(S3Fifo::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(self.capacity)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "S3-FIFO"
+    }
+}
@@ -0,0 +1,199 @@
+//! 分片 LRU
+//! N 个独立的 `lru::LruCache` 分片，各自由互斥锁保护，按 key 的哈希选择分片
+
+use crate::{AppError, ConcurrentCacheOps, Result};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::hash::{BuildHasher, DefaultHasher, Hash, Hasher, RandomState};
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// 每个 CPU 核心对应的默认分片数
+const SHARDS_PER_CORE: usize = 4;
+
+/// 指定种子时的分片数，与核数无关
+pub const SEEDED_SHARDS: usize = 16;
+
+/// 先写入种子的 SipHash：相同种子在各次运行中把 key 分到相同分片
+#[derive(Clone, Copy, Debug)]
+struct SeededState(u64);
+
+impl BuildHasher for SeededState {
+    type Hasher = DefaultHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> DefaultHasher {
+        let mut h = DefaultHasher::new();
+        h.write_u64(self.0);
+        h
+    }
+}
+
+/// 朴素分片 LRU：各分片独立淘汰，整体只是近似 LRU
+pub struct ShardedLru<K, V> {
+    shards: Box<[Mutex<LruCache<K, V>>]>,
+    hasher: SeededState,
+    /// 分片数减一，分片数为 2 的幂
+    mask: usize,
+}
+
+impl<K: Hash + Eq, V> ShardedLru<K, V> {
+    /// 按 CPU 核心数选择分片数
+    pub fn new(capacity: usize) -> Result<Self> {
+        let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self::with_shards(capacity, cores * SHARDS_PER_CORE)
+    }
+
+    /// 以 [`SEEDED_SHARDS`] 个分片与 `seed` 派生的哈希构建，分片方式在各次运行中一致
+    pub fn seeded(capacity: usize, seed: u64) -> Result<Self> {
+        Self::build(capacity, SEEDED_SHARDS, seed)
+    }
+
+    /// 指定分片数（不超过容量，向下取整为 2 的幂），总容量拆分到各分片，各分片容量之和恰为 `capacity`；
+    /// 哈希种子每次随机
+    pub fn with_shards(capacity: usize, shards: usize) -> Result<Self> {
+        Self::build(capacity, shards, RandomState::new().hash_one(0u8))
+    }
+
+    fn build(capacity: usize, shards: usize, seed: u64) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AppError::Config("capacity must be > 0".into()));
+        }
+        let m = shards.clamp(1, capacity);
+        let n = 1 << (usize::BITS - 1 - m.leading_zeros());
+        // 余数分给前几个分片，各分片至少 1
+        let per = |i: usize| NonZeroUsize::new(capacity / n + usize::from(i < capacity % n)).unwrap_or(NonZeroUsize::MIN);
+        Ok(Self {
+            shards: (0..n).map(|i| Mutex::new(LruCache::new(per(i)))).collect(),
+            hasher: SeededState(seed),
+            mask: n - 1,
+        })
+    }
+
+    /// 分片数
+    #[inline]
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    #[inline]
+    fn shard_index(&self, key: &K) -> usize {
+        // 截断到 usize 后取低位，as 转换安全
+        self.hasher.hash_one(key) as usize & self.mask
+    }
+
+    #[inline]
+    fn shard(&self, key: &K) -> &Mutex<LruCache<K, V>> {
+        &self.shards[self.shard_index(key)]
+    }
+
+    /// 按分片对下标分组排序，同一分片的下标连续且保持原有顺序
+    fn by_shard<'a>(&self, keys: impl Iterator<Item = &'a K>) -> Vec<(usize, usize)>
+    where
+        K: 'a,
+    {
+        let mut order: Vec<(usize, usize)> = keys.enumerate().map(|(i, k)| (self.shard_index(k), i)).collect();
+        order.sort_unstable();
+        order
+    }
+
+    /// 获取值的副本并更新访问顺序
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.shard(key).lock().get(key).cloned()
+    }
+
+    /// 是否存在，不更新访问顺序
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.shard(key).lock().contains(key)
+    }
+
+    /// 插入键值对，分片满时淘汰该分片最久未使用的条目
+    #[inline]
+    pub fn put(&self, key: K, value: V) {
+        self.shard(&key).lock().put(key, value);
+    }
+
+    /// 移除 key，返回其值
+    #[inline]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.shard(key).lock().pop(key)
+    }
+
+    /// 批量查询各 key 是否命中，每个涉及的分片只加锁一次
+    pub fn get_many(&self, keys: &[K]) -> Vec<bool> {
+        let mut hits = vec![false; keys.len()];
+        for run in self.by_shard(keys.iter()).chunk_by(|a, b| a.0 == b.0) {
+            let mut shard = self.shards[run[0].0].lock();
+            for &(_, i) in run {
+                hits[i] = shard.get(&keys[i]).is_some();
+            }
+        }
+        hits
+    }
+
+    /// 批量插入，每个涉及的分片只加锁一次；同一分片内按原有顺序插入
+    pub fn put_many(&self, entries: &[(K, V)])
+    where
+        K: Clone,
+        V: Clone,
+    {
+        for run in self.by_shard(entries.iter().map(|(k, _)| k)).chunk_by(|a, b| a.0 == b.0) {
+            let mut shard = self.shards[run[0].0].lock();
+            for &(_, i) in run {
+                let (key, value) = &entries[i];
+                shard.put(key.clone(), value.clone());
+            }
+        }
+    }
+
+    /// 条目总数
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.lock().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|s| s.lock().is_empty())
+    }
+}
+
+impl<V: Clone + Send> ConcurrentCacheOps<V> for ShardedLru<usize, V> {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<V> {
+        ShardedLru::get(self, key)
+    }
+
+    #[inline]
+    fn peek(&self, key: &usize) -> bool {
+        self.contains(key)
+    }
+
+    #[inline]
+    fn insert(&self, key: usize, value: V) {
+        self.put(key, value);
+    }
+
+    #[inline]
+    fn remove(&self, key: &usize) -> bool {
+        ShardedLru::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn get_many(&self, keys: &[usize]) -> Vec<bool> {
+        ShardedLru::get_many(self, keys)
+    }
+
+    #[inline]
+    fn insert_many(&self, entries: &[(usize, V)]) {
+        self.put_many(entries);
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Sharded LRU"
+    }
+}
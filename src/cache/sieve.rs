@@ -0,0 +1,194 @@
+//! SIEVE 淘汰算法
+//! 新条目插入队头，命中只置访问位（惰性提升）；淘汰时指针从队尾向队头移动，
+//! 清除沿途的访问位，淘汰第一个未访问的条目
+
+use crate::{AppError, CacheOps, Result};
+use std::collections::HashMap;
+
+/// 空下标
+const NIL: u32 = u32::MAX;
+
+/// SIEVE 缓存，节点存放在预分配的数组中，用下标代替指针
+pub struct Sieve<V> {
+    map: HashMap<usize, u32>,
+    keys: Vec<usize>,
+    vals: Vec<V>,
+    visited: Vec<bool>,
+    /// 指向更靠近队头的节点
+    prev: Vec<u32>,
+    /// 指向更靠近队尾的节点
+    next: Vec<u32>,
+    head: u32,
+    tail: u32,
+    hand: u32,
+    /// 删除后空出的槽位，经 `next` 串成链表
+    free: u32,
+    capacity: usize,
+}
+
+impl<V> Sieve<V> {
+    /// 创建指定容量的缓存，容量需可用 u32 下标表示
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 || capacity >= NIL as usize {
+            return Err(AppError::Config(format!("sieve capacity {capacity} out of range")));
+        }
+        Ok(Self {
+            map: HashMap::with_capacity(capacity),
+            keys: Vec::with_capacity(capacity),
+            vals: Vec::with_capacity(capacity),
+            visited: Vec::with_capacity(capacity),
+            prev: Vec::with_capacity(capacity),
+            next: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            hand: NIL,
+            free: NIL,
+            capacity,
+        })
+    }
+
+    /// 条目数
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    #[inline]
+    fn unlink(&mut self, i: u32) {
+        let (p, n) = (self.prev[i as usize], self.next[i as usize]);
+        if p == NIL {
+            self.head = n;
+        } else {
+            self.next[p as usize] = n;
+        }
+        if n == NIL {
+            self.tail = p;
+        } else {
+            self.prev[n as usize] = p;
+        }
+    }
+
+    #[inline]
+    fn push_front(&mut self, i: u32) {
+        self.prev[i as usize] = NIL;
+        self.next[i as usize] = self.head;
+        if self.head == NIL {
+            self.tail = i;
+        } else {
+            self.prev[self.head as usize] = i;
+        }
+        self.head = i;
+    }
+
+    /// 移动指针并淘汰一个条目，返回空出的槽位
+    fn evict(&mut self) -> u32 {
+        let mut i = if self.hand == NIL { self.tail } else { self.hand };
+        while self.visited[i as usize] {
+            self.visited[i as usize] = false;
+            i = self.prev[i as usize];
+            if i == NIL {
+                i = self.tail;
+            }
+        }
+        self.hand = self.prev[i as usize];
+        self.unlink(i);
+        self.map.remove(&self.keys[i as usize]);
+        i
+    }
+}
+
+impl<V> CacheOps<V> for Sieve<V> {
+    #[inline]
+    fn get(&mut self, key: &usize) -> Option<&V> {
+        let i = *self.map.get(key)? as usize;
+        self.visited[i] = true;
+        Some(&self.vals[i])
+    }
+
+    #[inline]
+    fn peek(&mut self, key: &usize) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        if let Some(&i) = self.map.get(&key) {
+            self.vals[i as usize] = value;
+            self.visited[i as usize] = true;
+            return;
+        }
+        let i = if self.free != NIL {
+            let i = self.free;
+            self.free = self.next[i as usize];
+            self.keys[i as usize] = key;
+            self.vals[i as usize] = value;
+            self.visited[i as usize] = false;
+            i
+        } else if self.keys.len() < self.capacity {
+            // 未满，追加新槽位；容量已校验可用 u32 表示
+            self.keys.push(key);
+            self.vals.push(value);
+            self.visited.push(false);
+            self.prev.push(NIL);
+            self.next.push(NIL);
+            (self.keys.len() - 1) as u32
+        } else {
+            let i = self.evict();
+            self.keys[i as usize] = key;
+            self.vals[i as usize] = value;
+            self.visited[i as usize] = false;
+            i
+        };
+        self.push_front(i);
+        self.map.insert(key, i);
+    }
+
+    /// 指针指向被删节点时前移，与淘汰后的位置一致
+    fn remove(&mut self, key: &usize) -> bool {
+        let Some(i) = self.map.remove(key) else {
+            return false;
+        };
+        if self.hand == i {
+            self.hand = self.prev[i as usize];
+        }
+        self.unlink(i);
+        self.next[i as usize] = self.free;
+        self.free = i;
+        true
+    }
+
+    /// 与容量已满时插入的淘汰相同，空出的槽位放入空闲链表
+    fn evict_one(&mut self) -> Option<usize> {
+        if self.map.is_empty() {
+            return None;
+        }
+        let i = self.evict();
+        self.next[i as usize] = self.free;
+        self.free = i;
+        Some(self.keys[i as usize])
+    }
+
+    #[inline]
+    fn can_evict(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn entry_count(&self) -> Option<usize> {
+        Some(Sieve::len(self))
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        Some(self.capacity)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        "SIEVE"
+    }
+}
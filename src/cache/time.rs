@@ -0,0 +1,76 @@
+//! 时间源
+//! 过期判定与回源延迟模拟统一从 [`TimeSource`] 取时间：真实时钟读取单调时间并真实睡眠，
+//! 模拟时钟只推进计数，供确定性测试与不睡眠的模拟时间模式使用
+//!
+//! Mini-Moka 等自带过期的缓存使用内部时钟，不受时间源影响
+
+use std::future::{Future, ready};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 时间源 trait
+pub trait TimeSource {
+    /// 自时钟起点以来经过的时间
+    fn now(&self) -> Duration;
+
+    /// 等待 `d`：真实时钟挂起当前任务，模拟时钟直接推进
+    fn sleep(&self, d: Duration) -> impl Future<Output = ()>;
+}
+
+/// 真实单调时钟，起点为创建时刻
+#[derive(Clone, Copy, Debug)]
+pub struct RealClock {
+    start: Instant,
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl TimeSource for RealClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    #[inline]
+    fn sleep(&self, d: Duration) -> impl Future<Output = ()> {
+        compio::time::sleep(d)
+    }
+}
+
+/// 模拟时钟，克隆后共享同一时间，可跨线程推进
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    nanos: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// 从零时刻开始
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 推进 `d`，超出 u64 纳秒的部分截断
+    #[inline]
+    pub fn advance(&self, d: Duration) {
+        let d = u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        self.nanos.fetch_add(d, Ordering::Relaxed);
+    }
+}
+
+impl TimeSource for MockClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn sleep(&self, d: Duration) -> impl Future<Output = ()> {
+        self.advance(d);
+        ready(())
+    }
+}
@@ -0,0 +1,108 @@
+//! TTL 包装
+//! 为不支持过期的缓存模拟 TTL/TTI，读取时惰性判定过期，过期条目视为未命中，由回源写入覆盖
+
+use crate::{CacheOps, RealClock, TimeSource};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+/// 按写入时间（TTL）与最近访问时间（TTI）判定过期的包装缓存，时间取自 `T`
+pub struct TtlCache<C, K, T = RealClock> {
+    inner: C,
+    ttl: Duration,
+    tti: Duration,
+    clock: T,
+    /// key -> (写入时间, 最近访问时间)
+    stamps: HashMap<K, (Duration, Duration)>,
+    expired: u64,
+}
+
+impl<C, K> TtlCache<C, K> {
+    /// 包装 `inner`，条目写入 `ttl` 后或 `tti` 内未被访问即过期
+    pub fn new(inner: C, ttl: Duration, tti: Duration) -> Self {
+        Self::with_clock(inner, ttl, tti, RealClock::default())
+    }
+}
+
+impl<C, K, T> TtlCache<C, K, T> {
+    /// 使用指定时间源，如与运行器共享的 [`crate::MockClock`]
+    pub fn with_clock(inner: C, ttl: Duration, tti: Duration, clock: T) -> Self {
+        Self {
+            inner,
+            ttl,
+            tti,
+            clock,
+            stamps: HashMap::new(),
+            expired: 0,
+        }
+    }
+
+    /// 因过期而未命中的读取次数
+    #[inline]
+    pub fn expired(&self) -> u64 {
+        self.expired
+    }
+}
+
+impl<C, K: Hash + Eq, T> TtlCache<C, K, T> {
+    /// 条目是否仍有效，过期时移除时间戳并计数
+    fn fresh(&mut self, key: &K, now: Duration) -> bool {
+        let Some(&(born, seen)) = self.stamps.get(key) else {
+            return false;
+        };
+        if now.saturating_sub(born) < self.ttl && now.saturating_sub(seen) < self.tti {
+            return true;
+        }
+        self.stamps.remove(key);
+        self.expired += 1;
+        false
+    }
+}
+
+impl<V, K: Hash + Eq + Clone, C: CacheOps<V, K>, T: TimeSource> CacheOps<V, K> for TtlCache<C, K, T> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let now = self.clock.now();
+        if !self.fresh(key, now) {
+            return None;
+        }
+        let value = self.inner.get(key);
+        match self.stamps.get_mut(key) {
+            Some(stamp) if value.is_some() => stamp.1 = now,
+            // 已被内部缓存淘汰
+            _ => {
+                self.stamps.remove(key);
+            }
+        }
+        value
+    }
+
+    /// 不刷新访问时间
+    fn peek(&mut self, key: &K) -> bool {
+        let now = self.clock.now();
+        self.fresh(key, now) && self.inner.peek(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let now = self.clock.now();
+        self.stamps.insert(key.clone(), (now, now));
+        self.inner.insert(key, value);
+    }
+
+    /// 已过期但尚未清理的条目视为不存在
+    fn remove(&mut self, key: &K) -> bool {
+        let now = self.clock.now();
+        let fresh = self.fresh(key, now);
+        self.stamps.remove(key);
+        self.inner.remove(key) && fresh
+    }
+
+    #[inline]
+    fn capacity_hint(&self) -> Option<usize> {
+        self.inner.capacity_hint()
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
@@ -1,24 +1,133 @@
 //! 配置模块
 //! 定义所有应用级别的常量和配置
 
+use crate::{AppError, Backend, CostFn, ENTRY_HEADER, Faults, Op, Result, ScenarioRegistry, Scramble, WorkloadGenerator, sized_cost, unit_cost};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs};
+
+/// 默认配置文件路径
+pub const CONFIG_FILE: &str = "bench.toml";
+
+/// 环境变量前缀，如 `LRU_BENCH_CAPACITY`
+pub const ENV_PREFIX: &str = "LRU_BENCH_";
+
 /// 缓存容量配置
 pub const CACHE_CAPACITY: u64 = 7500;
 
 /// 总Key数量
 pub const TOTAL_KEYS: usize = 10_000;
 
-/// 每次测试的操作数量
+/// 每次测试的操作数量，也是冒烟规模（`smoke`）的操作数量
 pub const WORKLOAD_SIZE: usize = 1_000;
 
+/// 完整规模（`full`）的操作数量
+pub const FULL_WORKLOAD_SIZE: usize = 10_000_000;
+
+/// 完整规模（`full`）的 Key 数量
+pub const FULL_TOTAL_KEYS: usize = 1_000_000;
+
 /// Zipf分布参数
 pub const ZIPF_S: f64 = 1.6;
 
 /// 读操作比例
 pub const READ_RATIO: f64 = 0.95;
 
-/// 后端延迟范围（微秒）
-pub const MIN_DELAY_US: u64 = 1000;
-pub const MAX_DELAY_US: u64 = 2000;
+/// 后端延迟范围
+pub const MIN_DELAY: Duration = Duration::from_millis(1);
+pub const MAX_DELAY: Duration = Duration::from_millis(2);
+
+/// 回源超时时长（故障注入）
+pub const BACKEND_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// 过期场景的默认 TTL
+pub const EXPIRE_TTL: Duration = Duration::from_millis(20);
+
+/// 过期场景的默认 TTI
+pub const EXPIRE_TTI: Duration = Duration::from_millis(5);
+
+/// 与基线对比时允许的吞吐量下降（百分比）
+pub const MAX_THROUGHPUT_DROP: f64 = 10.0;
+
+/// 与基线对比时允许的命中率下降（百分点）
+pub const MAX_HIT_RATE_DROP: f64 = 1.0;
+
+/// 远程缓存的默认连接数
+pub const REMOTE_CONNECTIONS: usize = 1;
+
+/// 磁盘层的默认容量（条目数）
+pub const DISK_CAPACITY: u64 = 10 * CACHE_CAPACITY;
+
+/// 磁盘层每个条目的默认块大小（字节）
+pub const DISK_BLOCK: usize = 4096;
+
+/// 文件读取后端的默认数据文件大小（字节）
+pub const BACKEND_FILE_SIZE: u64 = 256 << 20;
+
+/// 文件读取后端每次回源读取的默认块大小（字节）
+pub const BACKEND_FILE_BLOCK: usize = 4096;
+
+/// 磁盘层块首部存放 key 的字节数，块大小不得小于此值
+pub const DISK_HEADER: usize = size_of::<u64>();
+
+/// 范围扫描的默认长度
+pub const RANGE_LEN: usize = 16;
+
+/// YCSB 预设的 Zipf 参数
+pub const YCSB_ZIPF_S: f64 = 0.99;
+
+/// YCSB E 的范围扫描长度
+pub const YCSB_SCAN_LEN: usize = 100;
+
+/// 收敛检测的窗口操作数
+pub const CONVERGE_WINDOW: usize = 1_000;
+
+/// 收敛检测的命中率变化阈值（百分点）
+pub const CONVERGE_EPSILON: f64 = 0.5;
+
+/// 收敛检测的最大预热操作数
+pub const CONVERGE_MAX_OPS: usize = 1_000_000;
+
+/// 工作集时间线的采样点数
+pub const WORKING_SET_POINTS: usize = 100;
+
+/// 占用率采样间隔（操作数）
+pub const OCCUPANCY_EVERY: usize = 1000;
+
+/// 进度报告间隔（操作数）
+pub const PROGRESS_EVERY: usize = 100_000;
+
+/// 检查时间预算的间隔（操作数），避免每个操作都读取时钟
+pub const BUDGET_CHECK_EVERY: u64 = 1024;
+
+/// 火焰图缺省输出目录
+pub const PROFILE_DIR: &str = "flamegraphs";
+
+/// CPU 采样频率（Hz），取质数避免与周期性操作同步
+pub const PROFILE_FREQUENCY: i32 = 997;
+
+/// 并发 map 伪缓存每次清理后保留的条目数占容量的比例，留出余量使清理成批进行
+pub const MAP_SWEEP_KEEP: f64 = 0.9;
+
+/// ARC 追踪单行允许的最大块数，超出视为损坏的行，避免一行展开出海量操作
+pub const ARC_MAX_BLOCKS: u64 = 1 << 16;
+
+/// 同步对拍默认输出的分歧数
+pub const LOCKSTEP_DIVERGENCES: usize = 20;
+
+/// Bytes 值长度中位数（字节）
+pub const BYTES_MEDIAN: usize = 4096;
+
+/// Bytes 值长度对数正态分布的 sigma
+pub const BYTES_SIGMA: f64 = 1.0;
+
+/// Bytes 值最大长度（字节）
+pub const BYTES_MAX: usize = 256 * 1024;
 
 /// 基准测试配置
 pub mod bench {
@@ -38,9 +147,1200 @@ pub mod bench {
     
     /// 工作负载种子
     pub const WORKLOAD_SEED: u64 = 42;
+
+    /// 回源延迟与故障采样的种子
+    pub const BACKEND_SEED: u64 = 7;
     
     /// 最小命中率目标（百分比）
     pub const MIN_HIT_RATE_TARGET: f64 = 85.0;
+
+    /// 负缓存对比中不存在的 key 比例（配置未指定时使用）
+    pub const MISSING_RATIO: f64 = 0.1;
+
+    /// 加载器模式中同一线程上并发的任务数
+    pub const LOADER_TASKS: usize = 8;
+
+    /// 惊群场景中同时读取冷 key 的任务数
+    pub const DOGPILE_TASKS: usize = 32;
+
+    /// 惊群场景中每个任务读取冷 key 的次数
+    pub const DOGPILE_READS: usize = 4;
+
+    /// 批量接口对比的批大小
+    pub const BATCH_SIZES: [usize; 3] = [8, 64, 512];
+}
+
+/// 运行时配置，依次叠加默认值、配置文件与环境变量
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Config {
+    /// 缓存容量
+    pub capacity: u64,
+    /// 工作负载
+    pub workload: WorkloadConfig,
+    /// 选用的场景名，加载配置时叠加在配置文件之上、环境变量之下
+    pub scenario: Option<String>,
+    /// 额外的场景文件（TOML / YAML）
+    pub scenario_files: Vec<PathBuf>,
+    /// 按场景名覆盖工作负载规模，基准测试各组以组名查找；同名时覆盖内置的 `smoke` 与 `full`
+    pub sizes: BTreeMap<String, WorkloadSize>,
+    /// 预热，缺省时按容量推导
+    pub warmup: Option<WarmupConfig>,
+    /// 预热策略
+    pub warmup_strategy: WarmupStrategy,
+    /// 预热收敛检测，设置后预热长度由收敛所需操作数决定，缺省使用固定长度
+    pub convergence: Option<Convergence>,
+    /// 后端延迟模型
+    pub latency: Backend,
+    /// 文件读取后端，设置后回源改为从数据文件真实读取一个块，替代 [`Config::latency`]
+    pub backend_file: Option<FileBackendConfig>,
+    /// 回源故障注入，默认不注入
+    pub faults: Faults,
+    /// 回源延迟与故障采样的种子，每次运行（每个缓存）从同一种子开始
+    pub backend_seed: u64,
+    /// 是否缓存不存在的 key 的空结果（负缓存）
+    pub negative_cache: bool,
+    /// 过期场景的 TTL/TTI
+    pub expiration: Expiration,
+    /// 模拟时间：回源延迟只累加到虚拟时钟而不真实睡眠，耗时与延迟按模型时间报告
+    pub simulated_time: bool,
+    /// 确定性模式：以工作负载种子为主种子派生预热、回源与分片哈希的种子，并启用模拟时间，
+    /// 同一配置的多次运行命中与模型耗时逐位一致（缓存操作与回源等待的实测耗时除外）；
+    /// moka 与 mini-moka 的准入频率草图使用内部随机种子，命中率仍会浮动，见 [`crate::NONDETERMINISTIC`]
+    pub deterministic: bool,
+    /// 条目代价模型，非 `unit` 时按容量乘以平均代价的代价预算淘汰，比较按大小淘汰的效果
+    pub cost: CostModel,
+    /// 在标准错误输出运行进度：已完成操作数、当前命中率与预计剩余时间
+    pub progress: bool,
+    /// 真实时间预算，超出后停止运行并把已完成部分作为部分结果导出；参数扫描整体共享，缺省不限时
+    #[serde(with = "humantime_serde")]
+    pub budget: Option<Duration>,
+    /// 基准测试中要扫描的 Zipf 参数，空则不扫描
+    pub zipf_sweep: Vec<f64>,
+    /// 工作负载缓存目录，设置后按配置指纹复用已生成的工作负载，缺省每次重新生成
+    pub workload_cache: Option<PathBuf>,
+    /// JSON 结果报告输出路径，缺省不输出
+    pub report: Option<PathBuf>,
+    /// CSV 结果追加路径，缺省不输出
+    pub csv: Option<PathBuf>,
+    /// HTML 图表报告输出路径，缺省不输出
+    pub html: Option<PathBuf>,
+    /// Markdown 摘要输出路径，缺省不输出
+    pub markdown: Option<PathBuf>,
+    /// 火焰图输出目录，每个缓存一个 SVG，缺省为 [`PROFILE_DIR`]；仅在启用 `profiling` 特性时采样
+    pub profile_dir: Option<PathBuf>,
+    /// 基线对比与回归阈值
+    pub baseline: Baseline,
+    /// 外部缓存进程（`remote` 子命令），缺省不连接
+    pub remote: Option<RemoteConfig>,
+    /// 两级缓存的磁盘层，内存未命中时先查磁盘再回源，缺省不启用
+    pub disk: Option<DiskTierConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            capacity: CACHE_CAPACITY,
+            workload: WorkloadConfig::default(),
+            scenario: None,
+            scenario_files: Vec::new(),
+            sizes: BTreeMap::new(),
+            warmup: None,
+            warmup_strategy: WarmupStrategy::default(),
+            convergence: None,
+            latency: Backend::default(),
+            backend_file: None,
+            faults: Faults::default(),
+            backend_seed: bench::BACKEND_SEED,
+            negative_cache: false,
+            expiration: Expiration::default(),
+            simulated_time: false,
+            deterministic: false,
+            cost: CostModel::Unit,
+            progress: false,
+            budget: None,
+            zipf_sweep: Vec::new(),
+            workload_cache: None,
+            report: None,
+            csv: None,
+            html: None,
+            markdown: None,
+            profile_dir: None,
+            baseline: Baseline::default(),
+            remote: None,
+            disk: None,
+        }
+    }
+}
+
+impl Config {
+    /// 加载配置：`LRU_BENCH_CONFIG` 指定的文件（默认 bench.toml，不存在则跳过），再应用环境变量并校验
+    pub fn load() -> Result<Self> {
+        let path = env::var(format!("{ENV_PREFIX}CONFIG")).unwrap_or_else(|_| CONFIG_FILE.into());
+        let mut cfg = match fs::read_to_string(&path) {
+            Ok(text) => Self::from_toml(&text)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        cfg.apply_env()?;
+        cfg.apply_scenario()?;
+        cfg.apply_deterministic();
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// 应用 [`Config::scenario`] 指定的场景及其规模覆盖，随后重新应用环境变量，保证环境变量优先
+    pub fn apply_scenario(&mut self) -> Result<()> {
+        let Some(name) = self.scenario.clone() else {
+            return Ok(());
+        };
+        ScenarioRegistry::for_config(self)?.get(&name)?.apply(self);
+        if let Some(size) = self.size(&name) {
+            size.apply(&mut self.workload);
+        }
+        self.apply_env()
+    }
+
+    /// 启用 [`Config::deterministic`] 时由工作负载种子派生回源种子并启用模拟时间；
+    /// 修改工作负载种子后需重新调用
+    pub fn apply_deterministic(&mut self) {
+        if self.deterministic {
+            self.backend_seed = derive_seed(self.workload.seed, "backend");
+            self.simulated_time = true;
+        }
+    }
+
+    /// 解析 TOML 文本（不应用环境变量，不校验）
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// 场景的规模覆盖，先查 [`Config::sizes`]，再查内置规模
+    pub fn size(&self, scenario: &str) -> Option<WorkloadSize> {
+        self.sizes.get(scenario).copied().or_else(|| WorkloadSize::builtin(scenario))
+    }
+
+    /// 应用场景 `scenario` 的规模覆盖并重新校验，未配置该场景时保持原规模
+    pub fn sized(mut self, scenario: &str) -> Result<Self> {
+        if let Some(size) = self.size(scenario) {
+            size.apply(&mut self.workload);
+        }
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// 按工作负载配置生成操作序列，配置了 [`Config::workload_cache`] 时优先复用缓存文件
+    pub fn workload_ops(&self) -> Result<Vec<Op>> {
+        let mut generator = WorkloadGenerator::new(self.workload.clone());
+        match &self.workload_cache {
+            Some(dir) => generator.cached(dir),
+            None => generator.generate(),
+        }
+    }
+
+    /// 预热配置，未显式配置时按容量推导，策略取自 [`Config::warmup_strategy`]，
+    /// 工作负载启用打散时附带相同的映射
+    pub fn warmup(&self) -> WarmupConfig {
+        let w = &self.workload;
+        let warmup = self
+            .warmup
+            .clone()
+            .unwrap_or_else(|| WarmupConfig::for_capacity(self.capacity))
+            .with_strategy(self.warmup_strategy)
+            .with_scramble(w.scrambled().then(|| Scramble::new(w.total_keys, w.seed)));
+        if self.deterministic {
+            warmup.with_seed(derive_seed(w.seed, "warmup"))
+        } else {
+            warmup
+        }
+    }
+
+    /// 确定性模式下由工作负载种子派生的分片哈希种子，否则为 None（分片缓存每次随机）
+    pub fn shard_seed(&self) -> Option<u64> {
+        self.deterministic.then(|| derive_seed(self.workload.seed, "shards"))
+    }
+
+    /// 报告中的后端描述：启用文件读取后端时为 `file:路径`，否则为延迟模型
+    pub fn backend_label(&self) -> String {
+        match &self.backend_file {
+            Some(f) => format!("file:{}", f.path().display()),
+            None => self.latency.to_string(),
+        }
+    }
+
+    /// 用 `LRU_BENCH_*` 环境变量覆盖对应字段
+    pub fn apply_env(&mut self) -> Result<()> {
+        if let Some((_, name)) = env_var("SCENARIO") {
+            self.scenario = Some(name);
+        }
+        env_list("SCENARIO_FILES", &mut self.scenario_files)?;
+        env_parse("CAPACITY", &mut self.capacity)?;
+        // 预设与命名规模先于单项参数应用，单项环境变量可覆盖
+        let mut preset = WorkloadPreset::YcsbA;
+        if env_parse("WORKLOAD", &mut preset)? {
+            preset.apply(&mut self.workload);
+        }
+        if let Some((key, name)) = env_var("SIZE") {
+            let size = self.size(&name).ok_or_else(|| AppError::Config(format!("{key}: unknown size `{name}`")))?;
+            size.apply(&mut self.workload);
+        }
+        let w = &mut self.workload;
+        env_parse("TOTAL_KEYS", &mut w.total_keys)?;
+        env_parse("WORKLOAD_SIZE", &mut w.size)?;
+        env_parse("ZIPF_S", &mut w.zipf_s)?;
+        env_parse("READ_RATIO", &mut w.read_ratio)?;
+        env_parse("PEEK_RATIO", &mut w.peek_ratio)?;
+        env_parse("SEED", &mut w.seed)?;
+        env_parse("PHASES", &mut w.phases)?;
+        env_parse("VALUE_SIZE", &mut w.value_size)?;
+        env_parse("DISTRIBUTION", &mut w.distribution)?;
+        env_parse("SCRAMBLE", &mut w.scramble)?;
+        env_parse("MISSING_RATIO", &mut w.missing_ratio)?;
+        env_parse("DELETE_RATIO", &mut w.delete_ratio)?;
+        env_parse("INSERT_RATIO", &mut w.insert_ratio)?;
+        env_parse("RANGE_RATIO", &mut w.range_ratio)?;
+        env_parse("RANGE_LEN", &mut w.range_len)?;
+        let mut scan = w.scan.clone().unwrap_or_default();
+        let every = env_parse("SCAN_EVERY", &mut scan.every)?;
+        let len = env_parse("SCAN_LEN", &mut scan.len)?;
+        if every || len {
+            w.scan = Some(scan);
+        }
+        env_list("ZIPF_SWEEP", &mut self.zipf_sweep)?;
+        env_path("WORKLOAD_CACHE", &mut self.workload_cache);
+        env_path("REPORT", &mut self.report);
+        env_path("CSV", &mut self.csv);
+        env_path("HTML", &mut self.html);
+        env_path("MARKDOWN", &mut self.markdown);
+        env_path("PROFILE_DIR", &mut self.profile_dir);
+        env_parse("BACKEND", &mut self.latency)?;
+        let min = env_duration("MIN_DELAY")?;
+        let max = env_duration("MAX_DELAY")?;
+        self.latency = self.latency.with_bounds(min, max)?;
+        let mut file = self.backend_file.clone().unwrap_or_default();
+        let path = file.path.clone();
+        env_path("BACKEND_FILE", &mut file.path);
+        let size = env_parse("BACKEND_FILE_SIZE", &mut file.size)?;
+        let block = env_parse("BACKEND_FILE_BLOCK", &mut file.block)?;
+        if size || block || file.path != path {
+            self.backend_file = Some(file);
+        }
+        let f = &mut self.faults;
+        env_parse("ERROR_RATE", &mut f.error_rate)?;
+        env_parse("TIMEOUT_RATE", &mut f.timeout_rate)?;
+        env_parse("RETRIES", &mut f.retries)?;
+        if let Some(timeout) = env_duration("TIMEOUT")? {
+            f.timeout = timeout;
+        }
+        env_parse("BACKEND_SEED", &mut self.backend_seed)?;
+        env_parse("WARMUP_STRATEGY", &mut self.warmup_strategy)?;
+        env_parse("COST", &mut self.cost)?;
+        let mut conv = self.convergence.clone().unwrap_or_default();
+        let window = env_parse("CONVERGE_WINDOW", &mut conv.window)?;
+        let epsilon = env_parse("CONVERGE_EPSILON", &mut conv.epsilon)?;
+        let max_ops = env_parse("CONVERGE_MAX_OPS", &mut conv.max_ops)?;
+        if window || epsilon || max_ops {
+            self.convergence = Some(conv);
+        }
+        env_parse("NEGATIVE_CACHE", &mut self.negative_cache)?;
+        env_parse("SIMULATED_TIME", &mut self.simulated_time)?;
+        env_parse("DETERMINISTIC", &mut self.deterministic)?;
+        env_parse("PROGRESS", &mut self.progress)?;
+        if let Some(budget) = env_duration("BUDGET")? {
+            self.budget = Some(budget);
+        }
+        if let Some(ttl) = env_duration("TTL")? {
+            self.expiration.ttl = ttl;
+        }
+        if let Some(tti) = env_duration("TTI")? {
+            self.expiration.tti = tti;
+        }
+        let b = &mut self.baseline;
+        env_path("BASELINE", &mut b.path);
+        env_parse("MAX_THROUGHPUT_DROP", &mut b.max_throughput_drop)?;
+        env_parse("MAX_HIT_RATE_DROP", &mut b.max_hit_rate_drop)?;
+        // 地址写作 URL，连接数单独设置，互不覆盖
+        let mut remote = self.remote.clone().unwrap_or_default();
+        let connections = remote.connections;
+        let url = env_parse("REMOTE", &mut remote)?;
+        remote.connections = connections;
+        let conns = env_parse("REMOTE_CONNECTIONS", &mut remote.connections)?;
+        if url || conns {
+            self.remote = Some(remote);
+        }
+        let mut disk = self.disk.clone().unwrap_or_default();
+        let capacity = env_parse("DISK_CAPACITY", &mut disk.capacity)?;
+        let block = env_parse("DISK_BLOCK", &mut disk.block)?;
+        let path = disk.path.clone();
+        env_path("DISK_PATH", &mut disk.path);
+        if capacity || block || disk.path != path {
+            self.disk = Some(disk);
+        }
+        Ok(())
+    }
+
+    /// 校验取值范围
+    pub fn validate(&self) -> Result<()> {
+        let w = &self.workload;
+        let checks = [
+            (self.capacity > 0, "capacity must be > 0"),
+            (w.total_keys > 0, "workload.total_keys must be > 0"),
+            (w.size > 0, "workload.size must be > 0"),
+            (w.zipf_s >= 0.0, "workload.zipf_s must be >= 0"),
+            (w.phases > 0, "workload.phases must be > 0"),
+            ((0.0..=1.0).contains(&w.read_ratio), "workload.read_ratio must be in [0, 1]"),
+            ((0.0..=1.0).contains(&w.peek_ratio), "workload.peek_ratio must be in [0, 1]"),
+            ((0.0..=1.0).contains(&w.missing_ratio), "workload.missing_ratio must be in [0, 1]"),
+            ((0.0..=1.0).contains(&w.delete_ratio), "workload.delete_ratio must be in [0, 1]"),
+            ((0.0..=1.0).contains(&w.insert_ratio), "workload.insert_ratio must be in [0, 1]"),
+            ((0.0..=1.0).contains(&w.range_ratio), "workload.range_ratio must be in [0, 1]"),
+            (w.range_len > 0, "workload.range_len must be > 0"),
+            (self.zipf_sweep.iter().all(|s| *s >= 0.0), "zipf_sweep values must be >= 0"),
+            (self.budget.is_none_or(|b| !b.is_zero()), "budget must be > 0"),
+            (
+                !self.expiration.ttl.is_zero() && !self.expiration.tti.is_zero(),
+                "expiration.ttl and expiration.tti must be > 0",
+            ),
+            (
+                self.baseline.max_throughput_drop >= 0.0 && self.baseline.max_hit_rate_drop >= 0.0,
+                "baseline.max_throughput_drop and baseline.max_hit_rate_drop must be >= 0",
+            ),
+            (
+                self.convergence
+                    .as_ref()
+                    .is_none_or(|c| c.window > 0 && c.epsilon >= 0.0 && c.max_ops >= c.window),
+                "convergence.window must be > 0, epsilon >= 0 and max_ops >= window",
+            ),
+            (
+                self.convergence.is_none() || self.warmup_strategy == WarmupStrategy::Zipf,
+                "convergence requires the zipf warmup strategy",
+            ),
+            (
+                w.scan.as_ref().is_none_or(|s| s.every > 0 && s.len > 0),
+                "workload.scan.every and workload.scan.len must be > 0",
+            ),
+            (
+                self.remote.as_ref().is_none_or(|r| r.connections > 0),
+                "remote.connections must be > 0",
+            ),
+            (
+                self.backend_file.as_ref().is_none_or(|f| f.block > 0 && f.size >= f.block as u64),
+                "backend_file.block must be > 0 and backend_file.size >= block",
+            ),
+            (
+                self.backend_file.is_none() || !self.simulated_time,
+                "backend_file reads real files and cannot be combined with simulated_time",
+            ),
+            (
+                self.disk.as_ref().is_none_or(|d| d.capacity > 0 && d.block >= DISK_HEADER),
+                "disk.capacity must be > 0 and disk.block >= 8",
+            ),
+        ];
+        if let Some((_, msg)) = checks.iter().find(|(ok, _)| !ok) {
+            return Err(AppError::Config((*msg).into()));
+        }
+        w.value_size.validate()?;
+        w.distribution.validate()?;
+        self.faults.validate()?;
+        self.latency.validate()
+    }
+}
+
+/// 由主种子与用途名派生互不相关的子种子
+fn derive_seed(master: u64, stream: &str) -> u64 {
+    let mut bytes = master.to_le_bytes().to_vec();
+    bytes.extend_from_slice(stream.as_bytes());
+    crate::distribution::fnv64(&bytes)
+}
+
+/// 读取环境变量，不存在则保持原值
+fn env_var(name: &str) -> Option<(String, String)> {
+    let key = format!("{ENV_PREFIX}{name}");
+    env::var(&key).ok().map(|val| (key, val))
+}
+
+/// 解析环境变量到 slot，返回是否设置
+fn env_parse<T: FromStr>(name: &str, slot: &mut T) -> Result<bool>
+where
+    T::Err: Display,
+{
+    let Some((key, val)) = env_var(name) else {
+        return Ok(false);
+    };
+    *slot = val.parse().map_err(|e| AppError::Config(format!("{key}={val}: {e}")))?;
+    Ok(true)
+}
+
+fn env_path(name: &str, slot: &mut Option<PathBuf>) {
+    if let Some((_, val)) = env_var(name) {
+        *slot = Some(val.into());
+    }
+}
+
+/// 解析逗号分隔的列表
+fn env_list<T: FromStr>(name: &str, slot: &mut Vec<T>) -> Result<()>
+where
+    T::Err: Display,
+{
+    if let Some((key, val)) = env_var(name) {
+        *slot = val
+            .split(',')
+            .map(|v| v.trim().parse().map_err(|e| AppError::Config(format!("{key}={val}: {e}"))))
+            .collect::<Result<_>>()?;
+    }
+    Ok(())
+}
+
+/// 解析形如 `1ms`、`1500us` 的时长，未设置时返回 None
+fn env_duration(name: &str) -> Result<Option<Duration>> {
+    env_var(name)
+        .map(|(key, val)| {
+            humantime::parse_duration(&val).map_err(|e| AppError::Config(format!("{key}={val}: {e}")))
+        })
+        .transpose()
+}
+
+/// 工作负载配置
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WorkloadConfig {
+    /// Key 空间大小
+    pub total_keys: usize,
+    /// 操作数量
+    pub size: usize,
+    /// Zipf 分布参数
+    pub zipf_s: f64,
+    /// 读操作比例
+    pub read_ratio: f64,
+    /// 读操作中使用 peek（不更新访问顺序）的比例
+    pub peek_ratio: f64,
+    /// 随机种子
+    pub seed: u64,
+    /// 周期性顺序扫描，缺省不扫描
+    pub scan: Option<ScanConfig>,
+    /// 热点迁移阶段数，大于 1 时每阶段重新排列 key 的热度
+    pub phases: usize,
+    /// 写入值的大小分布，仅对 `Bytes`、`Vec<u8>` 等变长值生效
+    pub value_size: ValueSize,
+    /// key 分布，Zipf 类分布使用 `zipf_s`
+    pub distribution: DistributionKind,
+    /// 后端不存在的 key 所占比例，这些 key 只会以 [`crate::Op::ReadMissing`] 出现
+    pub missing_ratio: f64,
+    /// 删除（失效）操作所占比例，其余操作再按读写比例划分
+    pub delete_ratio: f64,
+    /// 写操作中插入新 key 的比例，其余写操作更新已有 key
+    pub insert_ratio: f64,
+    /// 读操作中范围扫描（[`crate::Op::Scan`]）的比例
+    pub range_ratio: f64,
+    /// 范围扫描的 key 数
+    pub range_len: usize,
+    /// 更新前先读取同一 key（读-改-写）
+    pub read_modify_write: bool,
+    /// 以 Fisher-Yates 随机排列打散热度排名到 key 的映射，使 key 的数值与热度无关
+    pub scramble: bool,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        Self {
+            total_keys: TOTAL_KEYS,
+            size: WORKLOAD_SIZE,
+            zipf_s: ZIPF_S,
+            read_ratio: READ_RATIO,
+            peek_ratio: 0.0,
+            seed: bench::WORKLOAD_SEED,
+            scan: None,
+            phases: 1,
+            value_size: ValueSize::default(),
+            distribution: DistributionKind::default(),
+            missing_ratio: 0.0,
+            delete_ratio: 0.0,
+            insert_ratio: 0.0,
+            range_ratio: 0.0,
+            range_len: RANGE_LEN,
+            read_modify_write: false,
+            scramble: false,
+        }
+    }
+}
+
+impl WorkloadConfig {
+    /// 是否打散热度排名到 key 的映射：显式启用 `scramble` 或使用 `scrambled-zipf` 分布
+    #[inline]
+    pub fn scrambled(&self) -> bool {
+        self.scramble || self.distribution == DistributionKind::ScrambledZipf
+    }
+
+    /// key `id` 是否不存在于后端；按 id 哈希判定，同一 key 结果固定且不消耗随机数
+    #[inline]
+    pub fn is_missing(&self, id: usize) -> bool {
+        if self.missing_ratio <= 0.0 {
+            return false;
+        }
+        // splitmix64 终混，把连续 id 打散到 [0, 1)
+        let mut h = (id as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        h ^= h >> 31;
+        ((h >> 11) as f64 / (1u64 << 53) as f64) < self.missing_ratio
+    }
+}
+
+/// 值大小分布，配置文件中写作 `value_size = { kind = "uniform", min = 64, max = 4096 }`，
+/// 环境变量写作 `fixed:1024`、`uniform:64..4096`、`lognormal:4096:1.0`、`trace:路径`
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+#[non_exhaustive]
+pub enum ValueSize {
+    /// 固定长度
+    Fixed { len: usize },
+    /// [min, max] 内均匀分布
+    Uniform { min: usize, max: usize },
+    /// 对数正态分布
+    LogNormal { median: usize, sigma: f64 },
+    /// 从追踪文件记录的值大小中均匀抽样
+    Trace { path: PathBuf },
+}
+
+impl Default for ValueSize {
+    fn default() -> Self {
+        Self::LogNormal {
+            median: BYTES_MEDIAN,
+            sigma: BYTES_SIGMA,
+        }
+    }
+}
+
+impl ValueSize {
+    /// 校验参数
+    pub fn validate(&self) -> Result<()> {
+        let ok = match *self {
+            Self::Fixed { len } => len > 0,
+            Self::Uniform { min, max } => min > 0 && min <= max,
+            Self::LogNormal { median, sigma } => median > 0 && sigma >= 0.0,
+            Self::Trace { .. } => true,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(AppError::Config(format!("invalid workload.value_size {self:?}")))
+        }
+    }
+}
+
+impl FromStr for ValueSize {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || AppError::Config(format!("invalid value size `{s}`"));
+        let num = |v: &str| v.trim().parse::<usize>().map_err(|_| bad());
+        let (kind, arg) = s.split_once(':').ok_or_else(bad)?;
+        Ok(match kind {
+            "fixed" => Self::Fixed { len: num(arg)? },
+            "uniform" => {
+                let (min, max) = arg.split_once("..").ok_or_else(bad)?;
+                Self::Uniform {
+                    min: num(min)?,
+                    max: num(max)?,
+                }
+            }
+            "lognormal" => {
+                let (median, sigma) = arg.split_once(':').ok_or_else(bad)?;
+                Self::LogNormal {
+                    median: num(median)?,
+                    sigma: sigma.trim().parse().map_err(|_| bad())?,
+                }
+            }
+            "trace" => Self::Trace { path: arg.into() },
+            _ => return Err(bad()),
+        })
+    }
+}
+
+/// YCSB 核心工作负载预设，写作 `ycsb-a` … `ycsb-f`
+///
+/// 只设置读写比例、key 分布与操作类型，key 空间与操作数沿用配置
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WorkloadPreset {
+    /// 更新密集：50% 读，50% 更新
+    YcsbA,
+    /// 读为主：95% 读，5% 更新
+    YcsbB,
+    /// 只读
+    YcsbC,
+    /// 读最新：95% 读，5% 插入，读偏向最近插入的 key
+    YcsbD,
+    /// 短范围扫描：95% 扫描，5% 插入
+    YcsbE,
+    /// 读-改-写：50% 读，50% 先读后更新
+    YcsbF,
+}
+
+impl WorkloadPreset {
+    /// 覆盖 `w` 中与预设相关的字段
+    pub fn apply(self, w: &mut WorkloadConfig) {
+        w.zipf_s = YCSB_ZIPF_S;
+        w.distribution = DistributionKind::ScrambledZipf;
+        w.peek_ratio = 0.0;
+        w.delete_ratio = 0.0;
+        w.insert_ratio = 0.0;
+        w.range_ratio = 0.0;
+        w.read_modify_write = false;
+        match self {
+            Self::YcsbA => w.read_ratio = 0.5,
+            Self::YcsbB => w.read_ratio = 0.95,
+            Self::YcsbC => w.read_ratio = 1.0,
+            Self::YcsbD => {
+                w.read_ratio = 0.95;
+                w.insert_ratio = 1.0;
+                w.distribution = DistributionKind::Latest;
+            }
+            Self::YcsbE => {
+                w.read_ratio = 0.95;
+                w.insert_ratio = 1.0;
+                w.range_ratio = 1.0;
+                w.range_len = YCSB_SCAN_LEN;
+            }
+            Self::YcsbF => {
+                w.read_ratio = 0.5;
+                w.read_modify_write = true;
+            }
+        }
+    }
+}
+
+impl fmt::Display for WorkloadPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::YcsbA => "ycsb-a",
+            Self::YcsbB => "ycsb-b",
+            Self::YcsbC => "ycsb-c",
+            Self::YcsbD => "ycsb-d",
+            Self::YcsbE => "ycsb-e",
+            Self::YcsbF => "ycsb-f",
+        })
+    }
+}
+
+impl FromStr for WorkloadPreset {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "ycsb-a" => Self::YcsbA,
+            "ycsb-b" => Self::YcsbB,
+            "ycsb-c" => Self::YcsbC,
+            "ycsb-d" => Self::YcsbD,
+            "ycsb-e" => Self::YcsbE,
+            "ycsb-f" => Self::YcsbF,
+            _ => return Err(AppError::Config(format!("invalid workload preset `{s}`, expected ycsb-a … ycsb-f"))),
+        })
+    }
+}
+
+/// key 分布，配置文件中写作 `distribution = { kind = "hotspot", hot_keys = 0.2, hot_ops = 0.8 }`，
+/// 环境变量写作 `uniform`、`zipf`、`scrambled-zipf`、`latest`、`hotspot:0.2:0.8`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub enum DistributionKind {
+    /// 均匀分布
+    Uniform,
+    /// Zipf 分布，小 id 最热
+    #[default]
+    Zipf,
+    /// Zipf 分布，热点经 [`Scramble`] 打散到整个 key 空间，等同 `zipf` 加 `scramble = true`
+    ScrambledZipf,
+    /// 最近插入的 key 最热
+    Latest,
+    /// `hot_ops` 比例的请求落在前 `hot_keys` 比例的 key 上
+    Hotspot { hot_keys: f64, hot_ops: f64 },
+}
+
+impl DistributionKind {
+    /// 校验参数
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            Self::Hotspot { hot_keys, hot_ops } if !(hot_keys > 0.0 && hot_keys <= 1.0 && (0.0..=1.0).contains(&hot_ops)) => {
+                Err(AppError::Config(format!("invalid workload.distribution {self:?}")))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for DistributionKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || AppError::Config(format!("invalid distribution `{s}`"));
+        Ok(match s {
+            "uniform" => Self::Uniform,
+            "zipf" => Self::Zipf,
+            "scrambled-zipf" => Self::ScrambledZipf,
+            "latest" => Self::Latest,
+            _ => {
+                let args = s.strip_prefix("hotspot:").ok_or_else(bad)?;
+                let (keys, ops) = args.split_once(':').ok_or_else(bad)?;
+                Self::Hotspot {
+                    hot_keys: keys.trim().parse().map_err(|_| bad())?,
+                    hot_ops: ops.trim().parse().map_err(|_| bad())?,
+                }
+            }
+        })
+    }
+}
+
+/// 工作负载规模覆盖，未设置的项保持原值
+///
+/// 配置文件中写作 `[sizes.<场景名>]`，例如 `[sizes.zipf-sweep]` 只放大 Zipf 扫描组的工作负载
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkloadSize {
+    /// 操作数量
+    pub size: Option<usize>,
+    /// Key 空间大小
+    pub total_keys: Option<usize>,
+}
+
+impl WorkloadSize {
+    /// 内置规模：`smoke` 即默认规模，用于快速验证，`full` 用于正式测量
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (size, total_keys) = match name {
+            "smoke" => (WORKLOAD_SIZE, TOTAL_KEYS),
+            "full" => (FULL_WORKLOAD_SIZE, FULL_TOTAL_KEYS),
+            _ => return None,
+        };
+        Some(Self {
+            size: Some(size),
+            total_keys: Some(total_keys),
+        })
+    }
+
+    /// 覆盖工作负载的规模
+    pub fn apply(&self, w: &mut WorkloadConfig) {
+        if let Some(size) = self.size {
+            w.size = size;
+        }
+        if let Some(total_keys) = self.total_keys {
+            w.total_keys = total_keys;
+        }
+    }
+}
+
+/// 扫描配置：每 `every` 个热点操作后，顺序读一遍热点 Key 空间之外的 `len` 个 key
+///
+/// 扫描 key 只出现在扫描中，用于衡量缓存抵抗扫描污染的能力
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct ScanConfig {
+    /// 两次扫描之间的热点操作数
+    pub every: usize,
+    /// 每次扫描的 key 数量
+    pub len: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            // 默认规模下扫描须先于工作负载结束触发
+            every: WORKLOAD_SIZE / 10,
+            len: TOTAL_KEYS,
+        }
+    }
+}
+
+/// 预热收敛检测：每 `window` 个预热写入计算一次命中率，相邻窗口之差低于 `epsilon` 即视为收敛
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Convergence {
+    /// 窗口操作数
+    pub window: usize,
+    /// 命中率变化阈值（百分点）
+    pub epsilon: f64,
+    /// 最大预热操作数，达到后即使未收敛也停止
+    pub max_ops: usize,
+}
+
+impl Default for Convergence {
+    fn default() -> Self {
+        Self {
+            window: CONVERGE_WINDOW,
+            epsilon: CONVERGE_EPSILON,
+            max_ops: CONVERGE_MAX_OPS,
+        }
+    }
+}
+
+/// 过期配置，时长写作 `20ms`、`1s`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Expiration {
+    /// 写入后的存活时间
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+    /// 未被访问的最长空闲时间
+    #[serde(with = "humantime_serde")]
+    pub tti: Duration,
+}
+
+impl Default for Expiration {
+    fn default() -> Self {
+        Self {
+            ttl: EXPIRE_TTL,
+            tti: EXPIRE_TTI,
+        }
+    }
+}
+
+/// 基线对比：与先前保存的 JSON 报告比较，超出阈值的下降视为回归
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Baseline {
+    /// 基线 JSON 报告路径，缺省不对比
+    pub path: Option<PathBuf>,
+    /// 允许的吞吐量下降（百分比）
+    pub max_throughput_drop: f64,
+    /// 允许的命中率下降（百分点）
+    pub max_hit_rate_drop: f64,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_throughput_drop: MAX_THROUGHPUT_DROP,
+            max_hit_rate_drop: MAX_HIT_RATE_DROP,
+        }
+    }
+}
+
+/// 外部缓存进程，命令行与环境变量写作 `redis://127.0.0.1:6379`、`memcached://127.0.0.1:11211`，
+/// 省略端口时使用协议的默认端口
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct RemoteConfig {
+    /// 协议
+    pub protocol: RemoteProtocol,
+    /// 服务地址 `host:port`
+    pub addr: String,
+    /// 连接数，并发的加载任务轮流使用
+    pub connections: usize,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        let protocol = RemoteProtocol::default();
+        Self {
+            protocol,
+            addr: format!("127.0.0.1:{}", protocol.default_port()),
+            connections: REMOTE_CONNECTIONS,
+        }
+    }
+}
+
+impl fmt::Display for RemoteConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.protocol, self.addr)
+    }
+}
+
+impl FromStr for RemoteConfig {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (scheme, addr) = s
+            .split_once("://")
+            .ok_or_else(|| AppError::Config(format!("invalid remote `{s}`, expected redis://host:port")))?;
+        let protocol: RemoteProtocol = scheme.parse()?;
+        let addr = addr.trim_end_matches('/');
+        if addr.is_empty() {
+            return Err(AppError::Config(format!("invalid remote `{s}`: missing host")));
+        }
+        // 末段不是端口号（含 IPv6 地址的方括号）时补默认端口
+        let addr = match addr.rsplit_once(':') {
+            Some((_, port)) if port.parse::<u16>().is_ok() && !addr.ends_with(']') => addr.to_owned(),
+            _ => format!("{addr}:{}", protocol.default_port()),
+        };
+        Ok(Self {
+            protocol,
+            addr,
+            ..Self::default()
+        })
+    }
+}
+
+/// 文件读取后端，见 [`crate::FileBackend`]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct FileBackendConfig {
+    /// 数据文件路径，缺省在临时目录下按大小命名，多次运行复用
+    pub path: Option<PathBuf>,
+    /// 数据文件大小（字节），文件不存在或偏小时重新生成
+    pub size: u64,
+    /// 每次回源读取的块大小（字节）
+    pub block: usize,
+}
+
+impl Default for FileBackendConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            size: BACKEND_FILE_SIZE,
+            block: BACKEND_FILE_BLOCK,
+        }
+    }
+}
+
+impl FileBackendConfig {
+    /// 数据文件路径，未指定时为临时目录下的 `lru-bench-backend-{size}.dat`
+    pub fn path(&self) -> PathBuf {
+        self.path
+            .clone()
+            .unwrap_or_else(|| env::temp_dir().join(format!("lru-bench-backend-{}.dat", self.size)))
+    }
+}
+
+/// 两级缓存的磁盘层，见 [`crate::DiskTier`]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct DiskTierConfig {
+    /// 数据文件路径，缺省在临时目录下新建，运行结束后删除
+    pub path: Option<PathBuf>,
+    /// 容量（条目数）
+    pub capacity: u64,
+    /// 每个条目占用的块大小（字节）
+    pub block: usize,
+}
+
+impl Default for DiskTierConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            capacity: DISK_CAPACITY,
+            block: DISK_BLOCK,
+        }
+    }
+}
+
+/// 远程缓存协议
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum RemoteProtocol {
+    /// Redis 及兼容服务（RESP2）
+    #[default]
+    Redis,
+    /// Memcached 文本协议
+    Memcached,
+}
+
+impl RemoteProtocol {
+    /// 协议的默认端口
+    pub fn default_port(self) -> u16 {
+        match self {
+            Self::Redis => 6379,
+            Self::Memcached => 11211,
+        }
+    }
+}
+
+impl fmt::Display for RemoteProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Redis => "redis",
+            Self::Memcached => "memcached",
+        })
+    }
+}
+
+impl FromStr for RemoteProtocol {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "redis" => Self::Redis,
+            "memcached" => Self::Memcached,
+            _ => return Err(AppError::Config(format!("invalid remote protocol `{s}`, expected redis or memcached"))),
+        })
+    }
+}
+
+/// 预热配置
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WarmupConfig {
+    /// 预热 Key 空间大小
+    pub key_space: u64,
+    /// 预热写入次数
+    pub size: u64,
+    /// Zipf 分布参数
+    pub zipf_s: f64,
+    /// 随机种子
+    pub seed: u64,
+    /// 预热策略，由 [`Config::warmup`] 填充
+    #[serde(skip)]
+    pub strategy: WarmupStrategy,
+    /// 工作负载的打散映射，按热度预热时据此选择 key，由 [`Config::warmup`] 填充
+    #[serde(skip)]
+    pub scramble: Option<Scramble>,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self::for_capacity(CACHE_CAPACITY)
+    }
+}
+
+impl WarmupConfig {
+    /// 按缓存容量推导：Key 空间为容量两倍，写入次数等于容量
+    pub fn for_capacity(capacity: u64) -> Self {
+        Self {
+            key_space: capacity * 2,
+            size: capacity,
+            zipf_s: ZIPF_S,
+            seed: bench::WARMUP_SEED,
+            strategy: WarmupStrategy::default(),
+            scramble: None,
+        }
+    }
+
+    /// 指定预热写入次数
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// 指定随机种子
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 指定预热策略
+    pub fn with_strategy(mut self, strategy: WarmupStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// 指定工作负载的打散映射
+    pub fn with_scramble(mut self, scramble: Option<Scramble>) -> Self {
+        self.scramble = scramble;
+        self
+    }
+}
+
+/// 预热策略，写作 `zipf`、`most-popular-first`、`sequential-fill`、`trace-prefix`、`none`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum WarmupStrategy {
+    /// 按 Zipf 分布随机写入，夹杂少量读取
+    #[default]
+    Zipf,
+    /// 只写入最热的 key，由冷到热写入，使最热的 key 最近被使用
+    MostPopularFirst,
+    /// 按 key 顺序依次写入，不考虑热度
+    SequentialFill,
+    /// 回放工作负载的前缀
+    TracePrefix,
+    /// 不预热，测量冷启动
+    None,
+}
+
+impl fmt::Display for WarmupStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Zipf => "zipf",
+            Self::MostPopularFirst => "most-popular-first",
+            Self::SequentialFill => "sequential-fill",
+            Self::TracePrefix => "trace-prefix",
+            Self::None => "none",
+        })
+    }
+}
+
+impl FromStr for WarmupStrategy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "zipf" => Self::Zipf,
+            "most-popular-first" => Self::MostPopularFirst,
+            "sequential-fill" => Self::SequentialFill,
+            "trace-prefix" => Self::TracePrefix,
+            "none" => Self::None,
+            _ => return Err(AppError::Config(format!("invalid warmup strategy `{s}`"))),
+        })
+    }
+}
+
+/// 条目代价模型，写作 `unit`、`sized`；容量仍按条目数配置，代价预算为容量乘以平均代价
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum CostModel {
+    /// 每个条目代价为 1，即按条目数限额
+    #[default]
+    Unit,
+    /// 代价为条目字节数，负载长度由 key 固定派生、大小不一，见 [`sized_cost`]
+    Sized,
+}
+
+impl CostModel {
+    /// 代价函数
+    pub fn cost_fn(self) -> CostFn {
+        match self {
+            Self::Unit => unit_cost,
+            Self::Sized => sized_cost,
+        }
+    }
+
+    /// 每个条目的平均代价
+    pub fn mean(self) -> f64 {
+        match self {
+            Self::Unit => 1.0,
+            // 负载长度在 1..=2 * BYTES_MEDIAN 上均匀分布
+            Self::Sized => ENTRY_HEADER as f64 + BYTES_MEDIAN as f64 + 0.5,
+        }
+    }
+
+    /// 容量（条目数）对应的代价预算
+    pub fn budget(self, capacity: u64) -> u64 {
+        (capacity as f64 * self.mean()).round() as u64
+    }
+}
+
+impl fmt::Display for CostModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unit => "unit",
+            Self::Sized => "sized",
+        })
+    }
+}
+
+impl FromStr for CostModel {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "unit" => Self::Unit,
+            "sized" => Self::Sized,
+            _ => return Err(AppError::Config(format!("invalid cost model `{s}`"))),
+        })
+    }
 }
 
 /// 错误消息常量
@@ -49,5 +1349,9 @@ pub mod messages {
     pub const WORKLOAD_GEN_FAILED: &str = "Failed to generate workload";
     pub const WARMUP_FAILED: &str = "Warmup operation failed";
     pub const CACHE_OPERATION_FAILED: &str = "Cache operation failed";
+    pub const CACHE_BUILD_FAILED: &str = "Failed to build cache";
+    pub const CONFIG_LOAD_FAILED: &str = "Failed to load configuration";
+    pub const REPORT_WRITE_FAILED: &str = "Failed to write report";
+    pub const BASELINE_REGRESSION: &str = "Regression against baseline";
     pub const ZIPF_CREATE_FAILED: &str = "Failed to create Zipf distribution";
 }
\ No newline at end of file
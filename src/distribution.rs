@@ -0,0 +1,180 @@
+//! Key 分布
+//! 与 YCSB 的请求分布对应：均匀、Zipf、打散的 Zipf、偏向最近插入的 Latest、热点集合 Hotspot。
+//! 所有分布采样 [1, key 空间] 内的整数 id，由 [`crate::Key::from_id`] 转为键；
+//! 打散统一由 [`Scramble`] 的随机双射完成
+
+use crate::{AppError, DistributionKind, Result};
+use rand::distr::Distribution;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Key 分布 trait，[`crate::WorkloadGenerator`] 据此采样每个操作的 key
+pub trait KeyDistribution {
+    /// 采样一个 key id
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize;
+
+    /// 生成器插入新 key 后调用，默认忽略
+    #[inline]
+    fn inserted(&mut self, _id: usize) {}
+}
+
+/// [1, n] 内均匀分布
+#[derive(Clone, Copy, Debug)]
+pub struct UniformKeys {
+    n: usize,
+}
+
+impl UniformKeys {
+    pub fn new(n: usize) -> Result<Self> {
+        if n == 0 {
+            return Err(AppError::Config("key space must be > 0".into()));
+        }
+        Ok(Self { n })
+    }
+}
+
+impl KeyDistribution for UniformKeys {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        rng.random_range(1..=self.n)
+    }
+}
+
+/// Zipf 分布，id 即热度排名，1 最热
+#[derive(Clone, Copy, Debug)]
+pub struct ZipfKeys {
+    zipf: rand_distr::Zipf<f64>,
+}
+
+impl ZipfKeys {
+    pub fn new(n: usize, s: f64) -> Result<Self> {
+        Ok(Self {
+            zipf: rand_distr::Zipf::new(n as f64, s)?,
+        })
+    }
+}
+
+impl KeyDistribution for ZipfKeys {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        self.zipf.sample(rng) as usize
+    }
+}
+
+/// Latest：按 Zipf 排名从最近插入的 key 往前数，最新的 key 最热
+///
+/// 初始时 key 空间内的 key 视为按 id 顺序插入
+#[derive(Clone, Copy, Debug)]
+pub struct LatestKeys {
+    zipf: rand_distr::Zipf<f64>,
+    latest: usize,
+}
+
+impl LatestKeys {
+    pub fn new(n: usize, s: f64) -> Result<Self> {
+        Ok(Self {
+            zipf: rand_distr::Zipf::new(n as f64, s)?,
+            latest: n,
+        })
+    }
+}
+
+impl KeyDistribution for LatestKeys {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        let rank = self.zipf.sample(rng) as usize;
+        (self.latest + 1).saturating_sub(rank).max(1)
+    }
+
+    #[inline]
+    fn inserted(&mut self, id: usize) {
+        self.latest = self.latest.max(id);
+    }
+}
+
+/// Hotspot：`hot_ops` 比例的请求均匀落在前 `hot_keys` 比例的 key 上，其余均匀落在剩余 key 上
+#[derive(Clone, Copy, Debug)]
+pub struct HotspotKeys {
+    n: usize,
+    hot: usize,
+    hot_ops: f64,
+}
+
+impl HotspotKeys {
+    pub fn new(n: usize, hot_keys: f64, hot_ops: f64) -> Result<Self> {
+        if n == 0 {
+            return Err(AppError::Config("key space must be > 0".into()));
+        }
+        // 比例已校验在 (0, 1]，乘积不超过 n
+        let hot = ((n as f64 * hot_keys) as usize).clamp(1, n);
+        Ok(Self { n, hot, hot_ops })
+    }
+}
+
+impl KeyDistribution for HotspotKeys {
+    #[inline]
+    fn sample(&mut self, rng: &mut dyn RngCore) -> usize {
+        if self.hot == self.n || rng.random::<f64>() < self.hot_ops {
+            rng.random_range(1..=self.hot)
+        } else {
+            rng.random_range(self.hot + 1..=self.n)
+        }
+    }
+}
+
+/// Fisher-Yates 打散：热度排名到 key id 的随机双射，使 key 的数值与热度无关
+///
+/// 只映射 [1, n]，范围外的 id（扫描 key、新插入的 key）原样保留
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Scramble {
+    perm: Vec<usize>,
+}
+
+impl Scramble {
+    /// 以 `seed` 打乱 [1, n]，相同参数得到相同映射
+    pub fn new(n: usize, seed: u64) -> Self {
+        let mut perm: Vec<usize> = (0..=n).collect();
+        perm[1..].shuffle(&mut StdRng::seed_from_u64(seed ^ SCRAMBLE_SALT));
+        Self { perm }
+    }
+
+    /// 排名对应的 key id
+    #[inline]
+    pub fn map(&self, rank: usize) -> usize {
+        self.perm.get(rank).copied().unwrap_or(rank)
+    }
+
+    /// 下标为排名的映射表，下标 0 保留
+    pub fn into_perm(self) -> Vec<usize> {
+        self.perm
+    }
+}
+
+/// 打散所用随机数与工作负载种子错开，避免与操作序列的随机数相关
+const SCRAMBLE_SALT: u64 = 0x5eed_5c2a_3b1e_0001;
+
+impl DistributionKind {
+    /// 在 [1, n] 上构造分布，`s` 为 Zipf 类分布的参数
+    pub fn build(&self, n: usize, s: f64) -> Result<Box<dyn KeyDistribution>> {
+        self.validate()?;
+        Ok(match *self {
+            Self::Uniform => Box::new(UniformKeys::new(n)?),
+            // 打散的 Zipf 由生成器叠加 [`Scramble`]，见 [`crate::WorkloadConfig::scrambled`]
+            Self::Zipf | Self::ScrambledZipf => Box::new(ZipfKeys::new(n, s)?),
+            Self::Latest => Box::new(LatestKeys::new(n, s)?),
+            Self::Hotspot { hot_keys, hot_ops } => Box::new(HotspotKeys::new(n, hot_keys, hot_ops)?),
+        })
+    }
+}
+
+/// FNV-1a 64 位哈希，与进程无关，字符串键的 id 等由此计算
+#[inline]
+pub(crate) fn fnv64(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
@@ -1,45 +1,71 @@
 //! 错误处理模块
 //! 定义了所有应用级别的错误类型
 
-use std::fmt;
+use rand_distr::ZipfError;
+use thiserror::Error;
 
 /// 应用主错误类型
-#[derive(Debug, Clone)]
+///
+/// 满足 `Send + Sync + 'static`，可直接转换为 anyhow/eyre 错误
+#[derive(Debug, Error)]
 pub enum AppError {
     /// 运行时创建错误
-    RuntimeCreate(String),
+    #[error("Runtime create error")]
+    RuntimeCreate(#[source] std::io::Error),
     /// Zipf分布创建错误
-    ZipfCreate(String),
+    #[error("Zipf distribution create error")]
+    ZipfCreate(#[from] ZipfError),
     /// 缓存操作错误
+    #[error("Cache operation error: {0}")]
     CacheOperation(String),
     /// IO错误
-    Io(String),
+    #[error("IO error")]
+    Io(#[from] std::io::Error),
     /// 配置错误
+    #[error("Configuration error: {0}")]
     Config(String),
+    /// 配置文件解析错误
+    #[error("Config file parse error")]
+    Toml(#[from] toml::de::Error),
+    /// 场景文件 YAML 解析错误
+    #[error("YAML parse error")]
+    Yaml(#[from] serde_yaml_ng::Error),
+    /// 工作负载文件格式错误
+    #[error("Invalid workload file: {0}")]
+    InvalidWorkload(String),
+    /// 延迟直方图创建错误
+    #[error("Histogram create error")]
+    Histogram(#[from] hdrhistogram::CreationError),
+    /// 报告序列化错误
+    #[error("JSON serialize error")]
+    Json(#[from] serde_json::Error),
+    /// 中断信号处理安装错误
+    #[error("Signal handler install error")]
+    Signal(#[from] ctrlc::Error),
+    /// CPU 采样错误
+    #[cfg(feature = "profiling")]
+    #[error("Profiler error")]
+    Profile(#[from] pprof::Error),
+    /// 与基线对比存在回归
+    #[error("{0} regression(s) against baseline")]
+    Regression(usize),
+    /// 附加上下文的错误
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<AppError>,
+    },
 }
 
-impl fmt::Display for AppError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            AppError::RuntimeCreate(msg) => write!(f, "Runtime create error: {}", msg),
-            AppError::ZipfCreate(msg) => write!(f, "Zipf distribution create error: {}", msg),
-            AppError::CacheOperation(msg) => write!(f, "Cache operation error: {}", msg),
-            AppError::Io(msg) => write!(f, "IO error: {}", msg),
-            AppError::Config(msg) => write!(f, "Configuration error: {}", msg),
-        }
-    }
-}
-
-impl std::error::Error for AppError {}
-
 /// 结果类型别名
 pub type Result<T> = std::result::Result<T, AppError>;
 
-impl From<std::io::Error> for AppError {
-    fn from(err: std::io::Error) -> Self {
-        AppError::Io(err.to_string())
-    }
-}
+/// 编译期保证错误类型可跨线程传递
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<AppError>();
+};
 
 /// 错误上下文扩展trait
 pub trait ErrorContext<T> {
@@ -49,12 +75,9 @@ pub trait ErrorContext<T> {
 
 impl<T> ErrorContext<T> for Result<T> {
     fn with_context(self, context: &str) -> Result<T> {
-        self.map_err(|e| match e {
-            AppError::RuntimeCreate(msg) => AppError::RuntimeCreate(format!("{}: {}", context, msg)),
-            AppError::ZipfCreate(msg) => AppError::ZipfCreate(format!("{}: {}", context, msg)),
-            AppError::CacheOperation(msg) => AppError::CacheOperation(format!("{}: {}", context, msg)),
-            AppError::Io(msg) => AppError::Io(format!("{}: {}", context, msg)),
-            AppError::Config(msg) => AppError::Config(format!("{}: {}", context, msg)),
+        self.map_err(|e| AppError::Context {
+            context: context.to_owned(),
+            source: Box::new(e),
         })
     }
-}
\ No newline at end of file
+}
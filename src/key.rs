@@ -0,0 +1,60 @@
+//! 缓存键模块
+//! 定义工作负载中使用的键类型，生成器以整数 id 采样后转换为具体键
+
+use crate::distribution::fnv64;
+use std::hash::Hash;
+
+/// 字符串键前缀
+const PREFIX: &str = "key:";
+
+/// 缓存键 trait
+pub trait Key: Clone + Hash + Eq {
+    /// 由采样得到的整数 id 构造键
+    fn from_id(id: usize) -> Self;
+
+    /// 键对应的整数 id，供 [`crate::Value::load`] 派生固定的回源值
+    fn id(&self) -> usize;
+
+    /// 按 id 顺序排在其后第 `n` 个的键，供范围扫描展开；超出 id 范围或无法解析时为 `None`
+    fn successor(&self, n: usize) -> Option<Self>;
+}
+
+impl Key for usize {
+    #[inline]
+    fn from_id(id: usize) -> Self {
+        id
+    }
+
+    #[inline]
+    fn id(&self) -> usize {
+        *self
+    }
+
+    #[inline]
+    fn successor(&self, n: usize) -> Option<Self> {
+        self.checked_add(n)
+    }
+}
+
+impl Key for String {
+    /// 形如 `key:42`，接近真实业务中的字符串键
+    #[inline]
+    fn from_id(id: usize) -> Self {
+        format!("{PREFIX}{id}")
+    }
+
+    /// FNV-1a 哈希，与进程无关，保证同一键的回源值固定
+    #[inline]
+    fn id(&self) -> usize {
+        fnv64(self.as_bytes()) as usize
+    }
+
+    /// 按 [`Key::from_id`] 的格式解析出 id 后顺延，哈希得到的 [`Key::id`] 不连续
+    fn successor(&self, n: usize) -> Option<Self> {
+        if n == 0 {
+            return Some(self.clone());
+        }
+        let id: usize = self.strip_prefix(PREFIX)?.parse().ok()?;
+        id.checked_add(n).map(Self::from_id)
+    }
+}
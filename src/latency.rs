@@ -0,0 +1,93 @@
+//! 延迟统计模块
+//! 基于 HDR 直方图，分别记录读、写操作的单次延迟
+
+use crate::{Outcome, Result};
+use hdrhistogram::Histogram;
+use serde::Serialize;
+use std::time::Duration;
+
+/// 可记录的最大延迟（纳秒），超出部分按最大值计
+const MAX_NANOS: u64 = 60_000_000_000;
+
+/// 有效数字位数
+const SIGFIG: u8 = 3;
+
+/// 读、写分开的延迟记录器
+#[derive(Clone, Debug)]
+pub struct LatencyRecorder {
+    reads: Histogram<u64>,
+    writes: Histogram<u64>,
+}
+
+impl LatencyRecorder {
+    /// 创建空记录器
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            reads: Histogram::new_with_bounds(1, MAX_NANOS, SIGFIG)?,
+            writes: Histogram::new_with_bounds(1, MAX_NANOS, SIGFIG)?,
+        })
+    }
+
+    /// 记录一次操作，命中与未命中计为读，更新、插入与删除计为写
+    #[inline]
+    pub fn record(&mut self, outcome: Outcome, latency: Duration) {
+        let hist = match outcome {
+            Outcome::Write | Outcome::Insert | Outcome::Delete => &mut self.writes,
+            Outcome::Hit | Outcome::Miss | Outcome::Failed => &mut self.reads,
+            // 扫描耗时随长度变化，不计入单 key 读延迟
+            Outcome::Scan { .. } => return,
+        };
+        // 纳秒数在 u64 内可表示约 584 年，as 转换安全
+        hist.saturating_record(latency.as_nanos() as u64);
+    }
+
+    /// 合并另一记录器的数据
+    pub fn merge(&mut self, other: &Self) {
+        // 两者边界相同，add 不会失败
+        let _ = self.reads.add(&other.reads);
+        let _ = self.writes.add(&other.writes);
+    }
+
+    /// 读、写延迟分位数
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            reads: Percentiles::of(&self.reads),
+            writes: Percentiles::of(&self.writes),
+        }
+    }
+}
+
+/// 读、写延迟分位数
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct LatencySummary {
+    pub reads: Percentiles,
+    pub writes: Percentiles,
+}
+
+/// 延迟分位数（纳秒）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Percentiles {
+    /// 样本数
+    pub count: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+impl Percentiles {
+    fn of(hist: &Histogram<u64>) -> Self {
+        let q = |q: f64| hist.value_at_quantile(q);
+        Self {
+            count: hist.len(),
+            p50: q(0.5),
+            p90: q(0.9),
+            p99: q(0.99),
+            p999: q(0.999),
+            max: hist.max(),
+        }
+    }
+}
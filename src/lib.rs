@@ -8,7 +8,115 @@
 //! - Zipf分布模拟真实访问模式
 //! - 增强的预热策略
 //! - 详细的性能报告
+//!
+//! 典型用法：配置 [`WorkloadConfig`] 生成操作序列，交给 [`CacheRunner`] 运行，得到 [`RunStats`]。
+//!
+//! 可复现性：
+//! - 工作负载与预热序列只由各自配置的种子决定，同一配置下逐位一致；缺省的 [`rand::rngs::StdRng`]
+//!   不保证跨 rand 版本一致，需要时以 [`WorkloadGenerator::with_rng`] / [`WarmupManager::with_rng`] 换用 `rand_chacha`
+//! - 回源延迟与故障的采样由 [`Config::backend_seed`] 决定，每次运行（每个缓存）从同一种子开始；
+//!   直接以 [`CacheRunner::new`] 创建且未调用 [`CacheRunner::with_seed`] 时每次随机。
+//!   缺省的 [`rand::rngs::SmallRng`] 在 32 与 64 位平台上序列不同，可用 [`CacheRunner::with_rng`] 替换
+//! - 真实时钟下的耗时与吞吐受机器负载影响，启用模拟时间后模型耗时才可复现；多线程运行的交错顺序不可复现
+//! - [`Config::deterministic`] 把以上种子统一由工作负载种子派生并启用模拟时间，用于对比调整前后的结果
 
 pub mod config;
 pub mod error;
-pub mod cache;
\ No newline at end of file
+pub mod backend;
+pub mod cache;
+pub mod distribution;
+pub mod key;
+pub mod analysis;
+pub mod latency;
+pub mod mem;
+pub mod perf;
+pub mod profile;
+pub mod report;
+pub mod runtime;
+pub mod scenario;
+pub mod series;
+pub mod sweep;
+pub mod trace;
+pub mod value;
+pub mod verify;
+
+pub use analysis::mrc::Mrc;
+pub use analysis::opt::belady;
+pub use analysis::reuse_distance::ReuseHistogram;
+pub use analysis::stats::{WORKING_SET_SHARE, WorkloadStats};
+pub use analysis::tiers::{PopularityTier, PopularityTiers, TierStats};
+pub use analysis::working_set::{WorkingSetPoint, working_set_timeline};
+pub use backend::file::FileBackend;
+pub use backend::{
+    Backend, BackendModel, Fault, Faults, LatencyModel, LatencyModelBuilder, LogNormalLatency, ParetoLatency,
+    simulate_backend_fetch, simulate_backend_latency,
+};
+#[cfg(feature = "hashlink")]
+pub use cache::arc::ArcCache;
+pub use cache::baseline::NoCache;
+#[cfg(feature = "dashmap")]
+pub use cache::capped::{CappedDashMap, MapEviction};
+pub use cache::clock::Clock;
+pub use cache::concurrent::{ConcurrentAdapter, ConcurrentCacheOps};
+pub use cache::fixed::FixedLru;
+pub use cache::future::{AsyncAdapter, AsyncCacheOps};
+pub use cache::interrupt::{INTERRUPT_EXIT_CODE, install_interrupt_handler, interrupted};
+pub use cache::converge::Converged;
+pub use cache::cost::{CostBounded, CostFn, ENTRY_HEADER, sized_cost, unit_cost};
+#[cfg(feature = "quick_cache")]
+pub use cache::cost::CostWeighter;
+pub use cache::disk::DiskTier;
+pub use cache::fairness::{CapacityFit, FILL_TOLERANCE};
+pub use cache::gdsf::Gdsf;
+pub use cache::kind::CacheKind;
+pub use cache::loads::{LoadCounter, dogpile_workloads};
+#[cfg(any(feature = "hashlink", feature = "lru"))]
+pub use cache::locked::{CacheLock, Locked, SharedRead};
+#[cfg(feature = "lru")]
+pub use cache::locked::MutexLru;
+#[cfg(feature = "hashlink")]
+pub use cache::locked::RwLockHashlink;
+pub use cache::lockstep::{Divergence, Lockstep};
+pub use cache::micro::replay_sync;
+pub use cache::occupancy::Occupancy;
+pub use cache::progress::{Progress, ProgressHook};
+pub use cache::remote::RemoteCache;
+pub use cache::s3fifo::S3Fifo;
+#[cfg(feature = "lru")]
+pub use cache::sharded::{SEEDED_SHARDS, ShardedLru};
+pub use cache::sieve::Sieve;
+pub use cache::time::{MockClock, RealClock, TimeSource};
+pub use cache::ttl::TtlCache;
+pub use cache::{CacheOps, CacheRunner, Op, Outcome, RunStats, WarmupManager, WorkloadGenerator};
+#[cfg(feature = "lru")]
+pub use cache::lru_cache;
+#[cfg(any(feature = "mini-moka", feature = "moka"))]
+pub use cache::OptimizedMokaCacheBuilder;
+pub use config::{
+    Baseline, Config, Convergence, CostModel, DiskTierConfig, DistributionKind, Expiration, FileBackendConfig, RemoteConfig, RemoteProtocol, ScanConfig, ValueSize,
+    WarmupConfig, WarmupStrategy, WorkloadConfig, WorkloadPreset, WorkloadSize,
+};
+pub use distribution::{HotspotKeys, KeyDistribution, LatestKeys, Scramble, UniformKeys, ZipfKeys};
+pub use error::{AppError, ErrorContext, Result};
+pub use key::Key;
+pub use latency::{LatencyRecorder, LatencySummary, Percentiles};
+#[cfg(feature = "mem")]
+pub use mem::CountingAlloc;
+pub use mem::{MemProbe, MemUsage};
+pub use perf::{PerfCounters, PerfProbe};
+pub use profile::Profiler;
+pub use report::compare::{BaselineReport, BaselineResult, Metric, Regression};
+pub use report::csv::CSV_HEADER;
+pub use report::matrix::{ComparisonMatrix, Pairwise};
+pub use report::{CacheResult, Report};
+pub use runtime::BenchRuntime;
+pub use scenario::{Scenario, ScenarioMetrics, ScenarioMode, ScenarioRegistry};
+pub use series::{SeriesPoint, SeriesRecorder};
+pub use sweep::{
+    SweepAxis, SweepConfig, SweepPoint, render_table, sweep_capacity, sweep_capacity_ops, sweep_zipf,
+};
+pub use trace::{
+    KeyMap, MmapOps, OpView, TraceFormat, TraceWorkload, load_entries, save_entries, save_ops,
+};
+pub use value::{SizeSampler, Value};
+pub use verify::{DEFERRED_EVICTION, GOLDEN_CAPACITY, NONDETERMINISTIC, ResidentDiff, Transcript, golden_ops, random_ops};
@@ -1,19 +1,1012 @@
-//! 缓存基准测试主模块
-//! 
-//! 这个项目提供了多种LRU缓存实现的性能基准测试，
-//! 包括Hashlink、LRU和Mini-Moka三种实现。
-//! 
-//! 特性：
-//! - 使用Compio异步运行时
-//! - Zipf分布模拟真实访问模式
-//! - 增强的预热策略
-//! - 详细的性能报告
-
-pub mod config;
-pub mod error;
-pub mod cache;
-
-fn main() {
-    println!("Cache Benchmark Suite");
-    println!("Run `./run_bench.sh` to execute benchmarks");
+//! 缓存基准测试命令行
+//!
+//! 不经过 Criterion，直接对单个缓存运行工作负载并输出命中率与延迟，例如：
+//! `lru-bench run --cache hashlink --zipf-s 1.2 --capacity 10000`
+
+use cache_bench::{
+    AppError, BenchRuntime, CacheKind, CapacityFit, CostModel, Lockstep, CacheResult, Backend, BackendModel, CacheRunner, Config, Converged, DiskTier, DistributionKind, FileBackend, WorkloadPreset, LatencyRecorder, MemProbe, MemUsage, MockClock, Mrc, Op,
+    PerfCounters, PerfProbe, Percentiles, PopularityTier, Profiler, PopularityTiers, RemoteCache, RemoteConfig, Report, Result, ReuseHistogram, RunStats, ScenarioMode, ScenarioRegistry, SeriesPoint, SeriesRecorder, SweepAxis, SweepConfig, TimeSource, TraceFormat, TraceWorkload,
+    WarmupConfig, WarmupManager, WarmupStrategy, WORKING_SET_SHARE, WorkingSetPoint, WorkloadStats, belady, render_table, sweep_capacity_ops, sweep_zipf, working_set_timeline,
+    INTERRUPT_EXIT_CODE, install_interrupt_handler, interrupted,
+};
+use cache_bench::config::{LOCKSTEP_DIVERGENCES, OCCUPANCY_EVERY, PROFILE_DIR};
+use cache_bench::verify::NONDETERMINISTIC;
+use clap::{Args, Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "mem")]
+#[global_allocator]
+static ALLOC: cache_bench::CountingAlloc = cache_bench::CountingAlloc;
+
+#[derive(Parser)]
+#[command(name = "lru-bench", version, about = "Cache benchmark suite")]
+struct Cli {
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// 对单个缓存运行一次工作负载
+    Run {
+        /// 缓存实现
+        #[arg(long, default_value = "hashlink")]
+        cache: CacheKind,
+        /// 每隔多少操作采样一次命中率并输出时间序列
+        #[arg(long)]
+        series: Option<usize>,
+        /// 以最近多少操作为窗口统计工作集大小并输出时间线
+        #[arg(long)]
+        working_set: Option<usize>,
+        /// 运行前输出工作负载统计
+        #[arg(long)]
+        stats: bool,
+        #[command(flatten)]
+        trace: TraceArgs,
+        #[command(flatten)]
+        params: Params,
+    },
+    /// 对外部缓存进程（Redis / Memcached）运行一次工作负载，容量由服务端决定
+    Remote {
+        /// 服务地址，如 redis://127.0.0.1:6379、memcached://127.0.0.1:11211，缺省取配置中的 remote
+        url: Option<RemoteConfig>,
+        /// 连接数，并发的加载任务轮流使用
+        #[arg(long)]
+        connections: Option<usize>,
+        /// 每隔多少操作采样一次命中率并输出时间序列
+        #[arg(long)]
+        series: Option<usize>,
+        /// 以最近多少操作为窗口统计工作集大小并输出时间线
+        #[arg(long)]
+        working_set: Option<usize>,
+        /// 运行前输出工作负载统计
+        #[arg(long)]
+        stats: bool,
+        #[command(flatten)]
+        trace: TraceArgs,
+        #[command(flatten)]
+        params: Params,
+    },
+    /// 依次对场景中的各个缓存运行工作负载；不指定场景时列出所有场景
+    Scenario {
+        /// 场景名
+        name: Option<String>,
+        #[command(flatten)]
+        params: Params,
+    },
+    /// 只生成工作负载并输出统计，检查配置是否合理
+    Stats {
+        #[command(flatten)]
+        trace: TraceArgs,
+        #[command(flatten)]
+        params: Params,
+    },
+    /// 把同一操作序列逐个同步交给多个缓存，输出各自命中率与最先出现的命中分歧
+    Lockstep {
+        /// 参与对拍的缓存，逗号分隔，默认全部
+        #[arg(long, value_delimiter = ',')]
+        caches: Vec<CacheKind>,
+        /// 输出的分歧数
+        #[arg(long, default_value_t = LOCKSTEP_DIVERGENCES)]
+        limit: usize,
+        /// 运行前输出工作负载统计
+        #[arg(long)]
+        stats: bool,
+        #[command(flatten)]
+        trace: TraceArgs,
+        #[command(flatten)]
+        params: Params,
+    },
+    /// 按容量扫描各缓存的命中率
+    Sweep {
+        /// 参与扫描的缓存，逗号分隔，默认全部
+        #[arg(long, value_delimiter = ',')]
+        caches: Vec<CacheKind>,
+        /// 起始容量（Key 空间百分比）
+        #[arg(long, default_value_t = 1.0)]
+        from: f64,
+        /// 结束容量（Key 空间百分比）
+        #[arg(long, default_value_t = 100.0)]
+        to: f64,
+        /// 取点数量
+        #[arg(long, default_value_t = 10)]
+        steps: usize,
+        /// 改为在固定容量下扫描这些 Zipf 参数，逗号分隔
+        #[arg(long, value_delimiter = ',')]
+        zipf: Vec<f64>,
+        /// 同时按该采样率（0, 1] 计算理论 LRU 的缺失率曲线
+        #[arg(long, conflicts_with = "zipf")]
+        mrc: Option<f64>,
+        #[command(flatten)]
+        trace: TraceArgs,
+        #[command(flatten)]
+        params: Params,
+    },
+}
+
+/// 以追踪文件代替合成工作负载
+#[derive(Args)]
+struct TraceArgs {
+    /// 追踪文件路径，回放时不做预热
+    #[arg(long)]
+    trace: Option<PathBuf>,
+    /// 追踪格式，缺省时按文件头识别
+    #[arg(long, requires = "trace")]
+    trace_format: Option<TraceFormat>,
+}
+
+impl TraceArgs {
+    /// 加载追踪文件，未指定时返回 None
+    fn load(&self) -> Result<Option<TraceWorkload>> {
+        self.trace
+            .as_ref()
+            .map(|path| TraceWorkload::open(path, self.trace_format))
+            .transpose()
+    }
+}
+
+/// 覆盖 bench.toml / 环境变量中的参数
+#[derive(Args)]
+struct Params {
+    /// 基准场景，先于其余命令行参数应用
+    #[arg(long)]
+    scenario: Option<String>,
+    /// 额外的场景文件（TOML / YAML），可重复
+    #[arg(long)]
+    scenario_file: Vec<PathBuf>,
+    /// 缓存容量
+    #[arg(long)]
+    capacity: Option<u64>,
+    /// 命名的工作负载规模：smoke、full 或配置文件 `[sizes.<名称>]` 中定义的规模
+    #[arg(long)]
+    size: Option<String>,
+    /// Key 空间大小
+    #[arg(long)]
+    keys: Option<usize>,
+    /// 操作数量
+    #[arg(long)]
+    ops: Option<usize>,
+    /// Zipf 分布参数
+    #[arg(long)]
+    zipf_s: Option<f64>,
+    /// 读操作比例
+    #[arg(long)]
+    read_ratio: Option<f64>,
+    /// 工作负载随机种子
+    #[arg(long)]
+    seed: Option<u64>,
+    /// 热点迁移阶段数
+    #[arg(long)]
+    phases: Option<usize>,
+    /// 每隔多少热点操作做一次顺序扫描
+    #[arg(long)]
+    scan_every: Option<usize>,
+    /// 每次扫描的 key 数量
+    #[arg(long)]
+    scan_len: Option<usize>,
+    /// 后端不存在的 key 所占比例
+    #[arg(long)]
+    missing_ratio: Option<f64>,
+    /// 删除（失效）操作所占比例
+    #[arg(long)]
+    delete_ratio: Option<f64>,
+    /// 写操作中插入新 key 的比例，其余为更新
+    #[arg(long)]
+    insert_ratio: Option<f64>,
+    /// YCSB 预设：ycsb-a … ycsb-f，先于其他工作负载参数应用
+    #[arg(long)]
+    workload: Option<WorkloadPreset>,
+    /// 以随机排列打散热度排名到 key 的映射
+    #[arg(long)]
+    scramble: bool,
+    /// key 分布：uniform、zipf、scrambled-zipf、latest、hotspot:热点 key 比例:热点请求比例
+    #[arg(long)]
+    distribution: Option<DistributionKind>,
+    /// 读操作中范围扫描的比例
+    #[arg(long)]
+    range_ratio: Option<f64>,
+    /// 范围扫描的 key 数
+    #[arg(long)]
+    range_len: Option<usize>,
+    /// 预热策略：zipf、most-popular-first、sequential-fill、trace-prefix、none
+    #[arg(long)]
+    warmup: Option<WarmupStrategy>,
+    /// 条目代价模型：unit 按条目数限额，sized 按条目字节数限额，预算为容量乘以平均代价
+    #[arg(long)]
+    cost: Option<CostModel>,
+    /// 预热至命中率收敛，而非固定长度
+    #[arg(long)]
+    converge: bool,
+    /// 收敛判定阈值（百分点），隐含 --converge
+    #[arg(long)]
+    converge_epsilon: Option<f64>,
+    /// 收敛检测窗口操作数，隐含 --converge
+    #[arg(long)]
+    converge_window: Option<usize>,
+    /// 缓存不存在的 key 的空结果，而非每次重新回源
+    #[arg(long)]
+    negative_cache: bool,
+    /// 模拟时间：回源不真实睡眠，按模型时间报告耗时与延迟
+    #[arg(long)]
+    simulated_time: bool,
+    /// 确定性模式：以 --seed 为主种子派生预热与回源的种子并启用模拟时间，多次运行结果逐位一致
+    #[arg(long)]
+    deterministic: bool,
+    /// 在标准错误输出运行进度：已完成操作数、当前命中率与预计剩余时间
+    #[arg(long)]
+    progress: bool,
+    /// 真实时间预算，如 10m，超出后停止并导出已完成的部分结果
+    #[arg(long, value_parser = humantime::parse_duration)]
+    budget: Option<Duration>,
+    /// 回源延迟模型，如 zero、constant:1ms、uniform:1ms..2ms、lognormal:1ms:0.5、pareto:1ms:1.5
+    #[arg(long)]
+    backend: Option<Backend>,
+    /// 回源改为从该数据文件真实读取一个块（不存在时生成），替代延迟模型
+    #[arg(long)]
+    backend_file: Option<PathBuf>,
+    /// 最小回源延迟，如 1ms，指定后使用均匀分布
+    #[arg(long, value_parser = humantime::parse_duration)]
+    min_delay: Option<Duration>,
+    /// 最大回源延迟，如 2ms
+    #[arg(long, value_parser = humantime::parse_duration)]
+    max_delay: Option<Duration>,
+    /// 回源出错概率
+    #[arg(long)]
+    error_rate: Option<f64>,
+    /// 回源超时概率
+    #[arg(long)]
+    timeout_rate: Option<f64>,
+    /// 回源超时时长，如 50ms
+    #[arg(long, value_parser = humantime::parse_duration)]
+    timeout: Option<Duration>,
+    /// 回源失败后的重试次数
+    #[arg(long)]
+    retries: Option<u32>,
+    /// 启用磁盘层并设置其容量（条目数），内存未命中时先查磁盘再回源
+    #[arg(long)]
+    disk_capacity: Option<u64>,
+    /// 磁盘层数据文件，缺省使用临时文件，隐含启用磁盘层
+    #[arg(long)]
+    disk_path: Option<PathBuf>,
+    /// 工作负载缓存目录，按配置指纹复用已生成的工作负载
+    #[arg(long)]
+    workload_cache: Option<PathBuf>,
+    /// 将结果以 JSON 写入该文件
+    #[arg(long)]
+    report: Option<PathBuf>,
+    /// 将结果追加到该 CSV 文件
+    #[arg(long)]
+    csv: Option<PathBuf>,
+    /// 将结果图表写入该 HTML 文件
+    #[arg(long)]
+    html: Option<PathBuf>,
+    /// 将结果摘要以 Markdown 表格写入该文件
+    #[arg(long)]
+    markdown: Option<PathBuf>,
+    /// 火焰图输出目录（需启用 profiling 特性）
+    #[arg(long)]
+    profile_dir: Option<PathBuf>,
+    /// 与该 JSON 基线报告对比，出现回归时以非零状态退出
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// 允许的吞吐量下降（百分比）
+    #[arg(long)]
+    max_throughput_drop: Option<f64>,
+    /// 允许的命中率下降（百分点）
+    #[arg(long)]
+    max_hit_rate_drop: Option<f64>,
+}
+
+impl Params {
+    /// 在已加载配置上应用命令行参数并校验
+    fn apply(self, mut cfg: Config) -> Result<Config> {
+        cfg.scenario_files.extend(self.scenario_file);
+        if self.scenario.is_some() {
+            cfg.scenario = self.scenario;
+            cfg.apply_scenario()?;
+        }
+        if let Some(preset) = self.workload {
+            preset.apply(&mut cfg.workload);
+        }
+        if let Some(name) = &self.size {
+            cfg.size(name)
+                .ok_or_else(|| AppError::Config(format!("--size: unknown size `{name}`")))?
+                .apply(&mut cfg.workload);
+        }
+        let w = &mut cfg.workload;
+        set(&mut cfg.capacity, self.capacity);
+        set(&mut w.total_keys, self.keys);
+        set(&mut w.size, self.ops);
+        set(&mut w.zipf_s, self.zipf_s);
+        set(&mut w.read_ratio, self.read_ratio);
+        set(&mut w.seed, self.seed);
+        set(&mut w.phases, self.phases);
+        set(&mut w.missing_ratio, self.missing_ratio);
+        set(&mut w.delete_ratio, self.delete_ratio);
+        set(&mut w.insert_ratio, self.insert_ratio);
+        set(&mut w.range_ratio, self.range_ratio);
+        set(&mut w.distribution, self.distribution);
+        set(&mut w.range_len, self.range_len);
+        set(&mut cfg.warmup_strategy, self.warmup);
+        set(&mut cfg.cost, self.cost);
+        if self.converge || self.converge_epsilon.is_some() || self.converge_window.is_some() {
+            let conv = cfg.convergence.get_or_insert_default();
+            set(&mut conv.epsilon, self.converge_epsilon);
+            set(&mut conv.window, self.converge_window);
+        }
+        if self.scan_every.is_some() || self.scan_len.is_some() {
+            let scan = w.scan.get_or_insert_default();
+            set(&mut scan.every, self.scan_every);
+            set(&mut scan.len, self.scan_len);
+        }
+        set(&mut cfg.workload_cache, self.workload_cache.map(Some));
+        set(&mut cfg.report, self.report.map(Some));
+        set(&mut cfg.csv, self.csv.map(Some));
+        set(&mut cfg.html, self.html.map(Some));
+        set(&mut cfg.markdown, self.markdown.map(Some));
+        set(&mut cfg.profile_dir, self.profile_dir.map(Some));
+        let b = &mut cfg.baseline;
+        set(&mut b.path, self.baseline.map(Some));
+        set(&mut b.max_throughput_drop, self.max_throughput_drop);
+        set(&mut b.max_hit_rate_drop, self.max_hit_rate_drop);
+        set(&mut cfg.latency, self.backend);
+        cfg.latency = cfg.latency.with_bounds(self.min_delay, self.max_delay)?;
+        if let Some(path) = self.backend_file {
+            cfg.backend_file.get_or_insert_default().path = Some(path);
+        }
+        let f = &mut cfg.faults;
+        set(&mut f.error_rate, self.error_rate);
+        set(&mut f.timeout_rate, self.timeout_rate);
+        set(&mut f.timeout, self.timeout);
+        set(&mut f.retries, self.retries);
+        if self.disk_capacity.is_some() || self.disk_path.is_some() {
+            let disk = cfg.disk.get_or_insert_default();
+            set(&mut disk.capacity, self.disk_capacity);
+            set(&mut disk.path, self.disk_path.map(Some));
+        }
+        w.scramble |= self.scramble;
+        cfg.negative_cache |= self.negative_cache;
+        cfg.simulated_time |= self.simulated_time;
+        cfg.deterministic |= self.deterministic;
+        cfg.progress |= self.progress;
+        set(&mut cfg.budget, self.budget.map(Some));
+        cfg.apply_deterministic();
+        cfg.validate()?;
+        Ok(cfg)
+    }
+}
+
+fn set<T>(slot: &mut T, val: Option<T>) {
+    if let Some(val) = val {
+        *slot = val;
+    }
+}
+
+fn main() -> Result<()> {
+    install_interrupt_handler()?;
+    let result = dispatch(Cli::parse().cmd);
+    // 中断后已写出部分结果，以中断退出码结束
+    if interrupted() && result.is_ok() {
+        std::process::exit(INTERRUPT_EXIT_CODE);
+    }
+    result
+}
+
+fn dispatch(cmd: Cmd) -> Result<()> {
+    match cmd {
+        Cmd::Run {
+            cache,
+            series,
+            working_set,
+            stats,
+            trace,
+            params,
+        } => {
+            let cfg = params.apply(Config::load()?)?;
+            let result = run(cache, series, working_set, stats, trace.load()?, &cfg)?;
+            write_report(cfg, [result])
+        }
+        Cmd::Remote {
+            url,
+            connections,
+            series,
+            working_set,
+            stats,
+            trace,
+            params,
+        } => {
+            let mut cfg = params.apply(Config::load()?)?;
+            if cfg.simulated_time {
+                return Err(AppError::Config("simulated time is not supported against a remote cache".into()));
+            }
+            let remote = cfg.remote.get_or_insert_default();
+            if let Some(url) = url {
+                remote.protocol = url.protocol;
+                remote.addr = url.addr;
+            }
+            set(&mut remote.connections, connections);
+            cfg.validate()?;
+            let result = run_remote(series, working_set, stats, trace.load()?, &cfg)?;
+            write_report(cfg, [result])
+        }
+        Cmd::Scenario { name, mut params } => {
+            let Some(name) = name else {
+                let cfg = params.apply(Config::load()?)?;
+                for s in ScenarioRegistry::for_config(&cfg)?.iter() {
+                    println!("{:<16} {}", s.name, s.description);
+                }
+                return Ok(());
+            };
+            params.scenario = Some(name.clone());
+            let cfg = params.apply(Config::load()?)?;
+            let scenario = ScenarioRegistry::for_config(&cfg)?.get(&name)?.clone();
+            if scenario.mode != ScenarioMode::Kinds {
+                return Err(AppError::Config(format!("scenario `{name}` runs only in the benchmark")));
+            }
+            let metrics = scenario.metrics;
+            let started = Instant::now();
+            let mut results = Vec::new();
+            for (i, kind) in scenario.caches().into_iter().enumerate() {
+                if interrupted() {
+                    println!("\npartial: interrupted, skipped {}", kind.key());
+                    continue;
+                }
+                // 时间预算由场景内的各缓存共享
+                let mut cfg = cfg.clone();
+                if let Some(budget) = cfg.budget {
+                    let Some(left) = budget.checked_sub(started.elapsed()).filter(|d| !d.is_zero()) else {
+                        println!("\npartial: time budget exhausted, skipped {}", kind.key());
+                        continue;
+                    };
+                    cfg.budget = Some(left);
+                }
+                println!("\n== {name}: {} ==", kind.key());
+                results.push(run(kind, metrics.series, metrics.working_set, metrics.stats && i == 0, None, &cfg)?);
+            }
+            write_report(cfg, results)
+        }
+        Cmd::Stats { trace, params } => {
+            let cfg = params.apply(Config::load()?)?;
+            match trace.load()? {
+                Some(tw) => print_workload_stats(tw.ops(), cfg.capacity, None),
+                None => print_workload_stats(&cfg.workload_ops()?, cfg.capacity, Some(cfg.workload.read_ratio)),
+            }
+        }
+        Cmd::Lockstep {
+            caches,
+            limit,
+            stats,
+            trace,
+            params,
+        } => {
+            let cfg = params.apply(Config::load()?)?;
+            let caches = if caches.is_empty() { CacheKind::ALL.to_vec() } else { caches };
+            let (ops, warmup) = workload(stats, trace.load()?, &cfg)?;
+            let warmup_ops = match warmup {
+                Some(warmup) => WarmupManager::new(warmup).generate_warmup_ops_for(&ops)?,
+                None => Vec::new(),
+            };
+            print_lockstep(&Lockstep::run(&caches, cfg.capacity, &warmup_ops, &ops, limit)?);
+            Ok(())
+        }
+        Cmd::Sweep {
+            caches,
+            from,
+            to,
+            steps,
+            zipf,
+            mrc,
+            trace,
+            params,
+        } => {
+            let mut sweep = SweepConfig::default();
+            if !caches.is_empty() {
+                sweep.caches = caches;
+            }
+            sweep.from_pct = from;
+            sweep.to_pct = to;
+            sweep.steps = steps;
+            let cfg = params.apply(Config::load()?)?;
+            let runtime = BenchRuntime::new()?;
+            if !zipf.is_empty() {
+                let points = runtime.block_on(sweep_zipf(&cfg, &zipf, &sweep.caches))?;
+                println!("{}", render_table(&points, SweepAxis::ZipfS));
+                print_sweep_partial(points.len(), zipf.len() * (sweep.caches.len() + 1));
+                return write_report(cfg, points.iter().map(|p| p.to_result()));
+            }
+            let (ops, key_space) = match trace.load()? {
+                Some(tw) => {
+                    sweep.warmup = false;
+                    let key_space = tw.key_space();
+                    (tw.generate(), key_space)
+                }
+                None => (cfg.workload_ops()?, cfg.workload.total_keys),
+            };
+            let points = runtime.block_on(sweep_capacity_ops(&cfg, &sweep, &ops, key_space))?;
+            println!("{}", render_table(&points, SweepAxis::Capacity));
+            print_sweep_partial(points.len(), sweep.capacities(key_space).len() * (sweep.caches.len() + 1));
+            if let Some(rate) = mrc {
+                print_mrc(&Mrc::compute(&ops, rate)?, &sweep.capacities(key_space));
+            }
+            write_report(cfg, points.iter().map(|p| p.to_result()))
+        }
+    }
+}
+
+/// 生成工作负载或回放追踪，按需先输出统计；回放追踪时不预热，预热配置为 None
+fn workload(stats: bool, trace: Option<TraceWorkload>, cfg: &Config) -> Result<(Vec<Op>, Option<WarmupConfig>)> {
+    match trace {
+        Some(tw) => {
+            if stats {
+                print_workload_stats(tw.ops(), cfg.capacity, None)?;
+            }
+            Ok((tw.generate(), None))
+        }
+        None => {
+            let ops = cfg.workload_ops()?;
+            if stats {
+                print_workload_stats(&ops, cfg.capacity, Some(cfg.workload.read_ratio))?;
+            }
+            Ok((ops, Some(cfg.warmup())))
+        }
+    }
+}
+
+fn run(
+    kind: CacheKind,
+    series: Option<usize>,
+    working_set: Option<usize>,
+    stats: bool,
+    trace: Option<TraceWorkload>,
+    cfg: &Config,
+) -> Result<CacheResult> {
+    let mut converged = None;
+    let (ops, warmup) = workload(stats, trace, cfg)?;
+    let warmup_ops = match warmup {
+        Some(mut warmup) => {
+            if let Some(conv) = &cfg.convergence {
+                let c = kind.converge(cfg.capacity, warmup.clone(), conv)?;
+                print_convergence(&c);
+                warmup = warmup.with_size(c.ops as u64);
+                converged = Some(c);
+            }
+            WarmupManager::new(warmup).generate_warmup_ops_for(&ops)?
+        }
+        None => Vec::new(),
+    };
+    let runner = CacheRunner::from_config(cfg);
+    let result = if let Some(file) = &cfg.backend_file {
+        let runner = runner.with_backend(FileBackend::new(file)?);
+        execute(kind, series, &ops, &warmup_ops, converged, cfg, runner)
+    } else if cfg.simulated_time {
+        let runner = runner.with_clock(MockClock::new());
+        execute(kind, series, &ops, &warmup_ops, converged, cfg, runner)
+    } else {
+        execute(kind, series, &ops, &warmup_ops, converged, cfg, runner)
+    }?;
+    Ok(attach_working_set(result, working_set, &ops, cfg.capacity))
+}
+
+/// 运行并输出结果，耗时与延迟取自运行器的时间源；报告由调用方统一写出
+fn execute<B: BackendModel, T: TimeSource>(
+    kind: CacheKind,
+    series: Option<usize>,
+    ops: &[Op],
+    warmup_ops: &[Op],
+    converged: Option<Converged>,
+    cfg: &Config,
+    runner: CacheRunner<B, T>,
+) -> Result<CacheResult> {
+    let runtime = BenchRuntime::new()?;
+
+    let mut latency = LatencyRecorder::new()?;
+    let mut hit_series = series.map(SeriesRecorder::new);
+    let observer = |_: &Op, outcome, d| {
+        latency.record(outcome, d);
+        if let Some(s) = hit_series.as_mut() {
+            s.record(outcome);
+        }
+    };
+    let runner = match &cfg.disk {
+        Some(disk) => runner.with_disk_tier(Arc::new(runtime.block_on(DiskTier::open(disk))?)),
+        None => runner,
+    }
+    .with_tiers(Arc::new(PopularityTiers::compute(ops)))
+    .with_occupancy(OCCUPANCY_EVERY);
+    // 采样器的分配不计入内存占用
+    let profiler = Profiler::start()?;
+    let counters = PerfProbe::start()
+        .inspect_err(|e| println!("warning: hardware counters unavailable: {e}"))
+        .ok();
+    let probe = MemProbe::start();
+    let start = runner.clock().now();
+    let stats = runtime.block_on(kind.run_observed(&runner, cfg.capacity, warmup_ops, ops, observer))?;
+    let elapsed = runner.clock().now().saturating_sub(start);
+    let memory = probe.finish(stats.ops as usize);
+    let perf = counters.and_then(|c| c.finish(stats.ops as usize));
+    let flamegraph = profiler.finish(cfg.profile_dir.as_deref().unwrap_or(Path::new(PROFILE_DIR)), kind.key())?;
+    let latency = latency.summary();
+    if cfg.simulated_time {
+        println!("simulated time: elapsed and latency are modeled, not measured");
+    }
+    if cfg.deterministic {
+        println!("deterministic: master seed {}", cfg.workload.seed);
+        if NONDETERMINISTIC.contains(&kind) {
+            println!("warning: {} seeds its admission sketch randomly, hit rates still vary between runs", kind.key());
+        }
+    }
+    if cfg.cost != CostModel::Unit {
+        println!("cost: {}, budget {}", cfg.cost, cfg.cost.budget(cfg.capacity));
+    }
+    let done = completed_ops(&stats, ops.len());
+    print_stats(kind.key(), &stats, done, elapsed);
+    let reachable = kind.resident(cfg.capacity, warmup_ops, ops);
+    // 无界锚点本就不受容量限制，部分结果未必填满容量；按代价限额时常驻条目数本就不等于容量
+    if kind != CacheKind::Unbounded
+        && cfg.cost == CostModel::Unit
+        && !stats.partial
+        && let Some(fit) = CapacityFit::check(&stats, cfg.capacity, reachable)
+    {
+        println!("warning: {} {fit}, hit rate is not comparable at the configured capacity", kind.key());
+    }
+    let opt = belady(warmup_ops, &ops[..done], cfg.capacity)?;
+    println!("opt hit rate: {:.2}%", opt.hit_rate());
+    print_latency("read", &latency.reads);
+    print_latency("write", &latency.writes);
+    if let Some(path) = flamegraph {
+        println!("flamegraph: {}", path.display());
+    }
+    let mut result = CacheResult::new(kind.key(), cfg.capacity, cfg.workload.zipf_s, &stats, done, elapsed)
+        .with_latency(latency);
+    if let Some(memory) = memory {
+        print_memory(&memory, reachable);
+        result = result.with_memory(memory);
+    }
+    if let Some(perf) = perf {
+        print_perf(&perf);
+        result = result.with_perf(perf);
+    }
+    if let Some(c) = converged {
+        result = result.with_convergence(c);
+    }
+    if let Some(s) = hit_series {
+        let points = s.finish();
+        print_series(&points);
+        result = result.with_series(points);
+    }
+    Ok(result)
+}
+
+/// 对配置的外部缓存运行工作负载，使用真实时钟；预热经网络写入，不计入耗时
+fn run_remote(
+    series: Option<usize>,
+    working_set: Option<usize>,
+    stats: bool,
+    trace: Option<TraceWorkload>,
+    cfg: &Config,
+) -> Result<CacheResult> {
+    let (ops, warmup) = workload(stats, trace, cfg)?;
+    let warmup_ops = match warmup {
+        Some(warmup) => WarmupManager::new(warmup).generate_warmup_ops_for(&ops)?,
+        None => Vec::new(),
+    };
+    let remote = cfg.remote.clone().unwrap_or_default();
+    let name = remote.protocol.to_string();
+    let runner = CacheRunner::from_config(cfg).with_tiers(Arc::new(PopularityTiers::compute(&ops)));
+    let runtime = BenchRuntime::new()?;
+
+    let mut latency = LatencyRecorder::new()?;
+    let mut hit_series = series.map(SeriesRecorder::new);
+    let observer = |_: &Op, outcome, d| {
+        latency.record(outcome, d);
+        if let Some(s) = hit_series.as_mut() {
+            s.record(outcome);
+        }
+    };
+    println!("remote: {remote} ({} connection(s))", remote.connections);
+    let (stats, elapsed) = runtime.block_on(async {
+        let cache = RemoteCache::connect(remote).await?;
+        WarmupManager::warmup_async_cache(&cache, &warmup_ops).await;
+        let start = runner.clock().now();
+        let stats = runner.run_async_cache_observed(&cache, &ops, observer).await?;
+        let elapsed = runner.clock().now().saturating_sub(start);
+        cache.check()?;
+        Ok::<_, AppError>((stats, elapsed))
+    })?;
+    let latency = latency.summary();
+    let done = completed_ops(&stats, ops.len());
+    print_stats(&name, &stats, done, elapsed);
+    let opt = belady(&warmup_ops, &ops[..done], cfg.capacity)?;
+    println!("opt hit rate at capacity {}: {:.2}%", cfg.capacity, opt.hit_rate());
+    print_latency("read", &latency.reads);
+    print_latency("write", &latency.writes);
+    let mut result =
+        CacheResult::new(&name, cfg.capacity, cfg.workload.zipf_s, &stats, done, elapsed).with_latency(latency);
+    if let Some(s) = hit_series {
+        let points = s.finish();
+        print_series(&points);
+        result = result.with_series(points);
+    }
+    Ok(attach_working_set(result, working_set, &ops, cfg.capacity))
+}
+
+/// 按窗口 `window` 统计工作集时间线，输出并附加到结果
+fn attach_working_set(result: CacheResult, window: Option<usize>, ops: &[Op], capacity: u64) -> CacheResult {
+    let Some(window) = window else {
+        return result;
+    };
+    let points = working_set_timeline(ops, window);
+    print_working_set(&points, window, capacity);
+    result.with_working_set(points)
+}
+
+/// 写出配置中指定的报告，并与基线对比，存在回归时返回错误
+fn write_report(cfg: Config, results: impl IntoIterator<Item = CacheResult>) -> Result<()> {
+    let mut report = Report::new(cfg);
+    for r in results {
+        report.push(r);
+    }
+    print_summary(&report.results);
+    // 扫描含多组容量或 Zipf 参数，各组矩阵只写入报告
+    if let [m] = report.comparison_matrices().as_slice() {
+        println!("\n{m}");
+    }
+    let regressions = report.check_baseline()?.len();
+    for path in report.save()? {
+        println!("report: {}", path.display());
+    }
+    for r in &report.regressions {
+        println!("regression: {r}");
+    }
+    if regressions > 0 {
+        return Err(AppError::Regression(regressions));
+    }
+    Ok(())
+}
+
+/// 提前停止的原因
+fn stop_reason() -> &'static str {
+    if interrupted() { "interrupted" } else { "time budget exhausted" }
+}
+
+/// 实际完成的操作数，超出时间预算或收到中断提前停止时输出提示
+fn completed_ops(stats: &RunStats, total: usize) -> usize {
+    if !stats.partial {
+        return total;
+    }
+    let done = stats.ops as usize;
+    println!("partial: {} after {done} of {total} ops", stop_reason());
+    done
+}
+
+/// 扫描提前停止时输出已完成的扫描点数；每个容量或 Zipf 参数含各缓存与离线最优各一点
+fn print_sweep_partial(points: usize, expected: usize) {
+    if points < expected {
+        println!("partial: {} after {points} of {expected} sweep points", stop_reason());
+    }
+}
+
+/// 提前停止时汇总写入报告的结果，标出只覆盖部分操作的结果
+fn print_summary(results: &[CacheResult]) {
+    let partial = results.iter().filter(|r| r.partial).count();
+    if partial == 0 && !interrupted() {
+        return;
+    }
+    println!("\nsummary: {} result(s) complete, {partial} partial", results.len() - partial);
+    for r in results {
+        let mark = if r.partial { "  (partial)" } else { "" };
+        println!(
+            "  {:<20} capacity {:>10}  zipf {:.2}  hit rate {:.2}%{mark}",
+            r.cache, r.capacity, r.zipf_s, r.hit_rate
+        );
+    }
+}
+
+fn print_stats(name: &str, stats: &RunStats, ops: usize, elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+    let (hits, misses, rate) = (stats.hits, stats.misses, stats.hit_rate());
+    println!("cache: {name}");
+    println!("hits: {hits}  misses: {misses}  hit rate: {rate:.2}%");
+    if stats.tiers.iter().any(|t| t.hits + t.misses > 0) {
+        let rates: Vec<_> =
+            PopularityTier::ALL.iter().map(|&t| format!("{t} {:.2}%", stats.tier(t).hit_rate())).collect();
+        println!("tier hit rates: {}", rates.join("  "));
+    }
+    if stats.disk_hits > 0 {
+        let (disk_hits, disk_rate) = (stats.disk_hits, stats.disk_hit_rate());
+        let backend = misses - disk_hits;
+        println!("disk tier: {disk_hits} hits ({disk_rate:.2}% of memory misses)  backend loads: {backend}");
+    }
+    if stats.evictions > 0 {
+        let (evictions, per_kop) = (stats.evictions, stats.evictions_per_kop());
+        let premature = stats.premature_eviction_rate();
+        println!("evictions: {evictions} ({per_kop:.1}/1k ops)  premature: {premature:.2}%");
+    }
+    if stats.max_resident > 0 {
+        println!("max resident: {}", stats.max_resident);
+    }
+    let occ = &stats.occupancy;
+    if let Some(mean) = occ.mean() {
+        let pct = |n: f64| occ.percent(n).map(|p| format!(" ({p:.1}%)")).unwrap_or_default();
+        let max = occ.max;
+        println!("occupancy: mean {mean:.0}{}  max {max}{}  ({} samples)", pct(mean), pct(max as f64), occ.samples);
+    }
+    if stats.updates + stats.inserts > 0 {
+        println!("writes: {} updates, {} inserts", stats.updates, stats.inserts);
+    }
+    if stats.deletes > 0 {
+        println!("deletes: {}", stats.deletes);
+    }
+    if stats.wasted_loads > 0 {
+        let (wasted, share) = (stats.wasted_loads, stats.wasted_loads as f64 / misses.max(1) as f64 * 100.0);
+        println!("wasted loads: {wasted} ({share:.2}% of misses)");
+    }
+    if stats.backend_calls > 0 {
+        let (calls, calls_rate, miss_rate) = (stats.backend_calls, stats.backend_calls_per_sec(), stats.misses_per_sec());
+        println!("backend calls: {calls} ({calls_rate:.0}/s)  misses: {miss_rate:.0}/s");
+    }
+    if stats.errors > 0 || stats.retries > 0 {
+        let (errors, retries) = (stats.errors, stats.retries);
+        println!("backend errors: {errors}  retries: {retries}");
+    }
+    if stats.negative_hits > 0 || stats.negative_misses > 0 {
+        let (hits, misses) = (stats.negative_hits, stats.negative_misses);
+        println!("negative hits: {hits}  negative misses: {misses}");
+    }
+    println!(
+        "elapsed: {elapsed:.2?}  throughput: {:.0} ops/s  avg latency: {:.2?}",
+        ops as f64 / secs,
+        elapsed.div_f64(ops.max(1) as f64)
+    );
+    println!(
+        "cache ops: {:.2?} ({:.1} ns/op)  backend wait: {:.2?}",
+        stats.cache_time,
+        stats.cache_ns_per_op(),
+        stats.backend_time
+    );
+}
+
+fn print_workload_stats(ops: &[Op], capacity: u64, read_ratio: Option<f64>) -> Result<()> {
+    let s = WorkloadStats::compute(ops)?;
+    let (reads, writes, deletes) = (s.reads, s.writes, s.deletes);
+    println!(
+        "workload: {} ops  reads {reads} ({:.1}%)  writes {writes}  deletes {deletes}",
+        s.ops,
+        s.read_ratio() * 100.0
+    );
+    println!(
+        "keys: {} unique  working set ({:.0}% of accesses) {}",
+        s.unique_keys(),
+        WORKING_SET_SHARE * 100.0,
+        s.working_set(WORKING_SET_SHARE)
+    );
+    let unique = s.unique_keys();
+    print!("top keys:");
+    for (label, k) in [("10".into(), 10), ("1%".into(), (unique / 100).max(1)), (format!("capacity {capacity}"), capacity as usize)] {
+        print!("  {label} -> {:.1}%", s.top_share(k) * 100.0);
+    }
+    println!();
+    match (s.reuse_distance(0.5), s.reuse_distance(0.9), s.reuse_distance(0.99)) {
+        (Some(p50), Some(p90), Some(p99)) => println!(
+            "reuse distance: p50 {p50}  p90 {p90}  p99 {p99}  cold reads {:.1}%",
+            s.cold_ratio() * 100.0
+        ),
+        _ => println!("reuse distance: no reuse"),
+    }
+    let hist = ReuseHistogram::compute(ops);
+    if hist.reads() > 0 {
+        // 累计比例即以区间上界为容量的理论 LRU 命中率
+        let (reads, mut acc) = (hist.reads() as f64, 0);
+        println!("reuse distance histogram (cold {:.1}%):", hist.cold() as f64 / reads * 100.0);
+        for (range, n) in hist.buckets() {
+            acc += n;
+            println!(
+                "  [{:>9}, {:>9})  {:>6.2}%  cumulative {:>6.2}%",
+                range.start,
+                range.end,
+                n as f64 / reads * 100.0,
+                acc as f64 / reads * 100.0
+            );
+        }
+    }
+    println!("theoretical LRU hit rate at capacity {capacity}: {:.2}%", s.lru_hit_rate(capacity));
+    for w in s.warnings(capacity, read_ratio) {
+        println!("warning: {w}");
+    }
+    println!();
+    Ok(())
+}
+
+fn print_convergence(c: &Converged) {
+    let state = if c.converged { "converged" } else { "not converged" };
+    println!("warmup {state} after {} ops (window hit rate {:.2}%)", c.ops, c.hit_rate);
+}
+
+fn print_memory(m: &MemUsage, entries: u64) {
+    let (allocated, peak) = (m.allocated, m.peak);
+    print!("memory: allocated {allocated} B  peak heap {peak} B");
+    if let Some(b) = m.per_entry(entries) {
+        print!("  ({b:.1} B/entry)");
+    }
+    print!("  allocs {:.1}/kop ({:.0} B/kop)", m.allocations_per_kop, m.bytes_per_kop);
+    if let Some(rss) = m.rss_peak {
+        print!("  peak rss {rss} B");
+    }
+    println!();
+}
+
+fn print_perf(p: &PerfCounters) {
+    println!(
+        "perf: {:.1} instructions/op  {:.3} cache misses/op  {:.3} branch misses/op",
+        p.instructions_per_op, p.cache_misses_per_op, p.branch_misses_per_op
+    );
+}
+
+fn print_mrc(mrc: &Mrc, capacities: &[u64]) {
+    println!("\ntheoretical LRU (SHARDS rate {}):", mrc.rate());
+    for &c in capacities {
+        println!("{c:>10} {:>11.2}%", mrc.hit_rate(c));
+    }
+}
+
+fn print_series(points: &[SeriesPoint]) {
+    println!("{:>10} {:>11} {:>11}", "op", "cumulative", "window");
+    for p in points {
+        println!("{:>10} {:>10.2}% {:>10.2}%", p.op, p.cumulative, p.window);
+    }
+}
+
+fn print_lockstep(l: &Lockstep) {
+    for (kind, stats) in l.caches.iter().zip(&l.stats) {
+        println!("{:<12} hit rate {:>6.2}%", kind.key(), stats.hit_rate());
+    }
+    let reads = l.stats.first().map_or(0, |s| s.total());
+    println!("divergent reads: {} / {reads}", l.divergent);
+    if l.first.is_empty() {
+        return;
+    }
+    print!("{:>10} {:>10}", "op", "key");
+    for kind in &l.caches {
+        print!(" {:>12}", kind.key());
+    }
+    println!();
+    for d in &l.first {
+        print!("{:>10} {:>10}", d.op, d.key);
+        for &hit in &d.hits {
+            print!(" {:>12}", if hit { "hit" } else { "miss" });
+        }
+        println!();
+    }
+}
+
+fn print_working_set(points: &[WorkingSetPoint], window: usize, capacity: u64) {
+    let Some(max) = points.iter().map(|p| p.unique).max() else {
+        return;
+    };
+    let min = points.iter().map(|p| p.unique).min().unwrap_or(max);
+    let mean = points.iter().map(|p| p.unique as f64).sum::<f64>() / points.len() as f64;
+    let over = points.iter().filter(|p| p.unique as u64 > capacity).count();
+    println!(
+        "working set (window {window}): min {min}  mean {mean:.0}  max {max}  capacity {capacity} ({over}/{} samples above)",
+        points.len()
+    );
+    println!("{:>10} {:>10} {:>9}", "op", "unique", "/capacity");
+    for p in points {
+        println!("{:>10} {:>10} {:>8.2}x", p.op, p.unique, p.unique as f64 / capacity.max(1) as f64);
+    }
+}
+
+fn print_latency(label: &str, p: &Percentiles) {
+    let ns = Duration::from_nanos;
+    let count = p.count;
+    println!(
+        "{label} latency ({count} ops): p50 {:.2?}  p90 {:.2?}  p99 {:.2?}  p999 {:.2?}  max {:.2?}",
+        ns(p.p50),
+        ns(p.p90),
+        ns(p.p99),
+        ns(p.p999),
+        ns(p.max)
+    );
 }
@@ -0,0 +1,164 @@
+//! 内存占用统计模块
+//! 启用 `mem` 特性后提供计数分配器，统计峰值占用之外还统计分配次数，稳态下的分配频率直接影响尾延迟；
+//! 需在二进制中注册为 `#[global_allocator]`：
+//! `static ALLOC: CountingAlloc = CountingAlloc;`
+
+use serde::Serialize;
+
+#[cfg(feature = "mem")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "mem")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 单次运行的内存占用
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct MemUsage {
+    /// 累计分配字节数
+    pub allocated: u64,
+    /// 分配次数，含 realloc
+    pub allocations: u64,
+    /// 每千个操作的分配次数
+    pub allocations_per_kop: f64,
+    /// 每千个操作分配的字节数
+    pub bytes_per_kop: f64,
+    /// 堆峰值相对起点的增量（字节）
+    pub peak: u64,
+    /// 进程峰值常驻内存（字节），仅 Linux 可得
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rss_peak: Option<u64>,
+}
+
+impl MemUsage {
+    /// 每个常驻条目的平均峰值字节数，无条目时为空
+    #[inline]
+    pub fn per_entry(&self, entries: u64) -> Option<f64> {
+        (entries > 0).then(|| self.peak as f64 / entries as f64)
+    }
+}
+
+#[cfg(feature = "mem")]
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "mem")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "mem")]
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "mem")]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// 包装系统分配器，统计分配次数与累计分配、当前与峰值堆字节数
+#[cfg(feature = "mem")]
+pub struct CountingAlloc;
+
+#[cfg(feature = "mem")]
+impl CountingAlloc {
+    #[inline]
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: 全部转发给 System，只额外更新计数
+#[cfg(feature = "mem")]
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new
+    }
+}
+
+/// 测量区间起点，[`Self::finish`] 给出区间内的内存占用；区间不可嵌套
+#[derive(Debug)]
+pub struct MemProbe {
+    #[cfg(feature = "mem")]
+    allocated: usize,
+    #[cfg(feature = "mem")]
+    allocations: usize,
+    #[cfg(feature = "mem")]
+    current: usize,
+}
+
+#[cfg(feature = "mem")]
+impl MemProbe {
+    /// 以当前堆用量为基线开始测量，并重置峰值
+    pub fn start() -> Self {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        Self {
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            current,
+        }
+    }
+
+    /// 结束测量，`ops` 为区间内执行的操作数
+    pub fn finish(self, ops: usize) -> Option<MemUsage> {
+        let allocated = ALLOCATED.load(Ordering::Relaxed).saturating_sub(self.allocated) as u64;
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed).saturating_sub(self.allocations) as u64;
+        let per_kop = |n: u64| if ops > 0 { n as f64 * 1000.0 / ops as f64 } else { 0.0 };
+        Some(MemUsage {
+            allocated,
+            allocations,
+            allocations_per_kop: per_kop(allocations),
+            bytes_per_kop: per_kop(allocated),
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.current) as u64,
+            rss_peak: rss_peak(),
+        })
+    }
+}
+
+/// 未启用 `mem` 特性时不做统计
+#[cfg(not(feature = "mem"))]
+impl MemProbe {
+    #[inline]
+    pub fn start() -> Self {
+        Self {}
+    }
+
+    #[inline]
+    pub fn finish(self, _ops: usize) -> Option<MemUsage> {
+        None
+    }
+}
+
+/// 读取 /proc/self/status 中的 VmHWM
+#[cfg(feature = "mem")]
+fn rss_peak() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kb: u64 = kb.trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kb * 1024)
+}
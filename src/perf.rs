@@ -0,0 +1,156 @@
+//! 硬件性能计数器
+//! 启用 `perf` 特性后在 Linux 上以 perf_event_open 统计运行区间内本进程用户态的指令数、
+//! 末级缓存未命中与分支预测失败次数；各缓存命中率相近时，微架构行为往往才是真正的差别。
+//! 其他平台或未启用特性时不统计
+
+use serde::Serialize;
+use std::io;
+
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use perf_event_open_sys::bindings::{
+    PERF_FLAG_FD_CLOEXEC, perf_event_attr, perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED,
+    perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING, perf_hw_id, perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES,
+    perf_hw_id_PERF_COUNT_HW_CACHE_MISSES, perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS, perf_type_id_PERF_TYPE_HARDWARE,
+};
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use perf_event_open_sys::{ioctls, perf_event_open};
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use std::ffi::{c_int, c_uint, c_ulong};
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use std::fs::File;
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use std::io::Read;
+#[cfg(all(feature = "perf", target_os = "linux"))]
+use std::os::fd::{AsRawFd, FromRawFd};
+
+/// 单次运行的硬件计数，附每个操作的平均值以便在不同操作数间比较
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct PerfCounters {
+    /// 执行的指令数
+    pub instructions: u64,
+    /// 末级缓存未命中次数
+    pub cache_misses: u64,
+    /// 分支预测失败次数
+    pub branch_misses: u64,
+    pub instructions_per_op: f64,
+    pub cache_misses_per_op: f64,
+    pub branch_misses_per_op: f64,
+}
+
+impl PerfCounters {
+    /// 以 `ops` 个操作的总计数构建
+    pub fn new(instructions: u64, cache_misses: u64, branch_misses: u64, ops: usize) -> Self {
+        let per_op = |n: u64| if ops > 0 { n as f64 / ops as f64 } else { 0.0 };
+        Self {
+            instructions,
+            cache_misses,
+            branch_misses,
+            instructions_per_op: per_op(instructions),
+            cache_misses_per_op: per_op(cache_misses),
+            branch_misses_per_op: per_op(branch_misses),
+        }
+    }
+}
+
+/// 计数区间起点，[`Self::finish`] 给出区间内的计数；计数覆盖开始后本进程新建的线程
+#[derive(Debug)]
+pub struct PerfProbe {
+    #[cfg(all(feature = "perf", target_os = "linux"))]
+    counters: [File; 3],
+}
+
+#[cfg(all(feature = "perf", target_os = "linux"))]
+impl PerfProbe {
+    /// 打开并启用计数器；内核不允许时（如 `perf_event_paranoid` 过高或容器未开放）返回错误
+    pub fn start() -> io::Result<Self> {
+        let counters = [
+            open(perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS)?,
+            open(perf_hw_id_PERF_COUNT_HW_CACHE_MISSES)?,
+            open(perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES)?,
+        ];
+        for c in &counters {
+            ioctl(c, ioctls::RESET)?;
+            ioctl(c, ioctls::ENABLE)?;
+        }
+        Ok(Self { counters })
+    }
+
+    /// 停止计数，`ops` 为区间内执行的操作数；读取失败时为空
+    pub fn finish(self, ops: usize) -> Option<PerfCounters> {
+        for c in &self.counters {
+            ioctl(c, ioctls::DISABLE).ok()?;
+        }
+        let [instructions, cache_misses, branch_misses] = self.counters;
+        Some(PerfCounters::new(
+            read(instructions).ok()?,
+            read(cache_misses).ok()?,
+            read(branch_misses).ok()?,
+            ops,
+        ))
+    }
+}
+
+/// 未启用 `perf` 特性或非 Linux 平台时不统计
+#[cfg(not(all(feature = "perf", target_os = "linux")))]
+impl PerfProbe {
+    #[inline]
+    pub fn start() -> io::Result<Self> {
+        Ok(Self {})
+    }
+
+    #[inline]
+    pub fn finish(self, _ops: usize) -> Option<PerfCounters> {
+        None
+    }
+}
+
+/// 打开本进程任意 CPU 上的用户态硬件计数器，初始为停用
+#[cfg(all(feature = "perf", target_os = "linux"))]
+fn open(event: perf_hw_id) -> io::Result<File> {
+    let mut attr = perf_event_attr {
+        type_: perf_type_id_PERF_TYPE_HARDWARE,
+        size: size_of::<perf_event_attr>() as u32,
+        config: event.into(),
+        read_format: (perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED
+            | perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING)
+            .into(),
+        ..Default::default()
+    };
+    attr.set_disabled(1);
+    attr.set_inherit(1);
+    attr.set_exclude_kernel(1);
+    attr.set_exclude_hv(1);
+    // SAFETY: attr 已完整初始化，pid 0 与 cpu -1 表示本进程的任意 CPU，不加入计数器组
+    let fd = unsafe { perf_event_open(&mut attr, 0, -1, -1, c_ulong::from(PERF_FLAG_FD_CLOEXEC)) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fd 为刚打开的有效描述符，只由返回的 File 持有
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[cfg(all(feature = "perf", target_os = "linux"))]
+fn ioctl(counter: &File, op: unsafe fn(c_int, c_uint) -> c_int) -> io::Result<()> {
+    // SAFETY: 描述符在 counter 存活期间有效，ENABLE/DISABLE/RESET 的参数为 0 表示只作用于该计数器
+    if unsafe { op(counter.as_raw_fd(), 0) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// 读取计数值；计数器与其他事件轮流占用硬件时按启用与实际运行时间之比放大
+#[cfg(all(feature = "perf", target_os = "linux"))]
+fn read(mut counter: File) -> io::Result<u64> {
+    let mut buf = [0u8; 24];
+    counter.read_exact(&mut buf)?;
+    let [value, enabled, running] = [0, 8, 16].map(|i| {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&buf[i..i + 8]);
+        u64::from_ne_bytes(word)
+    });
+    if running == 0 || running >= enabled {
+        return Ok(value);
+    }
+    Ok((u128::from(value) * u128::from(enabled) / u128::from(running)) as u64)
+}
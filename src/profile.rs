@@ -0,0 +1,62 @@
+//! CPU 火焰图
+//! 启用 `profiling` 特性后按缓存采样一次运行的调用栈并输出 SVG 火焰图，
+//! 用于区分耗时落在哈希、链表维护还是频率草图上；未启用时为空操作
+
+use crate::Result;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "profiling")]
+use crate::config::PROFILE_FREQUENCY;
+#[cfg(feature = "profiling")]
+use std::fs::{self, File};
+#[cfg(feature = "profiling")]
+use std::io::{BufWriter, Write};
+
+/// 采样区间起点，[`Self::finish`] 写出区间内的火焰图；同一时刻只能有一个采样区间
+pub struct Profiler {
+    #[cfg(feature = "profiling")]
+    guard: pprof::ProfilerGuard<'static>,
+}
+
+#[cfg(feature = "profiling")]
+impl Profiler {
+    /// 开始采样，跳过 libc 与线程库内部的栈帧
+    pub fn start() -> Result<Self> {
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(PROFILE_FREQUENCY)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()?;
+        Ok(Self { guard })
+    }
+
+    /// 结束采样，把火焰图写入 `dir` 下的 `<name>.svg` 并返回路径
+    pub fn finish(self, dir: &Path, name: &str) -> Result<Option<PathBuf>> {
+        let report = self.guard.report().build()?;
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{name}.svg"));
+        let mut w = BufWriter::new(File::create(&path)?);
+        report.flamegraph(&mut w)?;
+        w.flush()?;
+        Ok(Some(path))
+    }
+}
+
+/// 未启用 `profiling` 特性时不采样
+#[cfg(not(feature = "profiling"))]
+impl Profiler {
+    #[inline]
+    pub fn start() -> Result<Self> {
+        Ok(Self {})
+    }
+
+    #[inline]
+    pub fn finish(self, _dir: &Path, _name: &str) -> Result<Option<PathBuf>> {
+        Ok(None)
+    }
+}
+
+impl std::fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Profiler").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,254 @@
+//! 结果报告模块
+//! 可序列化的运行结果，供下游工具直接读取而非解析标准输出
+
+use crate::{Config, Converged, LatencySummary, MemUsage, Occupancy, PerfCounters, PopularityTier, Regression, Result, RunStats, SeriesPoint, WorkingSetPoint};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub mod compare;
+pub mod csv;
+pub mod html;
+pub mod markdown;
+pub mod matrix;
+
+/// 单个缓存的运行结果
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct CacheResult {
+    /// 缓存名称
+    pub cache: String,
+    /// 缓存容量
+    pub capacity: u64,
+    /// Zipf 分布参数
+    pub zipf_s: f64,
+    pub hits: u64,
+    pub misses: u64,
+    /// 内存未命中后由磁盘层满足的次数
+    pub disk_hits: u64,
+    /// 重试后仍失败的回源次数
+    pub errors: u64,
+    /// 回源重试次数
+    pub retries: u64,
+    /// 向后端发出的请求数（含重试与写回）
+    pub backend_calls: u64,
+    /// 不存在的 key 命中负缓存的次数
+    pub negative_hits: u64,
+    /// 不存在的 key 未命中的次数
+    pub negative_misses: u64,
+    /// 已加载过的 key 被淘汰后再次回源的次数
+    pub wasted_loads: u64,
+    /// 淘汰次数
+    pub evictions: u64,
+    /// 删除操作次数
+    pub deletes: u64,
+    /// 更新已有 key 的写操作次数
+    pub updates: u64,
+    /// 插入新 key 的写操作次数
+    pub inserts: u64,
+    /// 运行中观察到的最大常驻条目数，缓存不提供条目数时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_resident: Option<u64>,
+    /// 常驻条目数的采样统计，未采样时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occupancy: Option<Occupancy>,
+    /// 每千个操作的淘汰次数
+    pub evictions_per_kop: f64,
+    /// 过早淘汰率（百分比）
+    pub premature_eviction_rate: f64,
+    /// 命中率（百分比）
+    pub hit_rate: f64,
+    /// 磁盘层命中率（百分比）
+    pub disk_hit_rate: f64,
+    /// 头部、中部、尾部 key 的命中率（百分比），未分层统计时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier_hit_rates: Option<[f64; 3]>,
+    /// 吞吐量
+    pub ops_per_sec: f64,
+    /// 每秒未命中数
+    pub misses_per_sec: f64,
+    /// 每秒后端请求数
+    pub backend_calls_per_sec: f64,
+    /// 总耗时（秒）
+    pub elapsed_secs: f64,
+    /// 在缓存调用内的耗时（秒）
+    pub cache_secs: f64,
+    /// 等待回源（含磁盘层）的耗时（秒）
+    pub backend_secs: f64,
+    /// 每个操作在缓存调用内的平均耗时（纳秒）
+    pub cache_ns_per_op: f64,
+    /// 读写延迟分位数，未采集时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencySummary>,
+    /// 命中率时间序列，未采集时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<Vec<SeriesPoint>>,
+    /// 工作负载的工作集大小时间线，未采集时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_set: Option<Vec<WorkingSetPoint>>,
+    /// 内存占用，未启用 `mem` 特性时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemUsage>,
+    /// 硬件性能计数，未启用 `perf` 特性或内核不允许时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perf: Option<PerfCounters>,
+    /// 预热收敛检测结果，未启用时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub convergence: Option<Converged>,
+    /// 超出时间预算或收到中断提前停止，各项只覆盖已完成的操作
+    pub partial: bool,
+}
+
+impl CacheResult {
+    /// 由运行统计与耗时构建，`ops` 为执行的操作数
+    pub fn new(cache: &str, capacity: u64, zipf_s: f64, stats: &RunStats, ops: usize, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs_f64();
+        let per_sec = |n: u64| if secs > 0.0 { n as f64 / secs } else { 0.0 };
+        Self {
+            cache: cache.into(),
+            capacity,
+            zipf_s,
+            hits: stats.hits,
+            misses: stats.misses,
+            disk_hits: stats.disk_hits,
+            errors: stats.errors,
+            retries: stats.retries,
+            backend_calls: stats.backend_calls,
+            negative_hits: stats.negative_hits,
+            negative_misses: stats.negative_misses,
+            wasted_loads: stats.wasted_loads,
+            evictions: stats.evictions,
+            deletes: stats.deletes,
+            updates: stats.updates,
+            inserts: stats.inserts,
+            max_resident: (stats.max_resident > 0).then_some(stats.max_resident),
+            occupancy: (stats.occupancy.samples > 0).then_some(stats.occupancy),
+            evictions_per_kop: stats.evictions_per_kop(),
+            premature_eviction_rate: stats.premature_eviction_rate(),
+            hit_rate: stats.hit_rate(),
+            disk_hit_rate: stats.disk_hit_rate(),
+            tier_hit_rates: stats
+                .tiers
+                .iter()
+                .any(|t| t.hits + t.misses > 0)
+                .then(|| PopularityTier::ALL.map(|t| stats.tier(t).hit_rate())),
+            ops_per_sec: per_sec(ops as u64),
+            misses_per_sec: per_sec(stats.misses),
+            backend_calls_per_sec: per_sec(stats.backend_calls),
+            elapsed_secs: secs,
+            cache_secs: stats.cache_time.as_secs_f64(),
+            backend_secs: stats.backend_time.as_secs_f64(),
+            cache_ns_per_op: stats.cache_ns_per_op(),
+            latency: None,
+            series: None,
+            working_set: None,
+            memory: None,
+            perf: None,
+            convergence: None,
+            partial: stats.partial,
+        }
+    }
+
+    /// 附加命中率时间序列
+    pub fn with_series(mut self, series: Vec<SeriesPoint>) -> Self {
+        self.series = Some(series);
+        self
+    }
+
+    /// 附加工作集大小时间线
+    pub fn with_working_set(mut self, working_set: Vec<WorkingSetPoint>) -> Self {
+        self.working_set = Some(working_set);
+        self
+    }
+
+    /// 附加内存占用
+    pub fn with_memory(mut self, memory: MemUsage) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// 附加硬件性能计数
+    pub fn with_perf(mut self, perf: PerfCounters) -> Self {
+        self.perf = Some(perf);
+        self
+    }
+
+    /// 附加预热收敛检测结果
+    pub fn with_convergence(mut self, convergence: Converged) -> Self {
+        self.convergence = Some(convergence);
+        self
+    }
+
+    /// 附加延迟分位数
+    pub fn with_latency(mut self, latency: LatencySummary) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+}
+
+/// 一次运行的完整报告：时间戳、配置快照与各缓存结果
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct Report {
+    /// Unix 时间戳（秒）
+    pub timestamp: u64,
+    /// 配置快照
+    pub config: Config,
+    pub results: Vec<CacheResult>,
+    /// 与基线对比的回归，见 [`Report::check_baseline`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub regressions: Vec<Regression>,
+}
+
+impl Report {
+    /// 以当前时间创建空报告
+    pub fn new(config: Config) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            timestamp,
+            config,
+            results: Vec::new(),
+            regressions: Vec::new(),
+        }
+    }
+
+    /// 追加结果
+    pub fn push(&mut self, result: CacheResult) {
+        self.results.push(result);
+    }
+
+    /// 写出配置中指定的 JSON、CSV、HTML 与 Markdown 报告，返回实际写入的路径
+    pub fn save(&self) -> Result<Vec<&Path>> {
+        let mut written = Vec::new();
+        if let Some(path) = &self.config.report {
+            self.write_json(path)?;
+            written.push(path.as_path());
+        }
+        if let Some(path) = &self.config.csv {
+            self.append_csv(path)?;
+            written.push(path.as_path());
+        }
+        if let Some(path) = &self.config.html {
+            self.write_html(path)?;
+            written.push(path.as_path());
+        }
+        if let Some(path) = &self.config.markdown {
+            self.write_markdown(path)?;
+            written.push(path.as_path());
+        }
+        Ok(written)
+    }
+
+    /// 以格式化 JSON 写入文件
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut w, self)?;
+        w.write_all(b"\n")?;
+        w.flush()?;
+        Ok(())
+    }
+}
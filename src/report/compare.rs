@@ -0,0 +1,120 @@
+//! 基线对比
+//! 读取先前保存的 JSON 报告，按 (缓存, 容量, Zipf 参数) 匹配当前结果，标记吞吐量或命中率超出阈值的下降，供 CI 卡点
+
+use crate::{Baseline, Report, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// 基线报告中参与对比的字段，其余字段忽略
+#[derive(Clone, Debug, Deserialize)]
+pub struct BaselineResult {
+    pub cache: String,
+    pub capacity: u64,
+    pub zipf_s: f64,
+    pub hit_rate: f64,
+    pub ops_per_sec: f64,
+}
+
+/// 先前保存的报告
+#[derive(Clone, Debug, Deserialize)]
+pub struct BaselineReport {
+    pub results: Vec<BaselineResult>,
+}
+
+impl BaselineReport {
+    /// 读取 [`Report::write_json`] 写出的文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+}
+
+/// 回归的指标
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Metric {
+    /// 吞吐量，阈值按相对百分比
+    Throughput,
+    /// 命中率，阈值按百分点
+    HitRate,
+}
+
+/// 单项回归
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct Regression {
+    pub cache: String,
+    pub capacity: u64,
+    pub zipf_s: f64,
+    pub metric: Metric,
+    /// 基线值
+    pub baseline: f64,
+    /// 当前值
+    pub current: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (cache, capacity, zipf_s, base, cur) = (&self.cache, self.capacity, self.zipf_s, self.baseline, self.current);
+        match self.metric {
+            Metric::Throughput => {
+                let drop = (base - cur) / base * 100.0;
+                write!(f, "{cache} @{capacity} s={zipf_s}: throughput {base:.0} -> {cur:.0} ops/s (-{drop:.1}%)")
+            }
+            Metric::HitRate => write!(
+                f,
+                "{cache} @{capacity} s={zipf_s}: hit rate {base:.2}% -> {cur:.2}% (-{:.2} pts)",
+                base - cur
+            ),
+        }
+    }
+}
+
+impl Report {
+    /// 与基线对比，返回超出阈值的回归；基线中不存在的结果不参与对比
+    pub fn compare(&self, baseline: &BaselineReport, cfg: &Baseline) -> Vec<Regression> {
+        let mut out = Vec::new();
+        for r in &self.results {
+            let Some(b) = baseline
+                .results
+                .iter()
+                .find(|b| b.cache == r.cache && b.capacity == r.capacity && b.zipf_s == r.zipf_s)
+            else {
+                continue;
+            };
+            let regression = |metric, baseline, current| Regression {
+                cache: r.cache.clone(),
+                capacity: r.capacity,
+                zipf_s: r.zipf_s,
+                metric,
+                baseline,
+                current,
+            };
+            if b.ops_per_sec > 0.0 && r.ops_per_sec < b.ops_per_sec * (1.0 - cfg.max_throughput_drop / 100.0) {
+                out.push(regression(Metric::Throughput, b.ops_per_sec, r.ops_per_sec));
+            }
+            if r.hit_rate < b.hit_rate - cfg.max_hit_rate_drop {
+                out.push(regression(Metric::HitRate, b.hit_rate, r.hit_rate));
+            }
+        }
+        out
+    }
+
+    /// 按配置的基线对比，未配置基线时为空
+    pub fn regressions(&self) -> Result<Vec<Regression>> {
+        let cfg = &self.config.baseline;
+        match &cfg.path {
+            Some(path) => Ok(self.compare(&BaselineReport::load(path)?, cfg)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// 按配置的基线对比并把回归记入报告，需在 [`Report::save`] 之前调用，
+    /// 也避免报告与基线为同一文件时先被覆盖
+    pub fn check_baseline(&mut self) -> Result<&[Regression]> {
+        self.regressions = self.regressions()?;
+        Ok(&self.regressions)
+    }
+}
@@ -0,0 +1,58 @@
+//! CSV 结果导出
+//! 每行一个 (缓存, 工作负载, 运行)，多次运行追加到同一文件，便于用表格或 Pandas 作图
+
+use crate::{Report, Result};
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// 表头，列顺序与 [`Report::append_csv`] 写出的行一致
+pub const CSV_HEADER: &str = "timestamp,cache,capacity,total_keys,ops,zipf_s,read_ratio,peek_ratio,seed,\
+backend,hits,misses,hit_rate,ops_per_sec,misses_per_sec,backend_calls_per_sec,elapsed_secs,cache_secs,backend_secs,read_p50_ns,read_p99_ns,write_p50_ns,write_p99_ns,partial";
+
+impl Report {
+    /// 追加到 CSV 文件，文件为空时先写表头
+    pub fn append_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let empty = file.metadata()?.len() == 0;
+        let mut w = BufWriter::new(file);
+        if empty {
+            writeln!(w, "{CSV_HEADER}")?;
+        }
+        let ts = self.timestamp;
+        let wl = &self.config.workload;
+        let (total_keys, ops, read_ratio, peek_ratio, seed) =
+            (wl.total_keys, wl.size, wl.read_ratio, wl.peek_ratio, wl.seed);
+        let backend = self.config.backend_label();
+        for r in &self.results {
+            write!(
+                w,
+                "{ts},{},{},{total_keys},{ops},{},{read_ratio},{peek_ratio},{seed},{backend},{},{},{:.4},{:.2},{:.2},{:.2},{:.6},{:.6},{:.6}",
+                r.cache,
+                r.capacity,
+                r.zipf_s,
+                r.hits,
+                r.misses,
+                r.hit_rate,
+                r.ops_per_sec,
+                r.misses_per_sec,
+                r.backend_calls_per_sec,
+                r.elapsed_secs,
+                r.cache_secs,
+                r.backend_secs
+            )?;
+            // 未采集延迟时留空
+            match &r.latency {
+                Some(l) => write!(
+                    w,
+                    ",{},{},{},{}",
+                    l.reads.p50, l.reads.p99, l.writes.p50, l.writes.p99
+                )?,
+                None => write!(w, ",,,,")?,
+            }
+            writeln!(w, ",{}", r.partial)?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+}
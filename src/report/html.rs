@@ -0,0 +1,284 @@
+//! HTML 结果报告
+//! 单文件页面，图表以内嵌 SVG 绘制，不依赖外部脚本与样式：两两对比矩阵、命中率柱状图、读延迟分位数、命中率随时间变化曲线
+
+use crate::{CacheResult, ComparisonMatrix, Report, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// 图表宽度（像素）
+const WIDTH: f64 = 720.0;
+/// 条形图左侧标签区宽度
+const LABEL: f64 = 160.0;
+/// 单个条形高度
+const BAR: f64 = 18.0;
+/// 折线图绘图区高度
+const PLOT: f64 = 280.0;
+
+/// 各缓存的配色，超出后循环使用
+const PALETTE: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7", "#9c755f", "#bab0ac",
+];
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:4px 8px;text-align:right}\
+td:first-child,th:first-child{text-align:left}svg text{font-size:12px}";
+
+impl Report {
+    /// 写入单文件 HTML 报告
+    pub fn write_html(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.render_html(&mut w)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// 渲染 HTML 页面
+    pub fn render_html(&self, w: &mut impl Write) -> io::Result<()> {
+        let cfg = &self.config;
+        let wl = &cfg.workload;
+        writeln!(w, "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>lru_bench</title>")?;
+        writeln!(w, "<style>{STYLE}</style></head><body>\n<h1>lru_bench</h1>")?;
+        writeln!(
+            w,
+            "<p>timestamp {}, capacity {}, keys {}, ops {}, zipf {}, read ratio {}, backend {}</p>",
+            self.timestamp, cfg.capacity, wl.total_keys, wl.size, wl.zipf_s, wl.read_ratio, cfg.backend_label()
+        )?;
+        summary_table(w, &self.results)?;
+        pairwise_tables(w, &self.comparison_matrices())?;
+        hit_rate_bars(w, &self.results)?;
+        latency_bars(w, &self.results)?;
+        series_lines(w, &self.results)?;
+        writeln!(w, "</body></html>")
+    }
+}
+
+/// 结果汇总表
+fn summary_table(w: &mut impl Write, results: &[CacheResult]) -> io::Result<()> {
+    writeln!(w, "<h2>Summary</h2>\n<table>")?;
+    writeln!(
+        w,
+        "<tr><th>cache</th><th>capacity</th><th>zipf</th><th>hit rate %</th><th>ops/s</th>\
+<th>read p50 ns</th><th>read p99 ns</th></tr>"
+    )?;
+    for r in results {
+        let (p50, p99) = r
+            .latency
+            .map_or((String::new(), String::new()), |l| (l.reads.p50.to_string(), l.reads.p99.to_string()));
+        writeln!(
+            w,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{:.0}</td><td>{p50}</td><td>{p99}</td></tr>",
+            escape(&r.cache),
+            r.capacity,
+            r.zipf_s,
+            r.hit_rate,
+            r.ops_per_sec
+        )?;
+    }
+    writeln!(w, "</table>")
+}
+
+/// 两两对比矩阵，每格为行缓存相对列缓存的吞吐量差与命中率差
+fn pairwise_tables(w: &mut impl Write, matrices: &[ComparisonMatrix]) -> io::Result<()> {
+    if matrices.is_empty() {
+        return Ok(());
+    }
+    writeln!(w, "<h2>Pairwise comparison</h2>\n<p>row vs column: throughput / hit rate</p>")?;
+    for m in matrices {
+        writeln!(w, "<h3>capacity {}, zipf {}</h3>\n<table>", m.capacity, m.zipf_s)?;
+        write!(w, "<tr><th>vs</th>")?;
+        for c in &m.caches {
+            write!(w, "<th>{}</th>", escape(c))?;
+        }
+        writeln!(w, "</tr>")?;
+        for (i, (c, row)) in m.caches.iter().zip(&m.cells).enumerate() {
+            write!(w, "<tr><td>{}</td>", escape(c))?;
+            for (j, cell) in row.iter().enumerate() {
+                if i == j {
+                    write!(w, "<td></td>")?;
+                } else {
+                    write!(w, "<td>{cell}</td>")?;
+                }
+            }
+            writeln!(w, "</tr>")?;
+        }
+        writeln!(w, "</table>")?;
+    }
+    Ok(())
+}
+
+/// 结果标签：名称，多个容量或 Zipf 参数时附加区分
+fn label(r: &CacheResult, results: &[CacheResult]) -> String {
+    let mut s = escape(&r.cache);
+    if results.iter().any(|o| o.capacity != r.capacity) {
+        s.push_str(&format!(" @{}", r.capacity));
+    }
+    if results.iter().any(|o| o.zipf_s != r.zipf_s) {
+        s.push_str(&format!(" s={}", r.zipf_s));
+    }
+    s
+}
+
+/// 命中率水平条形图，刻度 0–100%
+fn hit_rate_bars(w: &mut impl Write, results: &[CacheResult]) -> io::Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+    let span = WIDTH - LABEL - 60.0;
+    let height = results.len() as f64 * (BAR + 6.0) + 10.0;
+    writeln!(w, "<h2>Hit rate</h2>\n<svg width=\"{WIDTH}\" height=\"{height:.0}\">")?;
+    for (i, r) in results.iter().enumerate() {
+        let y = 5.0 + i as f64 * (BAR + 6.0);
+        let len = span * r.hit_rate.clamp(0.0, 100.0) / 100.0;
+        let color = PALETTE[i % PALETTE.len()];
+        writeln!(
+            w,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\
+<rect x=\"{LABEL}\" y=\"{y}\" width=\"{len:.1}\" height=\"{BAR}\" fill=\"{color}\"/>\
+<text x=\"{:.1}\" y=\"{}\">{:.2}%</text>",
+            LABEL - 6.0,
+            y + BAR - 5.0,
+            label(r, results),
+            LABEL + len + 4.0,
+            y + BAR - 5.0,
+            r.hit_rate
+        )?;
+    }
+    writeln!(w, "</svg>")
+}
+
+/// 读延迟分位数分组条形图，对数刻度
+fn latency_bars(w: &mut impl Write, results: &[CacheResult]) -> io::Result<()> {
+    let rows: Vec<_> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.latency.map(|l| (i, r, l.reads)))
+        .collect();
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let max = rows.iter().map(|(.., p)| p.p999.max(1)).max().unwrap_or(1) as f64;
+    // 至少覆盖一个数量级
+    let top = max.log10().max(1.0);
+    let span = WIDTH - LABEL - 90.0;
+    let group = 4.0 * (BAR * 0.6) + 10.0;
+    let height = rows.len() as f64 * group + 10.0;
+    writeln!(
+        w,
+        "<h2>Read latency (p50 / p90 / p99 / p99.9, log scale)</h2>\n<svg width=\"{WIDTH}\" height=\"{height:.0}\">"
+    )?;
+    for (row, (i, r, p)) in rows.iter().enumerate() {
+        let y0 = 5.0 + row as f64 * group;
+        let color = PALETTE[i % PALETTE.len()];
+        writeln!(
+            w,
+            "<text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            LABEL - 6.0,
+            y0 + group / 2.0,
+            label(r, results)
+        )?;
+        for (j, (name, ns)) in [("p50", p.p50), ("p90", p.p90), ("p99", p.p99), ("p99.9", p.p999)]
+            .into_iter()
+            .enumerate()
+        {
+            let y = y0 + j as f64 * BAR * 0.6;
+            let len = span * (ns.max(1) as f64).log10() / top;
+            let opacity = 1.0 - j as f64 * 0.2;
+            writeln!(
+                w,
+                "<rect x=\"{LABEL}\" y=\"{y:.1}\" width=\"{len:.1}\" height=\"{:.1}\" fill=\"{color}\" \
+fill-opacity=\"{opacity:.1}\"/><text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\">{name} {}</text>",
+                BAR * 0.6 - 1.0,
+                LABEL + len + 4.0,
+                y + BAR * 0.6 - 2.0,
+                fmt_ns(ns)
+            )?;
+        }
+    }
+    writeln!(w, "</svg>")
+}
+
+/// 累计命中率随操作数变化的折线图
+fn series_lines(w: &mut impl Write, results: &[CacheResult]) -> io::Result<()> {
+    let lines: Vec<_> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.series.as_deref().filter(|s| !s.is_empty()).map(|s| (i, r, s)))
+        .collect();
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let max_op = lines
+        .iter()
+        .filter_map(|(.., s)| s.last().map(|p| p.op))
+        .max()
+        .unwrap_or(1)
+        .max(1) as f64;
+    let (left, top) = (50.0, 10.0);
+    let plot_w = WIDTH - left - 20.0;
+    let legend = lines.len() as f64 * 16.0;
+    let height = top + PLOT + 30.0 + legend;
+    writeln!(w, "<h2>Cumulative hit rate over time</h2>\n<svg width=\"{WIDTH}\" height=\"{height:.0}\">")?;
+    // 坐标轴与纵轴刻度
+    writeln!(
+        w,
+        "<path d=\"M{left} {top}V{}H{}\" stroke=\"#888\" fill=\"none\"/>",
+        top + PLOT,
+        left + plot_w
+    )?;
+    for pct in [0, 25, 50, 75, 100] {
+        let y = top + PLOT * (1.0 - pct as f64 / 100.0);
+        writeln!(
+            w,
+            "<text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">{pct}%</text>",
+            left - 4.0,
+            y + 4.0
+        )?;
+    }
+    writeln!(
+        w,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{max_op} ops</text>",
+        left + plot_w,
+        top + PLOT + 16.0
+    )?;
+    for (row, (i, r, series)) in lines.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let points: Vec<_> = series
+            .iter()
+            .map(|p| {
+                let x = left + plot_w * p.op as f64 / max_op;
+                let y = top + PLOT * (1.0 - p.cumulative.clamp(0.0, 100.0) / 100.0);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        writeln!(
+            w,
+            "<polyline points=\"{}\" stroke=\"{color}\" stroke-width=\"1.5\" fill=\"none\"/>",
+            points.join(" ")
+        )?;
+        let y = top + PLOT + 30.0 + row as f64 * 16.0;
+        writeln!(
+            w,
+            "<rect x=\"{left}\" y=\"{}\" width=\"12\" height=\"3\" fill=\"{color}\"/>\
+<text x=\"{}\" y=\"{y}\">{}</text>",
+            y - 5.0,
+            left + 18.0,
+            label(r, results)
+        )?;
+    }
+    writeln!(w, "</svg>")
+}
+
+/// 纳秒转为易读单位
+fn fmt_ns(ns: u64) -> String {
+    match ns {
+        0..1_000 => format!("{ns}ns"),
+        1_000..1_000_000 => format!("{:.1}µs", ns as f64 / 1e3),
+        _ => format!("{:.2}ms", ns as f64 / 1e6),
+    }
+}
+
+/// 转义 HTML 特殊字符
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,89 @@
+//! Markdown 结果摘要
+//! GitHub 风格表格，汇总各缓存的吞吐量、缓存调用与回源等待耗时、命中率、内存占用与分配频率，并附两两对比矩阵，可直接贴入 PR 描述或 README
+
+use crate::{Report, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+impl Report {
+    /// 写入 Markdown 摘要
+    pub fn write_markdown(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.render_markdown(&mut w)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// 渲染 Markdown 表格，未采集内存时内存与分配列为 `-`；同一容量与 Zipf 参数下有多个缓存时附两两对比矩阵
+    pub fn render_markdown(&self, w: &mut impl Write) -> io::Result<()> {
+        let cfg = &self.config;
+        let wl = &cfg.workload;
+        writeln!(
+            w,
+            "**lru_bench**: capacity {}, keys {}, ops {}, zipf {}, read ratio {}, backend `{}`\n",
+            cfg.capacity, wl.total_keys, wl.size, wl.zipf_s, wl.read_ratio, cfg.backend_label()
+        )?;
+        writeln!(w, "| Cache | Capacity | Zipf | Ops/s | Cache ns/op | Backend wait | Hit rate | Peak memory | Allocs/kop |")?;
+        writeln!(w, "|:------|---------:|-----:|------:|------------:|-------------:|---------:|------------:|-----------:|")?;
+        for r in &self.results {
+            let memory = r.memory.map_or_else(|| "-".into(), |m| fmt_bytes(m.peak));
+            let allocs = r.memory.map_or_else(|| "-".into(), |m| format!("{:.1}", m.allocations_per_kop));
+            let partial = if r.partial { " (partial)" } else { "" };
+            writeln!(
+                w,
+                "| {}{partial} | {} | {} | {:.0} | {:.1} | {:.3}s | {:.2}% | {memory} | {allocs} |",
+                r.cache.replace('|', "\\|"),
+                r.capacity,
+                r.zipf_s,
+                r.ops_per_sec,
+                r.cache_ns_per_op,
+                r.backend_secs,
+                r.hit_rate
+            )?;
+        }
+        for m in self.comparison_matrices() {
+            writeln!(
+                w,
+                "\n**Pairwise** (capacity {}, zipf {}): row vs column, throughput / hit rate\n",
+                m.capacity, m.zipf_s
+            )?;
+            write!(w, "| vs |")?;
+            for c in &m.caches {
+                write!(w, " {} |", c.replace('|', "\\|"))?;
+            }
+            writeln!(w, "\n|:---|{}", "---:|".repeat(m.caches.len()))?;
+            for (i, (c, row)) in m.caches.iter().zip(&m.cells).enumerate() {
+                write!(w, "| {} |", c.replace('|', "\\|"))?;
+                for (j, cell) in row.iter().enumerate() {
+                    if i == j {
+                        write!(w, " - |")?;
+                    } else {
+                        write!(w, " {cell} |")?;
+                    }
+                }
+                writeln!(w)?;
+            }
+        }
+        if !self.regressions.is_empty() {
+            writeln!(w, "\n**Regressions** against baseline\n")?;
+            for r in &self.regressions {
+                writeln!(w, "- {r}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 字节数转为易读单位
+fn fmt_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b < KIB {
+        format!("{bytes} B")
+    } else if b < KIB * KIB {
+        format!("{:.1} KiB", b / KIB)
+    } else {
+        format!("{:.2} MiB", b / KIB / KIB)
+    }
+}
@@ -0,0 +1,121 @@
+//! 两两对比矩阵
+//! 同一容量与 Zipf 参数下的各缓存两两对比：吞吐量相对高出的百分比与命中率相差的百分点，省去读者从原始表格手算比值
+
+use crate::{CacheResult, Report};
+use serde::Serialize;
+use std::fmt;
+
+/// 行缓存相对列缓存的表现
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Pairwise {
+    /// 吞吐量高出的百分比，负数表示更慢；列缓存吞吐量为 0（如离线最优）时为空
+    pub throughput_pct: Option<f64>,
+    /// 命中率相差的百分点
+    pub hit_rate_pts: f64,
+}
+
+impl Pairwise {
+    /// `a` 相对 `b` 的表现
+    pub fn new(a: &CacheResult, b: &CacheResult) -> Self {
+        let throughput_pct = (a.ops_per_sec > 0.0 && b.ops_per_sec > 0.0).then(|| (a.ops_per_sec / b.ops_per_sec - 1.0) * 100.0);
+        Self {
+            throughput_pct,
+            hit_rate_pts: a.hit_rate - b.hit_rate,
+        }
+    }
+}
+
+impl fmt::Display for Pairwise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.throughput_pct {
+            Some(pct) => write!(f, "{pct:+.1}%")?,
+            None => write!(f, "-")?,
+        }
+        write!(f, " / {:+.2} pt", self.hit_rate_pts)
+    }
+}
+
+/// 同一容量与 Zipf 参数下各缓存的两两对比，`cells[i][j]` 为第 i 个缓存相对第 j 个缓存
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ComparisonMatrix {
+    pub capacity: u64,
+    pub zipf_s: f64,
+    pub caches: Vec<String>,
+    pub cells: Vec<Vec<Pairwise>>,
+}
+
+impl ComparisonMatrix {
+    /// 以同组结果构建
+    pub fn new(results: &[&CacheResult]) -> Self {
+        let first = results.first();
+        Self {
+            capacity: first.map_or(0, |r| r.capacity),
+            zipf_s: first.map_or(0.0, |r| r.zipf_s),
+            caches: results.iter().map(|r| r.cache.clone()).collect(),
+            cells: results
+                .iter()
+                .map(|a| results.iter().map(|b| Pairwise::new(a, b)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for ComparisonMatrix {
+    /// 纯文本矩阵，每格为「吞吐量差 / 命中率差」，对角线为空
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.caches.iter().map(String::len).max().unwrap_or(0).max("vs".len());
+        let cells: Vec<Vec<String>> = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, c)| if i == j { String::new() } else { c.to_string() })
+                    .collect()
+            })
+            .collect();
+        let width = cells
+            .iter()
+            .flatten()
+            .map(String::len)
+            .chain(self.caches.iter().map(String::len))
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "capacity {}, zipf {}: row vs column, throughput / hit rate", self.capacity, self.zipf_s)?;
+        write!(f, "{:<name$}", "vs")?;
+        for c in &self.caches {
+            write!(f, "  {c:>width$}")?;
+        }
+        writeln!(f)?;
+        for (c, row) in self.caches.iter().zip(&cells) {
+            write!(f, "{c:<name$}")?;
+            for cell in row {
+                write!(f, "  {cell:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    /// 按容量与 Zipf 参数分组的两两对比矩阵，按结果中首次出现的顺序排列；只含一个结果的组不生成矩阵
+    pub fn comparison_matrices(&self) -> Vec<ComparisonMatrix> {
+        let mut groups: Vec<Vec<&CacheResult>> = Vec::new();
+        for r in &self.results {
+            match groups
+                .iter_mut()
+                .find(|g| g[0].capacity == r.capacity && g[0].zipf_s == r.zipf_s)
+            {
+                Some(g) => g.push(r),
+                None => groups.push(vec![r]),
+            }
+        }
+        groups
+            .iter()
+            .filter(|g| g.len() > 1)
+            .map(|g| ComparisonMatrix::new(g))
+            .collect()
+    }
+}
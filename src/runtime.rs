@@ -0,0 +1,22 @@
+//! 基准运行时
+//! 每个基准函数创建一次 Compio 运行时，在各次迭代间复用，运行时的创建开销不计入测量
+
+use crate::{AppError, Result};
+use compio::runtime::Runtime;
+use std::future::Future;
+
+/// 可复用的 Compio 运行时
+pub struct BenchRuntime(Runtime);
+
+impl BenchRuntime {
+    /// 创建运行时
+    pub fn new() -> Result<Self> {
+        Runtime::new().map(Self).map_err(AppError::RuntimeCreate)
+    }
+
+    /// 在运行时上驱动 `future` 直到完成
+    #[inline]
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.0.block_on(future)
+    }
+}
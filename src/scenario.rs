@@ -0,0 +1,256 @@
+//! 基准场景
+//! 场景是一组具名、可序列化的参数：工作负载、预热策略、后端延迟模型、条目代价模型、参与的缓存与要采集的指标。
+//! 内置若干常用场景，也可从 TOML / YAML 文件加载，由命令行 `--scenario` 或 `LRU_BENCH_SCENARIO` 按名称选择
+//!
+//! 场景文件写作 `[[scenario]]` 数组（YAML 为 `scenario:` 列表），与内置场景同名时覆盖内置场景。
+//! 基准测试为每个场景生成一个基准组，[`ScenarioMode`] 决定组内注册的缓存与驱动方式
+
+use crate::{
+    AppError, Backend, CacheKind, Config, CostModel, Result, ScanConfig, WarmupStrategy, WorkloadConfig, WorkloadPreset,
+    WorkloadSize,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 基准场景，未设置的项沿用当前配置
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Scenario {
+    /// 场景名，命令行与环境变量以此选择
+    pub name: String,
+    /// 一句话说明
+    pub description: String,
+    /// 缓存容量
+    pub capacity: Option<u64>,
+    /// 工作负载，整体替换配置中的工作负载
+    pub workload: Option<WorkloadConfig>,
+    /// 预热策略
+    pub warmup_strategy: Option<WarmupStrategy>,
+    /// 后端延迟模型
+    pub latency: Option<Backend>,
+    /// 条目代价模型
+    pub cost: Option<CostModel>,
+    /// 参与的缓存，空则全部
+    pub caches: Vec<CacheKind>,
+    /// 要采集的指标
+    pub metrics: ScenarioMetrics,
+    /// 基准中驱动缓存的方式
+    pub mode: ScenarioMode,
+}
+
+/// 基准中驱动场景缓存的方式；除 [`ScenarioMode::Kinds`] 外只在基准测试中运行
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum ScenarioMode {
+    /// 按 [`Scenario::caches`] 逐个运行
+    #[default]
+    Kinds,
+    /// `Bytes` 值
+    Bytes,
+    /// 字符串键与独占的变长值
+    StringKeys,
+    /// 同一缓存关闭与开启负缓存对比，工作负载未设置不存在的 key 时按默认比例生成
+    NegativeCache,
+    /// 原生过期与 TTL 包装对比，TTL/TTI 取自 [`Config::expiration`]
+    Expiration,
+    /// 异步缓存与同步接口对照
+    Async,
+    /// 多个任务并发 get_or_load，比较原生单飞与手工模拟
+    Loader,
+    /// 多个任务同时读取同一冷 key
+    Dogpile,
+    /// 按 [`Config::zipf_sweep`] 逐个 Zipf 参数运行，未配置时跳过
+    ZipfSweep,
+    /// 多线程共享缓存的争用
+    Threads,
+    /// 逐个调用与批量接口对比，不模拟回源
+    Batch,
+}
+
+/// 场景额外采集的指标；命中率、吞吐量与延迟总是采集
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioMetrics {
+    /// 命中率时间序列的采样间隔（操作数），缺省不采集
+    pub series: Option<usize>,
+    /// 工作集时间线的窗口（操作数），缺省不采集
+    pub working_set: Option<usize>,
+    /// 运行前输出工作负载统计
+    pub stats: bool,
+}
+
+impl Scenario {
+    /// 新建只有名称与说明的场景
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 将场景参数写入配置
+    pub fn apply(&self, cfg: &mut Config) {
+        if let Some(capacity) = self.capacity {
+            cfg.capacity = capacity;
+        }
+        if let Some(workload) = &self.workload {
+            cfg.workload = workload.clone();
+        }
+        if let Some(strategy) = self.warmup_strategy {
+            cfg.warmup_strategy = strategy;
+        }
+        if let Some(latency) = self.latency {
+            cfg.latency = latency;
+        }
+        if let Some(cost) = self.cost {
+            cfg.cost = cost;
+        }
+    }
+
+    /// 参与的缓存，未指定时为全部实现
+    pub fn caches(&self) -> Vec<CacheKind> {
+        if self.caches.is_empty() {
+            CacheKind::ALL.to_vec()
+        } else {
+            self.caches.clone()
+        }
+    }
+}
+
+/// 场景文件的顶层结构
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioFile {
+    scenario: Vec<Scenario>,
+}
+
+/// 按名称登记的场景
+#[derive(Clone, Debug, Default)]
+pub struct ScenarioRegistry {
+    scenarios: BTreeMap<String, Scenario>,
+}
+
+impl ScenarioRegistry {
+    /// 内置场景
+    pub fn builtin() -> Self {
+        let mut reg = Self::default();
+        let smoke = WorkloadSize::builtin("smoke").unwrap_or_default();
+        let mut workload = WorkloadConfig::default();
+        smoke.apply(&mut workload);
+        reg.register(Scenario {
+            workload: Some(workload),
+            latency: Some(Backend::Zero),
+            caches: [CacheKind::Hashlink, CacheKind::Lru, CacheKind::MiniMoka]
+                .into_iter()
+                .filter(|k| k.available())
+                .collect(),
+            metrics: ScenarioMetrics {
+                stats: true,
+                ..ScenarioMetrics::default()
+            },
+            ..Scenario::new("smoke", "quick sanity check: small workload, no backend latency, three LRU crates")
+        });
+        for preset in [
+            WorkloadPreset::YcsbA,
+            WorkloadPreset::YcsbB,
+            WorkloadPreset::YcsbC,
+            WorkloadPreset::YcsbD,
+            WorkloadPreset::YcsbE,
+            WorkloadPreset::YcsbF,
+        ] {
+            let mut workload = WorkloadConfig::default();
+            preset.apply(&mut workload);
+            reg.register(Scenario {
+                workload: Some(workload),
+                ..Scenario::new(preset.to_string(), format!("YCSB core workload {preset}"))
+            });
+        }
+        reg.register(Scenario {
+            workload: Some(WorkloadConfig {
+                scan: Some(ScanConfig::default()),
+                ..WorkloadConfig::default()
+            }),
+            ..Scenario::new("scan-resistance", "hot zipf traffic interleaved with one-off sequential scans")
+        });
+        reg.register(Scenario {
+            workload: Some(WorkloadConfig {
+                phases: 4,
+                ..WorkloadConfig::default()
+            }),
+            metrics: ScenarioMetrics {
+                series: Some(100),
+                ..ScenarioMetrics::default()
+            },
+            ..Scenario::new("hotspot-shift", "hot set moves to different keys in each of four phases")
+        });
+        reg.register(Scenario {
+            cost: Some(CostModel::Sized),
+            caches: CacheKind::COST_AWARE.to_vec(),
+            ..Scenario::new("cost-bounded", "entries of varying size under a byte budget, evicting by cost")
+        });
+        for (name, mode, description) in [
+            ("bytes", ScenarioMode::Bytes, "Bytes values with sampled sizes"),
+            ("string-keys", ScenarioMode::StringKeys, "string keys with owned variable-length values"),
+            ("negative-cache", ScenarioMode::NegativeCache, "caching empty results for missing keys vs refetching"),
+            ("expiration", ScenarioMode::Expiration, "native TTL/TTI vs TTL wrappers"),
+            ("async", ScenarioMode::Async, "async cache awaited on the runtime vs the sync interface"),
+            ("loader", ScenarioMode::Loader, "concurrent get_or_load: native single flight vs manual"),
+            ("dogpile", ScenarioMode::Dogpile, "many tasks reading the same cold key at once"),
+            ("zipf-sweep", ScenarioMode::ZipfSweep, "every cache at each configured zipf_sweep value"),
+            ("contention", ScenarioMode::Threads, "shared caches under multi-thread contention"),
+            ("batch", ScenarioMode::Batch, "one-by-one calls vs get_many / insert_many"),
+        ] {
+            reg.register(Scenario { mode, ..Scenario::new(name, description) });
+        }
+        reg
+    }
+
+    /// 内置场景加上配置中 [`Config::scenario_files`] 列出的场景文件
+    pub fn for_config(cfg: &Config) -> Result<Self> {
+        let mut reg = Self::builtin();
+        for path in &cfg.scenario_files {
+            reg.load_file(path)?;
+        }
+        Ok(reg)
+    }
+
+    /// 登记场景，同名时替换
+    pub fn register(&mut self, scenario: Scenario) {
+        self.scenarios.insert(scenario.name.clone(), scenario);
+    }
+
+    /// 加载场景文件，按扩展名识别格式：`.yaml` / `.yml` 为 YAML，其余为 TOML
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let file: ScenarioFile = match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => serde_yaml_ng::from_str(&text)?,
+            _ => toml::from_str(&text)?,
+        };
+        for scenario in file.scenario {
+            if scenario.name.is_empty() {
+                return Err(AppError::Config(format!("{}: scenario without name", path.display())));
+            }
+            self.register(scenario);
+        }
+        Ok(())
+    }
+
+    /// 按名称查找
+    pub fn get(&self, name: &str) -> Result<&Scenario> {
+        self.scenarios.get(name).ok_or_else(|| {
+            let names: Vec<_> = self.scenarios.keys().map(String::as_str).collect();
+            AppError::Config(format!("unknown scenario `{name}`, expected one of: {}", names.join(", ")))
+        })
+    }
+
+    /// 按名称顺序遍历
+    pub fn iter(&self) -> impl Iterator<Item = &Scenario> {
+        self.scenarios.values()
+    }
+}
@@ -0,0 +1,99 @@
+//! 命中率时间序列模块
+//! 每 K 个操作记录一次累计与窗口命中率，用于绘制预热曲线、比较收敛速度
+
+use crate::Outcome;
+use serde::Serialize;
+
+/// 单个采样点
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct SeriesPoint {
+    /// 已执行的操作数
+    pub op: usize,
+    /// 从开始到此处的命中率（百分比）
+    pub cumulative: f64,
+    /// 最近一个窗口内的命中率（百分比）
+    pub window: f64,
+}
+
+/// 命中率序列记录器，写操作计入操作数但不计入命中率
+#[derive(Clone, Debug)]
+pub struct SeriesRecorder {
+    every: usize,
+    ops: usize,
+    hits: u64,
+    reads: u64,
+    win_hits: u64,
+    win_reads: u64,
+    points: Vec<SeriesPoint>,
+}
+
+impl SeriesRecorder {
+    /// 每 `every` 个操作采样一次（至少为 1）
+    pub fn new(every: usize) -> Self {
+        Self {
+            every: every.max(1),
+            ops: 0,
+            hits: 0,
+            reads: 0,
+            win_hits: 0,
+            win_reads: 0,
+            points: Vec::new(),
+        }
+    }
+
+    /// 记录一次操作
+    #[inline]
+    pub fn record(&mut self, outcome: Outcome) {
+        self.ops += 1;
+        match outcome {
+            Outcome::Hit => {
+                self.hits += 1;
+                self.reads += 1;
+                self.win_hits += 1;
+                self.win_reads += 1;
+            }
+            Outcome::Miss | Outcome::Failed => {
+                self.reads += 1;
+                self.win_reads += 1;
+            }
+            Outcome::Scan { hits, misses } => {
+                self.hits += hits;
+                self.reads += hits + misses;
+                self.win_hits += hits;
+                self.win_reads += hits + misses;
+            }
+            Outcome::Write | Outcome::Insert | Outcome::Delete => {}
+        }
+        if self.ops.is_multiple_of(self.every) {
+            self.sample();
+        }
+    }
+
+    fn sample(&mut self) {
+        self.points.push(SeriesPoint {
+            op: self.ops,
+            cumulative: rate(self.hits, self.reads),
+            window: rate(self.win_hits, self.win_reads),
+        });
+        self.win_hits = 0;
+        self.win_reads = 0;
+    }
+
+    /// 结束记录，末尾不足一个窗口的部分也会采样
+    pub fn finish(mut self) -> Vec<SeriesPoint> {
+        if !self.ops.is_multiple_of(self.every) {
+            self.sample();
+        }
+        self.points
+    }
+}
+
+#[inline]
+fn rate(hits: u64, reads: u64) -> f64 {
+    if reads == 0 {
+        0.0
+    } else {
+        hits as f64 / reads as f64 * 100.0
+    }
+}
@@ -0,0 +1,274 @@
+//! 参数扫描模块
+//! 按缓存容量或 Zipf 参数扫描各实现，观察命中率曲线
+
+use crate::analysis::opt;
+use crate::{
+    Backend, CacheKind, CacheResult, CacheRunner, Config, MockClock, Op, Result, RunStats, TimeSource, WarmupConfig,
+    WarmupManager, WorkloadGenerator,
+};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// 容量扫描配置，容量按 Key 空间的百分比线性取点
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SweepConfig {
+    /// 起始百分比
+    pub from_pct: f64,
+    /// 结束百分比
+    pub to_pct: f64,
+    /// 取点数量
+    pub steps: usize,
+    /// 参与扫描的缓存
+    pub caches: Vec<CacheKind>,
+    /// 是否按容量预热，回放追踪时通常关闭以与论文的冷启动结果对齐
+    pub warmup: bool,
+}
+
+impl Default for SweepConfig {
+    fn default() -> Self {
+        Self {
+            from_pct: 1.0,
+            to_pct: 100.0,
+            steps: 10,
+            caches: CacheKind::ALL.to_vec(),
+            warmup: true,
+        }
+    }
+}
+
+impl SweepConfig {
+    /// 计算各扫描点的容量（至少为 1，去重）
+    pub fn capacities(&self, total_keys: usize) -> Vec<u64> {
+        let steps = self.steps.max(1);
+        let span = self.to_pct - self.from_pct;
+        let mut caps: Vec<u64> = (0..steps)
+            .map(|i| {
+                let pct = if steps == 1 {
+                    self.to_pct
+                } else {
+                    self.from_pct + span * i as f64 / (steps - 1) as f64
+                };
+                ((total_keys as f64 * pct / 100.0).round() as u64).max(1)
+            })
+            .collect();
+        caps.dedup();
+        caps
+    }
+}
+
+/// 扫描维度
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SweepAxis {
+    Capacity,
+    ZipfS,
+}
+
+/// 单个扫描点结果
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct SweepPoint {
+    /// 缓存名称，即 [`CacheKind::key`]，离线最优为 [`opt::NAME`]
+    pub cache: &'static str,
+    pub capacity: u64,
+    pub zipf_s: f64,
+    pub stats: RunStats,
+    /// 执行耗时（含预热），模拟时间模式下为模型时间，离线最优为 0
+    pub elapsed: Duration,
+    /// 执行的操作数
+    pub ops: usize,
+}
+
+impl SweepPoint {
+    /// 转为报告结果
+    pub fn to_result(&self) -> CacheResult {
+        CacheResult::new(self.cache, self.capacity, self.zipf_s, &self.stats, self.ops, self.elapsed)
+    }
+}
+
+/// 执行容量扫描，所有缓存共享同一工作负载；每个容量的预热序列按容量推导且各缓存相同。
+/// 每个扫描点额外附带 Belady 最优替换的结果作为上界。
+/// 超出 [`Config::budget`] 或收到中断时停止，返回已完成的扫描点，最后一点可能为部分结果
+pub async fn sweep_capacity(cfg: &Config, sweep: &SweepConfig) -> Result<Vec<SweepPoint>> {
+    let ops = cfg.workload_ops()?;
+    sweep_capacity_ops(cfg, sweep, &ops, cfg.workload.total_keys).await
+}
+
+/// 在给定操作序列（如追踪回放）上执行容量扫描，容量百分比相对 `key_space`
+pub async fn sweep_capacity_ops(
+    cfg: &Config,
+    sweep: &SweepConfig,
+    ops: &[Op],
+    key_space: usize,
+) -> Result<Vec<SweepPoint>> {
+    let runner = CacheRunner::from_config(cfg);
+    if cfg.simulated_time {
+        let runner = runner.with_clock(MockClock::new());
+        capacity_points(&runner, cfg, sweep, ops, key_space).await
+    } else {
+        capacity_points(&runner, cfg, sweep, ops, key_space).await
+    }
+}
+
+async fn capacity_points<T: TimeSource + Clone>(
+    runner: &CacheRunner<Backend, T>,
+    cfg: &Config,
+    sweep: &SweepConfig,
+    ops: &[Op],
+    key_space: usize,
+) -> Result<Vec<SweepPoint>> {
+    let zipf_s = cfg.workload.zipf_s;
+    let capacities = sweep.capacities(key_space);
+    let total = capacities.len() * sweep.caches.len() * ops.len();
+    let (started, mut done) = (Instant::now(), 0);
+    let mut points = Vec::new();
+    for capacity in capacities {
+        let warmup_ops = if sweep.warmup {
+            let warmup = WarmupConfig::for_capacity(capacity)
+                .with_strategy(cfg.warmup_strategy)
+                .with_seed(cfg.warmup().seed);
+            WarmupManager::new(warmup).generate_warmup_ops_for(ops)?
+        } else {
+            Vec::new()
+        };
+        for &cache in &sweep.caches {
+            if runner.expired() {
+                return Ok(points);
+            }
+            let runner = overall(runner, done, total, started);
+            points.push(run_point(&runner, cache, capacity, zipf_s, &warmup_ops, ops).await?);
+            done += ops.len();
+        }
+        if runner.expired() {
+            return Ok(points);
+        }
+        points.push(opt_point(capacity, zipf_s, &warmup_ops, ops)?);
+    }
+    Ok(points)
+}
+
+/// 在固定容量下按 Zipf 参数扫描，每个参数生成一次工作负载并由所有缓存共享，附带离线最优上界；
+/// 超出时间预算时同样返回已完成的扫描点
+pub async fn sweep_zipf(cfg: &Config, values: &[f64], caches: &[CacheKind]) -> Result<Vec<SweepPoint>> {
+    let runner = CacheRunner::from_config(cfg);
+    if cfg.simulated_time {
+        zipf_points(&runner.with_clock(MockClock::new()), cfg, values, caches).await
+    } else {
+        zipf_points(&runner, cfg, values, caches).await
+    }
+}
+
+async fn zipf_points<T: TimeSource + Clone>(
+    runner: &CacheRunner<Backend, T>,
+    cfg: &Config,
+    values: &[f64],
+    caches: &[CacheKind],
+) -> Result<Vec<SweepPoint>> {
+    let total = values.len() * caches.len() * cfg.workload.size;
+    let (started, mut done) = (Instant::now(), 0);
+    let mut points = Vec::new();
+    for &zipf_s in values {
+        let ops = WorkloadGenerator::new(cfg.workload.clone())
+            .generate_with_params(zipf_s, cfg.workload.total_keys)?;
+        let warmup_ops = WarmupManager::new(cfg.warmup()).generate_warmup_ops_for(&ops)?;
+        for &cache in caches {
+            if runner.expired() {
+                return Ok(points);
+            }
+            let runner = overall(runner, done, total, started);
+            points.push(run_point(&runner, cache, cfg.capacity, zipf_s, &warmup_ops, &ops).await?);
+            done += ops.len();
+        }
+        if runner.expired() {
+            return Ok(points);
+        }
+        points.push(opt_point(cfg.capacity, zipf_s, &warmup_ops, &ops)?);
+    }
+    Ok(points)
+}
+
+/// 把运行器的进度回调折算为整个扫描的进度：此前已完成 `done` 个操作，共 `total` 个
+fn overall<T: TimeSource + Clone>(
+    runner: &CacheRunner<Backend, T>,
+    done: usize,
+    total: usize,
+    started: Instant,
+) -> CacheRunner<Backend, T> {
+    match runner.progress() {
+        Some(hook) => {
+            let hook = hook.within(done, total, started);
+            runner.clone().with_progress(hook)
+        }
+        None => runner.clone(),
+    }
+}
+
+/// 运行单个缓存的扫描点，耗时取自运行器的时间源
+async fn run_point<T: TimeSource>(
+    runner: &CacheRunner<Backend, T>,
+    cache: CacheKind,
+    capacity: u64,
+    zipf_s: f64,
+    warmup_ops: &[Op],
+    ops: &[Op],
+) -> Result<SweepPoint> {
+    let start = runner.clock().now();
+    let stats = cache.run(runner, capacity, warmup_ops, ops).await?;
+    Ok(SweepPoint {
+        cache: cache.key(),
+        capacity,
+        zipf_s,
+        stats,
+        elapsed: runner.clock().now().saturating_sub(start),
+        ops: stats.ops as usize,
+    })
+}
+
+/// 计算离线最优的扫描点；离线算法的计算耗时不是缓存吞吐量，耗时记为 0，吞吐量对比中留空
+fn opt_point(capacity: u64, zipf_s: f64, warmup_ops: &[Op], ops: &[Op]) -> Result<SweepPoint> {
+    let stats = opt::belady(warmup_ops, ops, capacity)?;
+    Ok(SweepPoint {
+        cache: opt::NAME,
+        capacity,
+        zipf_s,
+        stats,
+        elapsed: Duration::ZERO,
+        ops: ops.len(),
+    })
+}
+
+/// 渲染命中率表：每行一个扫描值，每列一个缓存
+pub fn render_table(points: &[SweepPoint], axis: SweepAxis) -> String {
+    let mut caches: Vec<&str> = Vec::new();
+    for p in points {
+        if !caches.contains(&p.cache) {
+            caches.push(p.cache);
+        }
+    }
+    let row_key = |p: &SweepPoint| match axis {
+        SweepAxis::Capacity => p.capacity.to_string(),
+        SweepAxis::ZipfS => p.zipf_s.to_string(),
+    };
+    let head = match axis {
+        SweepAxis::Capacity => "capacity",
+        SweepAxis::ZipfS => "zipf_s",
+    };
+    let mut out = format!("{head:>10}");
+    for c in &caches {
+        let _ = write!(out, " {c:>12}");
+    }
+    for row in points.chunk_by(|a, b| row_key(a) == row_key(b)) {
+        let _ = write!(out, "\n{:>10}", row_key(&row[0]));
+        for c in &caches {
+            match row.iter().find(|p| p.cache == *c) {
+                Some(p) => {
+                    let _ = write!(out, " {:>11.2}%", p.stats.hit_rate());
+                }
+                None => {
+                    let _ = write!(out, " {:>12}", "-");
+                }
+            }
+        }
+    }
+    out
+}
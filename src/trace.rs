@@ -0,0 +1,367 @@
+//! 工作负载文件模块
+//! 二进制工作负载的写入与内存映射零拷贝回放，以及外部追踪文件的加载
+//!
+//! 二进制格式：8 字节魔数，随后每个操作 24 字节（小端 u64 的 tag、key、value）
+
+use crate::{AppError, Op, Result, Value};
+use memmap2::Mmap;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub mod arc;
+pub mod text;
+pub mod twitter;
+
+/// 文件魔数
+const MAGIC: &[u8; 8] = b"LRUBOPS1";
+
+/// 自动识别格式时读取的文件头长度
+const DETECT_LEN: usize = 4096;
+
+/// 单条记录字节数
+const RECORD: usize = 24;
+
+const TAG_READ: u64 = 0;
+const TAG_WRITE: u64 = 1;
+const TAG_PEEK: u64 = 2;
+const TAG_MISSING: u64 = 3;
+const TAG_DELETE: u64 = 4;
+const TAG_INSERT: u64 = 5;
+const TAG_SCAN: u64 = 6;
+
+/// 将操作序列写入二进制工作负载文件
+pub fn save_ops(path: impl AsRef<Path>, ops: &[Op]) -> Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    w.write_all(MAGIC)?;
+    for op in ops {
+        let (tag, key, val) = match *op {
+            Op::Read(k) => (TAG_READ, k, 0),
+            Op::Write(k, v) => (TAG_WRITE, k, v),
+            Op::Peek(k) => (TAG_PEEK, k, 0),
+            Op::ReadMissing(k) => (TAG_MISSING, k, 0),
+            Op::Delete(k) => (TAG_DELETE, k, 0),
+            Op::Insert(k, v) => (TAG_INSERT, k, v),
+            Op::Scan(k, len) => (TAG_SCAN, k, len),
+        };
+        for n in [tag, key as u64, val as u64] {
+            w.write_all(&n.to_le_bytes())?;
+        }
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// 将缓存导出的条目保存为写操作序列，可用 [`load_entries`] 恢复
+pub fn save_entries(path: impl AsRef<Path>, entries: &[(usize, usize)]) -> Result<()> {
+    let ops: Vec<Op> = entries.iter().map(|&(k, v)| Op::Write(k, v)).collect();
+    save_ops(path, &ops)
+}
+
+/// 读取 [`save_entries`] 保存的条目，顺序与导出时一致
+pub fn load_entries(path: impl AsRef<Path>) -> Result<Vec<(usize, usize)>> {
+    let ops = MmapOps::open(path)?;
+    Ok(ops
+        .view()
+        .iter()
+        .filter_map(|op| match op {
+            Op::Write(k, v) => Some((k, v)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// 内存映射的工作负载，按需解码，不做整体反序列化
+pub struct MmapOps {
+    map: Mmap,
+}
+
+impl MmapOps {
+    /// 映射并校验工作负载文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: 回放期间文件不应被其他进程修改
+        let map = unsafe { Mmap::map(&file)? };
+        if map.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(AppError::InvalidWorkload("bad magic".into()));
+        }
+        if !(map.len() - MAGIC.len()).is_multiple_of(RECORD) {
+            return Err(AppError::InvalidWorkload("truncated record".into()));
+        }
+        Ok(Self { map })
+    }
+
+    /// 操作数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.map.len() - MAGIC.len()) / RECORD
+    }
+
+    /// 是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 零拷贝视图
+    #[inline]
+    pub fn view(&self) -> OpView<'_> {
+        OpView(&self.map[MAGIC.len()..])
+    }
+}
+
+/// 借用映射内存的操作视图
+#[derive(Clone, Copy)]
+pub struct OpView<'a>(&'a [u8]);
+
+impl<'a> OpView<'a> {
+    /// 第 i 个操作，越界或 tag 未知时返回 None
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<Op> {
+        decode(self.0.get(i * RECORD..(i + 1) * RECORD)?)
+    }
+
+    /// 顺序解码所有操作，跳过未知 tag
+    pub fn iter(&self) -> impl Iterator<Item = Op> + 'a {
+        self.0.chunks_exact(RECORD).filter_map(decode)
+    }
+}
+
+#[inline]
+fn decode(rec: &[u8]) -> Option<Op> {
+    let word = |i: usize| {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(&rec[i * 8..i * 8 + 8]);
+        u64::from_le_bytes(b)
+    };
+    let key = word(1) as usize;
+    match word(0) {
+        TAG_READ => Some(Op::Read(key)),
+        TAG_WRITE => Some(Op::Write(key, word(2) as usize)),
+        TAG_PEEK => Some(Op::Peek(key)),
+        TAG_MISSING => Some(Op::ReadMissing(key)),
+        TAG_DELETE => Some(Op::Delete(key)),
+        TAG_INSERT => Some(Op::Insert(key, word(2) as usize)),
+        TAG_SCAN => Some(Op::Scan(key, word(2) as usize)),
+        _ => None,
+    }
+}
+
+/// 追踪文件格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceFormat {
+    /// [`save_ops`] 写出的二进制格式
+    Binary,
+    /// 每行 `GET key` / `SET key`
+    Text,
+    /// ARC 论文等学术追踪：每行 `起始块 [块数 ...]`，均为读
+    Arc,
+    /// Twitter 缓存集群追踪：`timestamp,key,key_size,value_size,client_id,op,ttl`
+    Twitter,
+}
+
+impl TraceFormat {
+    /// 全部格式
+    pub const ALL: [Self; 4] = [Self::Binary, Self::Text, Self::Arc, Self::Twitter];
+
+    /// 命令行中使用的名称
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Binary => "bin",
+            Self::Text => "text",
+            Self::Arc => "arc",
+            Self::Twitter => "twitter",
+        }
+    }
+
+    /// 按文件头识别：有魔数为二进制；首个有效行为 7 列逗号分隔时为 Twitter，
+    /// 以数字开头为 ARC，否则为文本
+    pub fn detect(path: impl AsRef<Path>) -> Result<Self> {
+        let mut head = Vec::with_capacity(DETECT_LEN);
+        File::open(path)?.take(DETECT_LEN as u64).read_to_end(&mut head)?;
+        if head.starts_with(MAGIC) {
+            return Ok(Self::Binary);
+        }
+        let head = String::from_utf8_lossy(&head);
+        let Some(first) = head.lines().map(str::trim).find(|l| !l.is_empty() && !l.starts_with('#')) else {
+            return Ok(Self::Text);
+        };
+        Ok(if first.split(',').count() == twitter::FIELDS {
+            Self::Twitter
+        } else if first.split_whitespace().next().is_some_and(|f| f.parse::<u64>().is_ok()) {
+            Self::Arc
+        } else {
+            Self::Text
+        })
+    }
+}
+
+impl FromStr for TraceFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.into_iter().find(|f| f.key() == s).ok_or_else(|| {
+            let keys: Vec<_> = Self::ALL.iter().map(|f| f.key()).collect();
+            AppError::Config(format!("unknown trace format `{s}`, expected one of: {}", keys.join(", ")))
+        })
+    }
+}
+
+/// 将追踪中的原始 key 映射为从 0 开始的稠密整数 id
+#[derive(Debug)]
+pub struct KeyMap<K = String>(HashMap<K, usize>);
+
+impl<K> Default for KeyMap<K> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<K: Hash + Eq> KeyMap<K> {
+    /// 取 key 的 id，首次出现时分配新 id
+    #[inline]
+    pub fn id<Q>(&mut self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let next = self.0.len();
+        match self.0.get(key) {
+            Some(&id) => id,
+            None => {
+                self.0.insert(key.to_owned(), next);
+                next
+            }
+        }
+    }
+
+    /// 已分配的 id 数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// 从追踪文件回放的工作负载，可替代 [`crate::WorkloadGenerator`]
+#[derive(Clone, Debug)]
+pub struct TraceWorkload {
+    ops: Vec<Op>,
+    key_space: usize,
+    /// 与 ops 一一对应的值大小（字节），格式未记录时为空
+    sizes: Option<Vec<u32>>,
+}
+
+impl TraceWorkload {
+    /// 按指定格式加载追踪文件，`format` 为空时自动识别
+    pub fn open(path: impl AsRef<Path>, format: Option<TraceFormat>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = match format {
+            Some(f) => f,
+            None => TraceFormat::detect(path)?,
+        };
+        match format {
+            TraceFormat::Binary => {
+                let ops: Vec<Op> = MmapOps::open(path)?.view().iter().collect();
+                let key_space = ops.iter().filter_map(|op| op.keys().last()).map(|k| k + 1).max().unwrap_or(0);
+                Ok(Self {
+                    ops,
+                    key_space,
+                    sizes: None,
+                })
+            }
+            TraceFormat::Text => {
+                let mut keys = KeyMap::default();
+                let ops = text::parse(BufReader::new(File::open(path)?), &mut keys)?;
+                Ok(Self {
+                    ops,
+                    key_space: keys.len(),
+                    sizes: None,
+                })
+            }
+            TraceFormat::Arc => {
+                let mut keys = KeyMap::default();
+                let ops = arc::parse(BufReader::new(File::open(path)?), &mut keys)?;
+                Ok(Self {
+                    ops,
+                    key_space: keys.len(),
+                    sizes: None,
+                })
+            }
+            TraceFormat::Twitter => {
+                let mut keys = KeyMap::default();
+                let (ops, sizes) = twitter::parse(BufReader::new(File::open(path)?), &mut keys)?;
+                Ok(Self {
+                    ops,
+                    key_space: keys.len(),
+                    sizes: Some(sizes),
+                })
+            }
+        }
+    }
+
+    /// 操作序列
+    #[inline]
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// key 空间大小（最大 id + 1）
+    #[inline]
+    pub fn key_space(&self) -> usize {
+        self.key_space
+    }
+
+    /// 每个操作的值大小（字节），仅部分格式记录
+    #[inline]
+    pub fn value_sizes(&self) -> Option<&[u32]> {
+        self.sizes.as_deref()
+    }
+
+    /// 转为指定值类型的操作序列；写入值按记录的大小由 [`Value::sized`] 生成，
+    /// 未记录大小时用 [`Value::load`]
+    pub fn generate<V: Value>(&self) -> Vec<Op<V>> {
+        let value = |i: usize, k| match &self.sizes {
+            Some(sizes) => V::sized(k, sizes[i] as usize),
+            None => V::load(k),
+        };
+        self.ops
+            .iter()
+            .enumerate()
+            .map(|(i, op)| match *op {
+                Op::Read(k) => Op::Read(k),
+                Op::Peek(k) => Op::Peek(k),
+                Op::ReadMissing(k) => Op::ReadMissing(k),
+                Op::Delete(k) => Op::Delete(k),
+                Op::Scan(k, len) => Op::Scan(k, len),
+                Op::Write(k, _) => Op::Write(k, value(i, k)),
+                Op::Insert(k, _) => Op::Insert(k, value(i, k)),
+            })
+            .collect()
+    }
+}
+
+/// 逐行读取文本追踪，跳过空行与 `#` 注释，产出 (行号, 去除首尾空白的内容)
+pub(crate) fn lines(reader: impl BufRead) -> impl Iterator<Item = Result<(usize, String)>> {
+    reader.lines().enumerate().filter_map(|(i, line)| match line {
+        Ok(line) => {
+            let text = line.trim();
+            (!text.is_empty() && !text.starts_with('#')).then(|| Ok((i + 1, text.to_owned())))
+        }
+        Err(e) => Some(Err(e.into())),
+    })
+}
+
+/// 无法解析的行
+pub(crate) fn bad_line(n: usize, line: &str) -> AppError {
+    AppError::InvalidWorkload(format!("line {n}: `{line}`"))
+}
@@ -0,0 +1,30 @@
+//! ARC 学术追踪格式
+//! 即 ARC 论文中 P1–P14、OLTP 等追踪：每行 `起始块 块数 忽略 请求号`，展开为连续块的读；
+//! 只有一列时视为单个块号；块数超过 [`ARC_MAX_BLOCKS`] 的行视为无法解析
+
+use crate::config::ARC_MAX_BLOCKS;
+use crate::trace::{KeyMap, bad_line, lines};
+use crate::{Op, Result};
+use std::io::BufRead;
+
+/// 解析 ARC 追踪，块号映射为稠密整数 id，全部为读操作
+pub(crate) fn parse(reader: impl BufRead, keys: &mut KeyMap<u64>) -> Result<Vec<Op>> {
+    let mut ops = Vec::new();
+    for line in lines(reader) {
+        let (n, line) = line?;
+        let mut fields = line.split_whitespace().map(str::parse::<u64>);
+        let start = match fields.next() {
+            Some(Ok(v)) => v,
+            _ => return Err(bad_line(n, &line)),
+        };
+        let count = match fields.next() {
+            None => 1,
+            Some(Ok(v)) if v <= ARC_MAX_BLOCKS => v,
+            _ => return Err(bad_line(n, &line)),
+        };
+        for block in start..start.saturating_add(count) {
+            ops.push(Op::Read(keys.id(&block)));
+        }
+    }
+    Ok(ops)
+}
@@ -0,0 +1,28 @@
+//! 文本追踪格式
+//! 每行一个操作：`GET key` 或 `SET key`，分隔符可为逗号或空白；空行与 `#` 开头的行忽略
+
+use crate::trace::{KeyMap, bad_line, lines};
+use crate::{Op, Result};
+use std::io::BufRead;
+
+/// 解析文本追踪，key 映射为稠密整数 id
+pub(crate) fn parse(reader: impl BufRead, keys: &mut KeyMap) -> Result<Vec<Op>> {
+    let mut ops = Vec::new();
+    for line in lines(reader) {
+        let (n, line) = line?;
+        let mut fields = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|f| !f.is_empty());
+        let (Some(op), Some(key)) = (fields.next(), fields.next()) else {
+            return Err(bad_line(n, &line));
+        };
+        let key = keys.id(key);
+        let op = if op.eq_ignore_ascii_case("get") || op.eq_ignore_ascii_case("read") {
+            Op::Read(key)
+        } else if op.eq_ignore_ascii_case("set") || op.eq_ignore_ascii_case("write") {
+            Op::Write(key, key)
+        } else {
+            return Err(bad_line(n, &line));
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
@@ -0,0 +1,47 @@
+//! Twitter 缓存集群追踪格式
+//! 即 twitter/cache-trace 开源的生产追踪（需先解压）：
+//! `timestamp,anonymized key,key size,value size,client id,operation,TTL`
+//!
+//! get/gets 为读；set/add/replace/cas/append/prepend/incr/decr 为写；delete 等无对应操作的行跳过
+
+use crate::trace::{KeyMap, bad_line, lines};
+use crate::{Op, Result};
+use std::io::BufRead;
+
+/// 每行字段数
+pub(crate) const FIELDS: usize = 7;
+
+const READS: [&str; 2] = ["get", "gets"];
+const WRITES: [&str; 8] = ["set", "add", "replace", "cas", "append", "prepend", "incr", "decr"];
+
+/// 解析 Twitter 追踪，返回操作序列与对应的值大小
+pub(crate) fn parse(reader: impl BufRead, keys: &mut KeyMap) -> Result<(Vec<Op>, Vec<u32>)> {
+    let (mut ops, mut sizes) = (Vec::new(), Vec::new());
+    for line in lines(reader) {
+        let (n, line) = line?;
+        // 匿名 key 可能含逗号：去掉首列后从右侧切出其余 5 列
+        let Some((_, rest)) = line.split_once(',') else {
+            return Err(bad_line(n, &line));
+        };
+        let mut cols = rest.rsplitn(FIELDS - 1, ',');
+        let (Some(_ttl), Some(op), Some(_client), Some(size), Some(_key_size), Some(key)) =
+            (cols.next(), cols.next(), cols.next(), cols.next(), cols.next(), cols.next())
+        else {
+            return Err(bad_line(n, &line));
+        };
+        let Ok(size) = size.parse::<u32>() else {
+            return Err(bad_line(n, &line));
+        };
+        let op = if READS.contains(&op) {
+            Op::Read(keys.id(key))
+        } else if WRITES.contains(&op) {
+            let id = keys.id(key);
+            Op::Write(id, id)
+        } else {
+            continue;
+        };
+        ops.push(op);
+        sizes.push(size);
+    }
+    Ok((ops, sizes))
+}
@@ -0,0 +1,208 @@
+//! 缓存值模块
+//! 定义工作负载中写入与回源加载的值类型
+
+use crate::config::{BYTES_MAX, BYTES_MEDIAN, BYTES_SIGMA};
+use crate::{AppError, Result, TraceWorkload, ValueSize};
+use bytes::Bytes;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use std::mem::size_of;
+use std::sync::LazyLock;
+
+/// 共享负载缓冲区，所有 Bytes 值都是它的切片，clone 只增加引用计数
+static PAYLOAD: LazyLock<Bytes> = LazyLock::new(|| Bytes::from(vec![0u8; BYTES_MAX]));
+
+/// 缓存值 trait
+pub trait Value: Clone {
+    /// 生成一次写入的值
+    fn generate<R: Rng>(rng: &mut R) -> Self;
+
+    /// 回源加载 key 对应的值（同一 key 结果固定）
+    fn load(key: usize) -> Self;
+
+    /// 按追踪中记录的值大小构造，不关心大小的类型退化为 [`Value::load`]
+    #[inline]
+    fn sized(key: usize, _len: usize) -> Self {
+        Self::load(key)
+    }
+
+    /// 按值大小分布生成一次写入的值，不关心大小的类型退化为 [`Value::generate`]
+    #[inline]
+    fn generate_sized<R: Rng>(rng: &mut R, _sizes: &SizeSampler) -> Self {
+        Self::generate(rng)
+    }
+
+    /// 值占用的字节数，供按权重淘汰的缓存使用
+    #[inline]
+    fn weight(&self) -> usize {
+        size_of::<Self>()
+    }
+
+    /// 序列化后的负载长度，远程缓存按此长度传输，读回时经 [`Value::sized`] 还原
+    #[inline]
+    fn payload_len(&self) -> usize {
+        size_of::<Self>()
+    }
+}
+
+impl Value for usize {
+    #[inline]
+    fn generate<R: Rng>(rng: &mut R) -> Self {
+        rng.random::<u32>() as usize
+    }
+
+    #[inline]
+    fn load(key: usize) -> Self {
+        key
+    }
+}
+
+impl Value for Bytes {
+    /// 长度服从对数正态分布，接近 HTTP 响应体的大小分布
+    #[inline]
+    fn generate<R: Rng>(rng: &mut R) -> Self {
+        payload(random_len(rng))
+    }
+
+    #[inline]
+    fn load(key: usize) -> Self {
+        payload(key_len(key))
+    }
+
+    #[inline]
+    fn sized(_key: usize, len: usize) -> Self {
+        payload(len)
+    }
+
+    #[inline]
+    fn generate_sized<R: Rng>(rng: &mut R, sizes: &SizeSampler) -> Self {
+        payload(sizes.sample(rng))
+    }
+
+    #[inline]
+    fn weight(&self) -> usize {
+        size_of::<Self>() + self.len()
+    }
+
+    #[inline]
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// 独占的变长缓冲区，长度分布与 [`Bytes`] 相同，但每个值各自分配，clone 会复制内容
+impl Value for Vec<u8> {
+    #[inline]
+    fn generate<R: Rng>(rng: &mut R) -> Self {
+        vec![0; clamp_len(random_len(rng))]
+    }
+
+    #[inline]
+    fn load(key: usize) -> Self {
+        vec![0; key_len(key)]
+    }
+
+    #[inline]
+    fn sized(_key: usize, len: usize) -> Self {
+        vec![0; clamp_len(len)]
+    }
+
+    #[inline]
+    fn generate_sized<R: Rng>(rng: &mut R, sizes: &SizeSampler) -> Self {
+        vec![0; clamp_len(sizes.sample(rng))]
+    }
+
+    #[inline]
+    fn weight(&self) -> usize {
+        size_of::<Self>() + self.len()
+    }
+
+    #[inline]
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// 由 [`ValueSize`] 解析出的长度采样器，追踪分布在构造时一次性加载
+#[derive(Clone, Debug)]
+pub struct SizeSampler {
+    model: ValueSize,
+    /// 追踪中记录的值大小，仅 [`ValueSize::Trace`] 使用
+    sizes: Vec<u32>,
+}
+
+impl SizeSampler {
+    /// 解析分布，追踪文件不含值大小时报错
+    pub fn new(model: &ValueSize) -> Result<Self> {
+        model.validate()?;
+        let sizes = match model {
+            ValueSize::Trace { path } => {
+                let tw = TraceWorkload::open(path, None)?;
+                let sizes = tw.value_sizes().unwrap_or_default().to_vec();
+                if sizes.is_empty() {
+                    let path = path.display();
+                    return Err(AppError::InvalidWorkload(format!("{path}: no value sizes recorded")));
+                }
+                sizes
+            }
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            model: model.clone(),
+            sizes,
+        })
+    }
+
+    /// 采样一个长度（字节），由调用方限制范围
+    #[inline]
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        match self.model {
+            ValueSize::Fixed { len } => len,
+            ValueSize::Uniform { min, max } => rng.random_range(min..=max),
+            ValueSize::LogNormal { median, sigma } => lognormal_len(rng, median, sigma),
+            ValueSize::Trace { .. } => self.sizes[rng.random_range(0..self.sizes.len())] as usize,
+        }
+    }
+
+    /// 分布的期望长度（字节），用于按平均条目大小换算字节预算
+    pub fn mean(&self) -> f64 {
+        match self.model {
+            ValueSize::Fixed { len } => len as f64,
+            ValueSize::Uniform { min, max } => (min + max) as f64 / 2.0,
+            ValueSize::LogNormal { median, sigma } => median as f64 * (sigma * sigma / 2.0).exp(),
+            ValueSize::Trace { .. } => {
+                self.sizes.iter().map(|&s| s as f64).sum::<f64>() / self.sizes.len() as f64
+            }
+        }
+    }
+}
+
+/// 默认分布（对数正态）采样的值长度
+#[inline]
+fn random_len<R: Rng>(rng: &mut R) -> usize {
+    lognormal_len(rng, BYTES_MEDIAN, BYTES_SIGMA)
+}
+
+#[inline]
+fn lognormal_len<R: Rng>(rng: &mut R, median: usize, sigma: f64) -> usize {
+    let z: f64 = rng.sample(StandardNormal);
+    (median as f64 * (sigma * z).exp()) as usize
+}
+
+/// 由 key 派生固定长度，避免回源再消耗随机数
+#[inline]
+pub(crate) fn key_len(key: usize) -> usize {
+    key.wrapping_mul(0x9E37_79B9) % (BYTES_MEDIAN * 2) + 1
+}
+
+/// 长度限制在 1..=BYTES_MAX
+#[inline]
+fn clamp_len(len: usize) -> usize {
+    len.clamp(1, BYTES_MAX)
+}
+
+/// 截取指定长度的共享负载
+#[inline]
+fn payload(len: usize) -> Bytes {
+    PAYLOAD.slice(..clamp_len(len))
+}
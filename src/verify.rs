@@ -0,0 +1,139 @@
+//! 正确性校验
+//! 以固定的小规模操作序列驱动各缓存，记录每个读请求的命中与否以及结束时的常驻 key，
+//! 与 `tests/golden` 下提交的记录对比：适配层的语义错误（如把 `put` 写成 `push`）会立即改变记录
+
+use crate::cache::micro::step;
+use crate::{CacheKind, CacheOps, Key, Op, Result, RunStats};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+/// 黄金记录使用的容量
+pub const GOLDEN_CAPACITY: u64 = 8;
+/// 黄金序列读写的 key 空间
+const GOLDEN_KEYS: usize = 24;
+/// 黄金序列的操作数
+const GOLDEN_OPS: usize = 400;
+/// 后端不存在的 key 最小从此 id 起
+const MISSING_BASE: usize = 1000;
+
+/// 准入频率草图以内部随机种子哈希、无法由主种子固定的实现，确定性模式下命中率仍在多次运行间浮动
+pub const NONDETERMINISTIC: &[CacheKind] = &[CacheKind::MokaSync, CacheKind::MokaSyncLru, CacheKind::MiniMoka];
+/// 淘汰在后台批量执行、常驻条目数可暂时超过容量的实现
+pub const DEFERRED_EVICTION: &[CacheKind] = &[CacheKind::MokaSync, CacheKind::MokaSyncLru];
+
+/// 固定的操作序列，覆盖全部操作类型
+pub fn golden_ops() -> Vec<Op> {
+    random_ops(0x601d, GOLDEN_KEYS, GOLDEN_OPS)
+}
+
+/// 以 `seed` 生成 `len` 个覆盖全部操作类型的操作，读写集中在 `keys` 个 key 上且低 id 更热；
+/// 以 ChaCha 生成，跨平台与 rand 版本保持一致
+pub fn random_ops(seed: u64, keys: usize, len: usize) -> Vec<Op> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut next_new = keys;
+    // 新 key 至多 len 个，不存在的 key 排在其后
+    let missing = MISSING_BASE.max(keys + len);
+    (0..len)
+        .map(|_| {
+            // 两次均匀采样取小者
+            let key = rng.random_range(0..keys).min(rng.random_range(0..keys));
+            match rng.random_range(0..100) {
+                0..60 => Op::Read(key),
+                60..68 => Op::Peek(key),
+                68..80 => Op::Write(key, key),
+                80..86 => Op::Delete(key),
+                86..90 => {
+                    let id = next_new;
+                    next_new += 1;
+                    Op::Insert(id, id)
+                }
+                90..94 => Op::ReadMissing(missing + key),
+                _ => Op::Scan(key, 3),
+            }
+        })
+        .collect()
+}
+
+/// 一次回放的记录
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Transcript {
+    /// 各读请求依次命中（`H`）或未命中（`M`），范围扫描按 key 展开
+    pub reads: String,
+    /// 结束时仍在缓存中的 key，升序
+    pub resident: Vec<usize>,
+}
+
+impl Transcript {
+    /// 以 `capacity` 新建 `kind`，同步回放 `ops`（同 [`crate::replay_sync`]），
+    /// 之后逐个 peek 序列中出现过的 key 得到常驻集合
+    pub fn record(kind: CacheKind, capacity: u64, ops: &[Op]) -> Result<Self> {
+        let mut cache = kind.build(capacity)?;
+        let mut stats = RunStats::default();
+        let mut reads = String::new();
+        for op in ops {
+            step(cache.as_mut(), op, &mut stats, |hit| reads.push(if hit { 'H' } else { 'M' }));
+        }
+        let max = ops.iter().flat_map(|op| op.keys()).map(|k| k.id()).max().unwrap_or(0);
+        let resident = (0..=max).filter(|k| cache.peek(k)).collect();
+        Ok(Self { reads, resident })
+    }
+}
+
+/// 黄金文件格式：`reads` 与 `resident` 各一行
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "reads {}", self.reads)?;
+        write!(f, "resident")?;
+        for k in &self.resident {
+            write!(f, " {k}")?;
+        }
+        writeln!(f)
+    }
+}
+
+/// 两个实现的常驻条目首次不一致的位置
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResidentDiff {
+    /// 刚执行完的操作下标
+    pub op: usize,
+    /// 第一个实现的常驻 key，按最久未使用到最近使用排序（不支持导出时升序）
+    pub a: Vec<usize>,
+    /// 第二个实现的常驻 key，排序同上
+    pub b: Vec<usize>,
+}
+
+impl ResidentDiff {
+    /// 同步回放 `ops`，每个操作后比较两个实现的常驻条目（含顺序），返回首个不一致处；
+    /// 同一淘汰策略的不同实现应始终一致，不一致说明适配层或 crate 本身有缺陷
+    pub fn find(a: CacheKind, b: CacheKind, capacity: u64, ops: &[Op]) -> Result<Option<Self>> {
+        let (mut ca, mut cb) = (a.build(capacity)?, b.build(capacity)?);
+        let max = ops.iter().flat_map(|op| op.keys()).map(|k| k.id()).max().unwrap_or(0);
+        let mut stats = RunStats::default();
+        for (i, op) in ops.iter().enumerate() {
+            step(ca.as_mut(), op, &mut stats, |_| ());
+            step(cb.as_mut(), op, &mut stats, |_| ());
+            let (ra, rb) = (resident(ca.as_mut(), max), resident(cb.as_mut(), max));
+            if ra != rb {
+                return Ok(Some(Self { op: i, a: ra, b: rb }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl fmt::Display for ResidentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "resident entries diverge after op {}: {:?} vs {:?}", self.op, self.a, self.b)
+    }
+}
+
+/// 常驻 key：优先按导出顺序，不支持导出时逐个 peek 至 `max`
+fn resident(cache: &mut dyn CacheOps, max: usize) -> Vec<usize> {
+    match cache.export_entries() {
+        Some(entries) => entries.into_iter().map(|(k, _)| k).collect(),
+        None => (0..=max).filter(|k| cache.peek(k)).collect(),
+    }
+}
@@ -0,0 +1,43 @@
+//! ARC 目标大小自适应：命中 B1 增大 T1 目标 p，命中 B2 减小 p，步长为另一幽灵链表与所在链表长度之比
+
+#![cfg(feature = "hashlink")]
+
+use cache_bench::{ArcCache, CacheOps};
+
+/// T2 = {1, 2}，T1 = {4, 5}，B1 = {3}
+fn with_b1_ghost() -> ArcCache<usize> {
+    let mut cache = ArcCache::new(4).expect("build arc");
+    for k in [1, 2] {
+        cache.insert(k, k);
+        cache.get(&k);
+    }
+    for k in [3, 4, 5] {
+        cache.insert(k, k);
+    }
+    assert!(!cache.peek(&3));
+    assert_eq!(cache.target(), 0);
+    cache
+}
+
+#[test]
+fn b1_hit_grows_target() {
+    let mut cache = with_b1_ghost();
+    cache.insert(3, 3);
+    assert_eq!(cache.target(), 1);
+    assert!(cache.peek(&3));
+    assert_eq!(cache.len(), 4);
+}
+
+#[test]
+fn b2_hit_shrinks_target() {
+    let mut cache = with_b1_ghost();
+    // p = 1，B1 = {4}，T1 = {5}，T2 = {1, 2, 3}
+    cache.insert(3, 3);
+    // T1 未超出目标，从 T2 淘汰 1 进入 B2
+    cache.insert(6, 6);
+    assert!(!cache.peek(&1));
+    cache.insert(1, 1);
+    assert_eq!(cache.target(), 0);
+    assert!(cache.peek(&1));
+    assert_eq!(cache.len(), 4);
+}
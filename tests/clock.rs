@@ -0,0 +1,47 @@
+//! CLOCK 二次机会：引用位为 1 的条目被指针跳过一次并清零，下一圈未再访问时淘汰
+
+use cache_bench::{CacheOps, Clock};
+
+/// 插入 `keys` 的容量为 `capacity` 的缓存
+fn clock(capacity: usize, keys: &[usize]) -> Clock<usize> {
+    let mut cache = Clock::new(capacity).expect("build clock");
+    for &k in keys {
+        cache.insert(k, k);
+    }
+    cache
+}
+
+#[test]
+fn referenced_entry_gets_one_more_pass() {
+    let mut cache = clock(3, &[1, 2, 3]);
+    cache.get(&1);
+    cache.insert(4, 4);
+    assert!(cache.peek(&1));
+    assert!(!cache.peek(&2));
+    // 4 占用 2 的槽位，指针停在其后，依次淘汰 3 与引用位已清零的 1
+    assert_eq!(cache.evict_one(), Some(3));
+    assert_eq!(cache.evict_one(), Some(1));
+    assert_eq!(cache.evict_one(), Some(4));
+    assert_eq!(cache.evict_one(), None);
+}
+
+#[test]
+fn all_referenced_evicts_after_full_turn() {
+    let mut cache = clock(3, &[1, 2, 3]);
+    for k in [1, 2, 3] {
+        cache.get(&k);
+    }
+    // 转一整圈清零后回到起点
+    assert_eq!(cache.evict_one(), Some(1));
+    assert!(cache.peek(&2) && cache.peek(&3));
+}
+
+#[test]
+fn update_sets_reference() {
+    let mut cache = clock(2, &[1, 2]);
+    // 覆盖写入视为访问
+    cache.insert(1, 10);
+    cache.insert(3, 3);
+    assert!(!cache.peek(&2));
+    assert_eq!(cache.get(&1), Some(&10));
+}
@@ -0,0 +1,78 @@
+//! 代价限额性质测试：随机操作序列下，记录的代价总和始终等于常驻条目的代价之和且不超出预算，
+//! 内层缓存因条目数上限自行淘汰时也成立
+
+use cache_bench::{CacheOps, Clock, CostBounded, Gdsf, NoCache, S3Fifo, Sieve, unit_cost};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// key 空间，远大于预算可容纳的条目数
+const KEYS: usize = 32;
+const BUDGET: u64 = 12;
+
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    Get(usize),
+    Insert(usize),
+    Remove(usize),
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    let key = 0..KEYS;
+    prop_oneof![
+        3 => key.clone().prop_map(Step::Get),
+        6 => key.clone().prop_map(Step::Insert),
+        1 => key.prop_map(Step::Remove),
+    ]
+}
+
+/// 代价 1 到 5 不等
+fn cost(key: &usize, _: &usize) -> u32 {
+    (key % 5 + 1) as u32
+}
+
+fn check<C: CacheOps>(inner: C, steps: &[Step]) -> Result<(), TestCaseError> {
+    let name = inner.name();
+    let mut cache = CostBounded::new(inner, cost, BUDGET).expect("wrap cache");
+    for (i, &s) in steps.iter().enumerate() {
+        match s {
+            Step::Get(k) => {
+                cache.get(&k);
+            }
+            Step::Insert(k) => cache.insert(k, k),
+            Step::Remove(k) => {
+                cache.remove(&k);
+            }
+        }
+        let resident: u64 = (0..KEYS).filter(|k| cache.peek(k)).map(|k| u64::from(cost(&k, &k))).sum();
+        prop_assert_eq!(cache.used(), resident, "{} step {}: recorded cost differs from resident entries", name, i);
+        prop_assert!(cache.used() <= cache.budget(), "{name} step {i}: used {} exceeds budget", cache.used());
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn used_matches_resident_within_budget(inner_capacity in 1usize..8, steps in prop::collection::vec(step(), 1..300)) {
+        // 条目数上限足够时只按代价淘汰
+        check(S3Fifo::new(KEYS).expect("build"), &steps)?;
+        check(Sieve::new(KEYS).expect("build"), &steps)?;
+        check(Clock::new(KEYS).expect("build"), &steps)?;
+        check(Gdsf::new(KEYS as u64, unit_cost).expect("build"), &steps)?;
+        // 条目数上限较小时内层缓存自行淘汰
+        check(S3Fifo::new(inner_capacity).expect("build"), &steps)?;
+        check(Sieve::new(inner_capacity).expect("build"), &steps)?;
+        check(Clock::new(inner_capacity).expect("build"), &steps)?;
+        #[cfg(feature = "lru")]
+        check(cache_bench::lru_cache(inner_capacity).expect("build"), &steps)?;
+        #[cfg(feature = "hashlink")]
+        check(hashlink::LruCache::new(inner_capacity), &steps)?;
+    }
+}
+
+#[test]
+fn rejects_caches_that_cannot_evict() {
+    assert!(CostBounded::new(NoCache, cost, BUDGET).is_err());
+    assert!(CostBounded::new(HashMap::<usize, usize>::new(), cost, BUDGET).is_err());
+    #[cfg(feature = "hashlink")]
+    assert!(CostBounded::new(cache_bench::ArcCache::new(4).expect("build"), cost, BUDGET).is_err());
+}
@@ -0,0 +1,36 @@
+//! hashlink 与 lru 的差分测试：两者都是严格 LRU，相同操作序列下每一步的常驻条目与顺序都应一致
+//!
+//! 默认运行较短的序列；长时间运行：`DIFF_OPS=5000000 cargo test --release --test differential -- --ignored`
+
+#![cfg(all(feature = "hashlink", feature = "lru"))]
+
+use cache_bench::{CacheKind, ResidentDiff, random_ops};
+
+/// 容量远小于 key 空间，持续触发淘汰
+const CAPACITY: u64 = 64;
+const KEYS: usize = 256;
+
+fn differential(seed: u64, len: usize) {
+    let ops = random_ops(seed, KEYS, len);
+    let diff = ResidentDiff::find(CacheKind::Hashlink, CacheKind::Lru, CAPACITY, &ops).expect("build caches");
+    if let Some(d) = diff {
+        panic!("hashlink vs lru (seed {seed}): {d}, op {:?}", ops[d.op]);
+    }
+}
+
+#[test]
+fn hashlink_matches_lru() {
+    for seed in 0..4 {
+        differential(seed, 50_000);
+    }
+}
+
+#[test]
+#[ignore = "long-running, set DIFF_OPS to choose the length"]
+fn hashlink_matches_lru_long() {
+    let len = std::env::var("DIFF_OPS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(2_000_000);
+    differential(0xd1ff, len);
+}
@@ -0,0 +1,62 @@
+//! key 分布：各分布只采样 [1, n]，相同种子的采样序列与生成的工作负载逐项相同
+
+use cache_bench::{DistributionKind, KeyDistribution, WorkloadConfig, WorkloadGenerator};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const KEYS: usize = 1000;
+const SAMPLES: usize = 10_000;
+
+const KINDS: [DistributionKind; 5] = [
+    DistributionKind::Uniform,
+    DistributionKind::Zipf,
+    DistributionKind::ScrambledZipf,
+    DistributionKind::Latest,
+    DistributionKind::Hotspot { hot_keys: 0.1, hot_ops: 0.9 },
+];
+
+fn samples(kind: DistributionKind, seed: u64) -> Vec<usize> {
+    let mut dist: Box<dyn KeyDistribution> = kind.build(KEYS, 1.0).expect("build distribution");
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..SAMPLES).map(|_| dist.sample(&mut rng)).collect()
+}
+
+#[test]
+fn samples_stay_in_key_space() {
+    for kind in KINDS {
+        for k in samples(kind, 7) {
+            assert!((1..=KEYS).contains(&k), "{kind:?} sampled {k}");
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_samples() {
+    for kind in KINDS {
+        assert_eq!(samples(kind, 7), samples(kind, 7), "{kind:?}");
+        assert_ne!(samples(kind, 7), samples(kind, 8), "{kind:?}");
+    }
+}
+
+#[test]
+fn zipf_favors_low_ranks() {
+    let keys = samples(DistributionKind::Zipf, 7);
+    let top = keys.iter().filter(|&&k| k <= KEYS / 10).count();
+    // s = 1 时前 10% 的 key 约占 70% 的请求
+    assert!(top > SAMPLES / 2, "top 10% keys got {top} of {SAMPLES}");
+}
+
+fn workload_keys(distribution: DistributionKind, seed: u64) -> Vec<usize> {
+    let mut cfg = WorkloadConfig::default();
+    (cfg.distribution, cfg.seed, cfg.total_keys, cfg.size) = (distribution, seed, KEYS, SAMPLES);
+    let ops = WorkloadGenerator::new(cfg).generate::<usize, usize>().expect("generate workload");
+    ops.iter().map(|op| *op.key()).collect()
+}
+
+#[test]
+fn workload_is_deterministic_per_seed() {
+    for kind in KINDS {
+        assert_eq!(workload_keys(kind, 7), workload_keys(kind, 7), "{kind:?}");
+        assert_ne!(workload_keys(kind, 7), workload_keys(kind, 8), "{kind:?}");
+    }
+}
@@ -0,0 +1,54 @@
+//! GDSF 膨胀值：淘汰时膨胀值 L 提升到被淘汰条目的优先级，新条目以 L 为起点，
+//! 使长期未访问的高频条目逐渐老化；同频次时代价大的条目先淘汰
+
+use cache_bench::{CacheOps, Gdsf, unit_cost};
+
+#[test]
+fn inflation_ages_frequent_entries() {
+    let mut cache = Gdsf::new(2, unit_cost).expect("build gdsf");
+    cache.insert(1, 1);
+    cache.insert(2, 2);
+    // H(1) = 2，H(2) = 1
+    cache.get(&1);
+    // 淘汰 2，L = 1，H(3) = L + 1 = 2
+    cache.insert(3, 3);
+    assert!(!cache.peek(&2));
+    // 1 与 3 优先级相同，较早更新的 1 先淘汰；没有膨胀时会淘汰只访问过一次的 3
+    cache.insert(4, 4);
+    assert!(!cache.peek(&1));
+    assert!(cache.peek(&3) && cache.peek(&4));
+}
+
+#[test]
+fn evicts_lowest_priority_first() {
+    let mut cache = Gdsf::new(3, unit_cost).expect("build gdsf");
+    for k in [1, 2, 3] {
+        cache.insert(k, k);
+    }
+    for _ in 0..2 {
+        cache.get(&1);
+    }
+    cache.get(&2);
+    assert_eq!(cache.evict_one(), Some(3));
+    assert_eq!(cache.evict_one(), Some(2));
+    assert_eq!(cache.evict_one(), Some(1));
+    assert_eq!(cache.used(), 0);
+}
+
+/// key 不小于 100 的条目代价为 4，其余为 1
+fn cost(key: &usize, _: &usize) -> u32 {
+    if *key >= 100 { 4 } else { 1 }
+}
+
+#[test]
+fn costly_entry_evicted_first() {
+    let mut cache = Gdsf::new(5, cost).expect("build gdsf");
+    // H(100) = 1/4，H(1) = 1
+    cache.insert(100, 0);
+    cache.insert(1, 1);
+    assert_eq!(cache.used(), 5);
+    cache.insert(2, 2);
+    assert!(!cache.peek(&100));
+    assert!(cache.peek(&1) && cache.peek(&2));
+    assert_eq!(cache.used(), 2);
+}
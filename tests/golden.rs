@@ -0,0 +1,36 @@
+//! 黄金记录测试：固定操作序列下各缓存的命中序列与常驻集合须与 `tests/golden/<cache>.txt` 一致
+//!
+//! 有意修改淘汰行为或适配层后，以 `UPDATE_GOLDEN=1 cargo test --test golden` 重新生成并审阅差异
+
+use cache_bench::{CacheKind, GOLDEN_CAPACITY, Transcript, golden_ops};
+use std::fs;
+use std::path::PathBuf;
+
+fn golden_path(kind: CacheKind) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.txt", kind.key()))
+}
+
+#[test]
+fn golden_transcripts() {
+    let ops = golden_ops();
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut mismatched = Vec::new();
+    for &kind in CacheKind::ALL {
+        let actual = Transcript::record(kind, GOLDEN_CAPACITY, &ops)
+            .expect("build cache")
+            .to_string();
+        let path = golden_path(kind);
+        if update {
+            fs::write(&path, &actual).expect("write golden file");
+            continue;
+        }
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("{}: {e}, run with UPDATE_GOLDEN=1 to create it", path.display()));
+        if actual != expected {
+            mismatched.push(format!("{}:\n  expected {expected}  actual   {actual}", kind.key()));
+        }
+    }
+    assert!(mismatched.is_empty(), "golden transcripts differ:\n{}", mismatched.join("\n"));
+}
@@ -0,0 +1,55 @@
+//! S3-FIFO 幽灵晋升：从小队列淘汰的 key 记入幽灵队列，再次插入时直接进入主队列，
+//! 之后只访问一次的新条目先于它从小队列淘汰
+
+use cache_bench::{CacheOps, S3Fifo};
+
+/// 容量 10：小队列 1 个条目，幽灵队列 9 个 key
+const CAPACITY: usize = 10;
+
+/// 填满后再插入一个新 key，把最早的 key 0 从小队列淘汰进幽灵队列
+fn with_ghost() -> S3Fifo<usize> {
+    let mut cache = S3Fifo::new(CAPACITY).expect("build s3-fifo");
+    for k in 0..=CAPACITY {
+        cache.insert(k, k);
+    }
+    assert!(!cache.peek(&0));
+    cache
+}
+
+#[test]
+fn ghost_hit_enters_main() {
+    let mut cache = with_ghost();
+    cache.insert(0, 0);
+    assert!(cache.peek(&0));
+    // 一次性的新条目都从小队列淘汰，主队列中的 0 保留
+    for k in 100..200 {
+        cache.insert(k, k);
+        assert!(cache.peek(&0), "0 evicted by one-hit key {k}");
+    }
+}
+
+#[test]
+fn fresh_key_stays_in_small() {
+    let mut cache = with_ghost();
+    // 未进入幽灵队列的 key 与其他新条目一样从小队列淘汰
+    cache.insert(50, 50);
+    for k in 100..200 {
+        cache.insert(k, k);
+    }
+    assert!(!cache.peek(&50));
+}
+
+#[test]
+fn removed_key_is_not_remembered() {
+    let mut cache = S3Fifo::new(CAPACITY).expect("build s3-fifo");
+    for k in 0..CAPACITY {
+        cache.insert(k, k);
+    }
+    // 删除不进入幽灵队列，再次插入仍进入小队列
+    assert!(cache.remove(&0));
+    cache.insert(0, 0);
+    for k in 100..200 {
+        cache.insert(k, k);
+    }
+    assert!(!cache.peek(&0));
+}
@@ -0,0 +1,37 @@
+//! Zipf 打散：排名到 key id 的映射是 [1, n] 上的双射，由种子唯一确定，范围外的 id 原样保留
+
+use cache_bench::Scramble;
+
+const KEYS: usize = 1000;
+
+#[test]
+fn is_bijection_on_key_space() {
+    for seed in 0..8 {
+        let scramble = Scramble::new(KEYS, seed);
+        let mut ids: Vec<usize> = (1..=KEYS).map(|rank| scramble.map(rank)).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, (1..=KEYS).collect::<Vec<_>>(), "seed {seed}");
+    }
+}
+
+#[test]
+fn same_seed_same_mapping() {
+    assert_eq!(Scramble::new(KEYS, 7), Scramble::new(KEYS, 7));
+    assert_ne!(Scramble::new(KEYS, 7), Scramble::new(KEYS, 8));
+}
+
+#[test]
+fn decorrelates_hot_ranks() {
+    let scramble = Scramble::new(KEYS, 7);
+    // 最热的 10 个排名不应仍映射到最小的 10 个 id
+    let hot: Vec<usize> = (1..=10).map(|rank| scramble.map(rank)).collect();
+    assert!(hot.iter().any(|&id| id > 10), "hot ranks map to {hot:?}");
+}
+
+#[test]
+fn keeps_ids_outside_key_space() {
+    let scramble = Scramble::new(KEYS, 7);
+    assert_eq!(scramble.map(0), 0);
+    assert_eq!(scramble.map(KEYS + 1), KEYS + 1);
+    assert_eq!(scramble.into_perm()[0], 0);
+}
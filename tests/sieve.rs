@@ -0,0 +1,50 @@
+//! SIEVE 指针移动：淘汰从指针处继续而不是每次回到队尾，沿途清除访问位，到达队头后回绕
+
+use cache_bench::{CacheOps, Sieve};
+
+/// 插入 `keys` 的容量为 `capacity` 的缓存
+fn sieve(capacity: usize, keys: &[usize]) -> Sieve<usize> {
+    let mut cache = Sieve::new(capacity).expect("build sieve");
+    for &k in keys {
+        cache.insert(k, k);
+    }
+    cache
+}
+
+#[test]
+fn hand_skips_visited_and_keeps_position() {
+    let mut cache = sieve(3, &[1, 2, 3]);
+    cache.get(&1);
+    // 指针从队尾出发，清除 1 的访问位后淘汰 2
+    cache.insert(4, 4);
+    assert!(!cache.peek(&2));
+    // 指针停在 3，1 虽已无访问位仍保留
+    assert_eq!(cache.evict_one(), Some(3));
+    assert_eq!(cache.evict_one(), Some(4));
+    assert_eq!(cache.evict_one(), Some(1));
+    assert_eq!(cache.evict_one(), None);
+}
+
+#[test]
+fn hand_wraps_to_tail_when_all_visited() {
+    let mut cache = sieve(2, &[1, 2]);
+    cache.get(&1);
+    cache.get(&2);
+    // 清除全部访问位后回到队尾
+    cache.insert(3, 3);
+    assert!(!cache.peek(&1));
+    assert!(cache.peek(&2) && cache.peek(&3));
+    assert_eq!(cache.evict_one(), Some(2));
+}
+
+#[test]
+fn removing_hand_moves_it_forward() {
+    let mut cache = sieve(3, &[1, 2, 3]);
+    cache.get(&1);
+    cache.insert(4, 4);
+    // 指针指向 3，删除后前移到 4
+    assert!(cache.remove(&3));
+    cache.insert(5, 5);
+    assert_eq!(cache.evict_one(), Some(4));
+    assert!(cache.peek(&1) && cache.peek(&5));
+}
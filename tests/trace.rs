@@ -0,0 +1,29 @@
+//! 追踪解析：ARC 追踪按行展开为连续块的读，异常的块数被拒绝
+
+use cache_bench::config::ARC_MAX_BLOCKS;
+use cache_bench::{AppError, Op, Result, TraceFormat, TraceWorkload};
+use std::{env, fs, process};
+
+fn open_arc(name: &str, text: &str) -> Result<TraceWorkload> {
+    let path = env::temp_dir().join(format!("lru-bench-{}-{name}.arc", process::id()));
+    fs::write(&path, text).expect("write trace");
+    let trace = TraceWorkload::open(&path, Some(TraceFormat::Arc));
+    fs::remove_file(&path).expect("remove trace");
+    trace
+}
+
+#[test]
+fn arc_expands_block_ranges() {
+    let trace = open_arc("expand", "10 3 0 0\n11\n").expect("parse trace");
+    let keys: Vec<usize> = trace.ops().iter().map(|op| *op.key()).collect();
+    assert_eq!(keys, [0, 1, 2, 1]);
+    assert!(trace.ops().iter().all(|op| matches!(op, Op::Read(_))));
+}
+
+#[test]
+fn arc_rejects_oversized_counts() {
+    let oversized = open_arc("oversized", &format!("0 {} 0 0\n", ARC_MAX_BLOCKS + 1));
+    assert!(matches!(oversized, Err(AppError::InvalidWorkload(_))));
+    let max = open_arc("max", &format!("0 {ARC_MAX_BLOCKS} 0 0\n")).expect("parse trace");
+    assert_eq!(max.ops().len() as u64, ARC_MAX_BLOCKS);
+}
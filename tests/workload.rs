@@ -0,0 +1,37 @@
+//! 工作负载生成：扫描、范围扫描与分阶段生成的操作序列
+
+use cache_bench::{Op, ScanConfig, WorkloadConfig, WorkloadGenerator};
+
+fn generate(cfg: WorkloadConfig) -> Vec<Op<usize, usize>> {
+    WorkloadGenerator::new(cfg).generate().expect("generate workload")
+}
+
+#[test]
+fn default_scan_workload_scans() {
+    let mut cfg = WorkloadConfig::default();
+    cfg.scan = Some(ScanConfig::default());
+    let total_keys = cfg.total_keys;
+    let ops = generate(cfg);
+    // 扫描 key 位于热点 Key 空间之外
+    let scanned = ops.iter().filter(|op| matches!(op, Op::Read(k) if *k > total_keys)).count();
+    assert!(scanned > 0, "default scan workload has no scan reads");
+}
+
+#[test]
+fn string_key_scan_follows_key_ids() {
+    let op: Op<usize, String> = Op::Scan("key:5".into(), 3);
+    assert_eq!(op.keys().collect::<Vec<_>>(), ["key:5", "key:6", "key:7"]);
+}
+
+#[test]
+fn scan_stops_at_end_of_id_space() {
+    let op: Op<usize, usize> = Op::Scan(usize::MAX - 1, 5);
+    assert_eq!(op.keys().collect::<Vec<_>>(), [usize::MAX - 1, usize::MAX]);
+}
+
+#[test]
+fn phased_workload_keeps_size() {
+    let mut cfg = WorkloadConfig::default();
+    (cfg.size, cfg.phases) = (1000, 3);
+    assert_eq!(generate(cfg).len(), 1000);
+}